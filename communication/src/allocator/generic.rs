@@ -8,7 +8,7 @@ use std::cell::RefCell;
 
 use crate::allocator::thread::ThreadBuilder;
 use crate::allocator::process::ProcessBuilder as TypedProcessBuilder;
-use crate::allocator::{Allocate, AllocateBuilder, Thread, Process};
+use crate::allocator::{Allocate, AllocateBuilder, BufferStats, ChannelBytes, Thread, Process};
 use crate::allocator::zero_copy::allocator_process::{ProcessBuilder, ProcessAllocator};
 use crate::allocator::zero_copy::allocator::{TcpBuilder, TcpAllocator};
 
@@ -73,6 +73,33 @@ impl Generic {
             Generic::ZeroCopy(z) => z.release(),
         }
     }
+    /// Reports for each process whether it is still connected.
+    pub fn peer_health(&self) -> Vec<bool> {
+        match self {
+            Generic::Thread(t) => t.peer_health(),
+            Generic::Process(p) => p.peer_health(),
+            Generic::ProcessBinary(pb) => pb.peer_health(),
+            Generic::ZeroCopy(z) => z.peer_health(),
+        }
+    }
+    /// Reports the buffers that receive data from other processes.
+    pub fn buffer_stats(&self) -> BufferStats {
+        match self {
+            Generic::Thread(t) => t.buffer_stats(),
+            Generic::Process(p) => p.buffer_stats(),
+            Generic::ProcessBinary(pb) => pb.buffer_stats(),
+            Generic::ZeroCopy(z) => z.buffer_stats(),
+        }
+    }
+    /// Reports the bytes each channel has exchanged with other processes.
+    pub fn channel_bytes(&self) -> Vec<ChannelBytes> {
+        match self {
+            Generic::Thread(t) => t.channel_bytes(),
+            Generic::Process(p) => p.channel_bytes(),
+            Generic::ProcessBinary(pb) => pb.channel_bytes(),
+            Generic::ZeroCopy(z) => z.channel_bytes(),
+        }
+    }
     fn events(&self) -> &Rc<RefCell<Vec<usize>>> {
         match self {
             Generic::Thread(ref t) => t.events(),
@@ -92,6 +119,9 @@ impl Allocate for Generic {
 
     fn receive(&mut self) { self.receive(); }
     fn release(&mut self) { self.release(); }
+    fn peer_health(&self) -> Vec<bool> { self.peer_health() }
+    fn buffer_stats(&self) -> BufferStats { self.buffer_stats() }
+    fn channel_bytes(&self) -> Vec<ChannelBytes> { self.channel_bytes() }
     fn events(&self) -> &Rc<RefCell<Vec<usize>>> { self.events() }
     fn await_events(&self, _duration: Option<std::time::Duration>) {
         match self {
@@ -32,6 +32,28 @@ pub trait AllocateBuilder : Send {
     fn build(self) -> Self::Allocator;
 }
 
+/// Counts of the buffers that receive data from other processes, as reported by `Allocate::buffer_stats`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct BufferStats {
+    /// The number of buffers allocated afresh.
+    pub allocated: usize,
+    /// The number of buffers reused once all references to their previous contents were dropped.
+    pub recycled: usize,
+    /// The bytes of the buffers currently retained for receiving.
+    pub retained_bytes: usize,
+}
+
+/// The bytes a channel has exchanged with other processes, as reported by `Allocate::channel_bytes`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct ChannelBytes {
+    /// The identifier of the channel.
+    pub channel: usize,
+    /// The bytes of serialized messages sent to workers of other processes.
+    pub sent: usize,
+    /// The bytes of serialized messages received from workers of other processes.
+    pub received: usize,
+}
+
 /// A type capable of allocating channels.
 ///
 /// There is some feature creep, in that this contains several convenience methods about the nature
@@ -80,6 +102,27 @@ pub trait Allocate {
     /// buffers, and can be a performance problem if invoked casually.
     fn release(&mut self) { }
 
+    /// Reports for each process whether it is still connected, indexed by process.
+    ///
+    /// A process is disconnected once a thread communicating with it has failed, because its
+    /// connection was closed or returned an error. Processes that stop responding while their
+    /// connection stays open are not detected. The process of this worker is always reported
+    /// connected, and by default all workers are assumed to share one process.
+    fn peer_health(&self) -> Vec<bool> { vec![true] }
+
+    /// Reports the buffers that receive data from other processes.
+    ///
+    /// The buffers belong to the receive threads of the process, and so every worker of a
+    /// process reports the same buffers. By default, there are none.
+    fn buffer_stats(&self) -> BufferStats { BufferStats::default() }
+
+    /// Reports the bytes each allocated channel has exchanged with other processes, in order
+    /// of channel identifier.
+    ///
+    /// Channels are reported until their receiving end is dropped, and messages are counted
+    /// by the size of their serialized data. By default, no channels are reported.
+    fn channel_bytes(&self) -> Vec<ChannelBytes> { Vec::new() }
+
     /// Constructs a pipeline channel from the worker to itself.
     ///
     /// By default, this method uses the thread-local channel constructor
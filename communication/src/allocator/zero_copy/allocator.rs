@@ -1,7 +1,8 @@
 //! Zero-copy allocator based on TCP.
 use std::rc::Rc;
-use std::cell::RefCell;
-use std::collections::{VecDeque, HashMap, hash_map::Entry};
+use std::cell::{Cell, RefCell};
+use std::sync::Arc;
+use std::collections::{BTreeMap, VecDeque, HashMap, hash_map::Entry};
 use crossbeam_channel::{Sender, Receiver};
 
 use bytes::arc::Bytes;
@@ -9,10 +10,11 @@ use bytes::arc::Bytes;
 use crate::networking::MessageHeader;
 
 use crate::{Allocate, Message, Data, Push, Pull};
-use crate::allocator::AllocateBuilder;
+use crate::allocator::{AllocateBuilder, BufferStats, ChannelBytes};
 use crate::allocator::canary::Canary;
 
 use super::bytes_exchange::{BytesPull, SendEndpoint, MergeQueue};
+use super::bytes_slab::SlabCounters;
 use super::push_pull::{Pusher, PullerInner};
 
 /// Builds an instance of a TcpAllocator.
@@ -25,14 +27,34 @@ pub struct TcpBuilder<A: AllocateBuilder> {
     inner:  A,
     index:  usize,                      // number out of peers
     peers:  usize,                      // number of peer allocators.
+    offsets: Vec<usize>,                // index of the first worker of each process.
     futures:   Vec<Receiver<MergeQueue>>,  // to receive queues to each network thread.
     promises:   Vec<Sender<MergeQueue>>,    // to send queues from each network thread.
+    slabs:      Vec<Arc<SlabCounters>>,     // buffer counts of each receive thread.
 }
 
 /// Creates a vector of builders, sharing appropriate state.
 ///
 /// `threads` is the number of workers in a single process, `processes` is the
-/// total number of processes.
+/// total number of processes, and each process is assumed to have the same number
+/// of workers. The returned tuple is as for `new_vector_from_counts`.
+pub fn new_vector<A: AllocateBuilder>(
+    allocators: Vec<A>,
+    my_process: usize,
+    processes: usize)
+-> (Vec<TcpBuilder<A>>,
+    Vec<Vec<Sender<MergeQueue>>>,
+    Vec<Vec<Receiver<MergeQueue>>>)
+{
+    let threads = vec![allocators.len(); processes];
+    new_vector_from_counts(allocators, my_process, &threads)
+}
+
+/// Creates a vector of builders, sharing appropriate state.
+///
+/// `threads[p]` is the number of workers in process `p`, which must equal `allocators.len()`
+/// for `my_process`. Workers are indexed contiguously in the order of their processes, so that
+/// process `p` hosts the workers starting at the sum of `threads[.. p]`.
 /// The returned tuple contains
 /// ```ignore
 /// (
@@ -41,14 +63,19 @@ pub struct TcpBuilder<A: AllocateBuilder> {
 ///   info to spawn ingress comm thresds,
 /// )
 /// ```
-pub fn new_vector<A: AllocateBuilder>(
+pub fn new_vector_from_counts<A: AllocateBuilder>(
     allocators: Vec<A>,
     my_process: usize,
-    processes: usize)
+    threads: &[usize])
 -> (Vec<TcpBuilder<A>>,
     Vec<Vec<Sender<MergeQueue>>>,
     Vec<Vec<Receiver<MergeQueue>>>)
 {
+    assert_eq!(threads[my_process], allocators.len());
+    let processes = threads.len();
+    let offsets = threads.iter().scan(0, |offset, count| { let start = *offset; *offset += count; Some(start) }).collect::<Vec<_>>();
+    let peers: usize = threads.iter().sum();
+    let my_offset = offsets[my_process];
     let threads = allocators.len();
 
     // For queues from worker threads to network threads, and vice versa.
@@ -64,10 +91,12 @@ pub fn new_vector<A: AllocateBuilder>(
         .map(|(index, ((inner, promises), futures))| {
             TcpBuilder {
                 inner,
-                index: my_process * threads + index,
-                peers: threads * processes,
+                index: my_offset + index,
+                peers,
+                offsets: offsets.clone(),
                 promises,
                 futures,
+                slabs: Vec::new(),
             }})
         .collect();
 
@@ -76,6 +105,12 @@ pub fn new_vector<A: AllocateBuilder>(
 
 impl<A: AllocateBuilder> TcpBuilder<A> {
 
+    /// Reports the buffer counts of the receive threads through the allocator.
+    pub(crate) fn with_slabs(mut self, slabs: Vec<Arc<SlabCounters>>) -> Self {
+        self.slabs = slabs;
+        self
+    }
+
     /// Builds a `TcpAllocator`, instantiating `Rc<RefCell<_>>` elements.
     pub fn build(self) -> TcpAllocator<A::Allocator> {
 
@@ -103,12 +138,15 @@ impl<A: AllocateBuilder> TcpBuilder<A> {
             inner: self.inner.build(),
             index: self.index,
             peers: self.peers,
+            offsets: self.offsets,
             canaries: Rc::new(RefCell::new(Vec::new())),
             channel_id_bound: None,
             staged: Vec::new(),
             sends,
             recvs,
             to_local: HashMap::new(),
+            slabs: self.slabs,
+            channel_bytes: BTreeMap::new(),
         }
     }
 }
@@ -120,6 +158,7 @@ pub struct TcpAllocator<A: Allocate> {
 
     index:      usize,                              // number out of peers
     peers:      usize,                              // number of peer allocators (for typed channel allocation).
+    offsets:    Vec<usize>,                         // index of the first worker of each process.
 
     staged:     Vec<Bytes>,                         // staging area for incoming Bytes
     canaries:   Rc<RefCell<Vec<usize>>>,
@@ -130,11 +169,34 @@ pub struct TcpAllocator<A: Allocate> {
     sends:      Vec<Rc<RefCell<SendEndpoint<MergeQueue>>>>,     // sends[x] -> goes to process x.
     recvs:      Vec<MergeQueue>,                                // recvs[x] <- from process x.
     to_local:   HashMap<usize, Rc<RefCell<VecDeque<Bytes>>>>,   // to worker-local typed pullers.
+    slabs:      Vec<Arc<SlabCounters>>,                         // buffer counts of each receive thread.
+    channel_bytes: BTreeMap<usize, (Rc<Cell<usize>>, Rc<Cell<usize>>)>, // bytes sent and received, by channel.
 }
 
 impl<A: Allocate> Allocate for TcpAllocator<A> {
     fn index(&self) -> usize { self.index }
     fn peers(&self) -> usize { self.peers }
+    fn peer_health(&self) -> Vec<bool> {
+        let my_process = self.offsets.iter().rposition(|offset| *offset <= self.index).expect("worker index out of range");
+        let mut health: Vec<bool> = self.recvs.iter().zip(self.sends.iter()).map(|(recv, send)| {
+            !recv.is_poisoned() && !send.borrow().target().is_poisoned()
+        }).collect();
+        health.insert(my_process, true);
+        health
+    }
+    fn channel_bytes(&self) -> Vec<ChannelBytes> {
+        self.channel_bytes
+            .iter()
+            .map(|(channel, (sent, received))| ChannelBytes { channel: *channel, sent: sent.get(), received: received.get() })
+            .collect()
+    }
+    fn buffer_stats(&self) -> BufferStats {
+        let mut stats = BufferStats::default();
+        for slab in self.slabs.iter() {
+            slab.add_to(&mut stats);
+        }
+        stats
+    }
     fn allocate<T: Data>(&mut self, identifier: usize) -> (Vec<Box<dyn Push<Message<T>>>>, Box<dyn Pull<Message<T>>>) {
 
         // Assume and enforce in-order identifier allocation.
@@ -146,16 +208,20 @@ impl<A: Allocate> Allocate for TcpAllocator<A> {
         // Result list of boxed pushers.
         let mut pushes = Vec::<Box<dyn Push<Message<T>>>>::new();
 
+        // Counters of the bytes the channel sends and receives.
+        let (sent, received) = self.channel_bytes.entry(identifier).or_default().clone();
+
         // Inner exchange allocations.
-        let inner_peers = self.inner.peers();
         let (mut inner_sends, inner_recv) = self.inner.allocate(identifier);
 
+        let process_of = |index: usize| self.offsets.iter().rposition(|offset| *offset <= index).expect("worker index out of range");
+        let my_process = process_of(self.index);
+
         for target_index in 0 .. self.peers() {
 
-            // TODO: crappy place to hardcode this rule.
-            let mut process_id = target_index / inner_peers;
+            let mut process_id = process_of(target_index);
 
-            if process_id == self.index / inner_peers {
+            if process_id == my_process {
                 pushes.push(inner_sends.remove(0));
             }
             else {
@@ -169,8 +235,8 @@ impl<A: Allocate> Allocate for TcpAllocator<A> {
                 };
 
                 // create, box, and stash new process_binary pusher.
-                if process_id > self.index / inner_peers { process_id -= 1; }
-                pushes.push(Box::new(Pusher::new(header, self.sends[process_id].clone())));
+                if process_id > my_process { process_id -= 1; }
+                pushes.push(Box::new(Pusher::new(header, self.sends[process_id].clone()).with_counter(sent.clone())));
             }
         }
 
@@ -182,7 +248,7 @@ impl<A: Allocate> Allocate for TcpAllocator<A> {
 
         use crate::allocator::counters::Puller as CountPuller;
         let canary = Canary::new(identifier, self.canaries.clone());
-        let puller = Box::new(CountPuller::new(PullerInner::new(inner_recv, channel, canary), identifier, self.events().clone()));
+        let puller = Box::new(CountPuller::new(PullerInner::new(inner_recv, channel, canary).with_counter(received), identifier, self.events().clone()));
 
         (pushes, puller, )
     }
@@ -198,6 +264,7 @@ impl<A: Allocate> Allocate for TcpAllocator<A> {
             self.to_local
                 .remove(&dropped_channel)
                 .expect("non-existent channel dropped");
+            self.channel_bytes.remove(&dropped_channel);
             // Borrowed channels may be non-empty, if the dataflow was forcibly
             // dropped. The contract is that if a dataflow is dropped, all other
             // workers will drop the dataflow too, without blocking indefinitely
@@ -208,7 +275,14 @@ impl<A: Allocate> Allocate for TcpAllocator<A> {
 
         self.inner.receive();
 
-        for recv in self.recvs.iter_mut() {
+        let my_process = self.offsets.iter().rposition(|offset| *offset <= self.index).expect("worker index out of range");
+        for (index, recv) in self.recvs.iter_mut().enumerate() {
+            // The receive thread poisons its queue when the connection is closed or fails. Stalled
+            // connections are not detected, as nothing distinguishes them from idle ones.
+            if recv.is_poisoned() {
+                let process = if index < my_process { index } else { index + 1 };
+                panic!("timely communication error: connection to process {} failed", process);
+            }
             recv.drain_into(&mut self.staged);
         }
 
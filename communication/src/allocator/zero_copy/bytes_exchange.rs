@@ -47,6 +47,10 @@ impl MergeQueue {
         if self.panic.load(Ordering::SeqCst) { panic!("MergeQueue poisoned."); }
         Arc::strong_count(&self.queue) == 1 && self.queue.lock().expect("Failed to acquire lock").is_empty()
     }
+    /// Indicates that a thread holding a handle to the queue has panicked.
+    pub fn is_poisoned(&self) -> bool {
+        self.panic.load(Ordering::SeqCst)
+    }
 }
 
 impl BytesPush for MergeQueue {
@@ -170,6 +174,10 @@ impl<P: BytesPush> SendEndpoint<P> {
     pub fn publish(&mut self) {
         self.send_buffer();
     }
+    /// The target into which bytes are sent.
+    pub fn target(&self) -> &P {
+        &self.send
+    }
 }
 
 impl<P: BytesPush> Drop for SendEndpoint<P> {
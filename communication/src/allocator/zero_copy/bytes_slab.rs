@@ -1,18 +1,28 @@
 //! A large binary allocation for writing and sharing.
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use bytes::arc::Bytes;
 
+use crate::allocator::BufferStats;
+
 /// A large binary allocation for writing and sharing.
 ///
 /// A bytes slab wraps a `Bytes` and maintains a valid (written) length, and supports writing after
 /// this valid length, and extracting `Bytes` up to this valid length. Extracted bytes are enqueued
 /// and checked for uniqueness in order to recycle them (once all shared references are dropped).
+///
+/// The slab counts the buffers it allocates afresh and those it recycles, which allows the
+/// effectiveness of recycling to be observed through `allocated` and `recycled`. The receive
+/// threads of a cluster publish these counts, which workers report with `Allocate::buffer_stats`.
 pub struct BytesSlab {
     buffer:         Bytes,                      // current working buffer.
     in_progress:    Vec<Option<Bytes>>,         // buffers shared with workers.
     stash:          Vec<Bytes>,                 // reclaimed and resuable buffers.
     shift:          usize,                      // current buffer allocation size.
     valid:          usize,                      // buffer[..valid] are valid bytes.
+    allocated:      usize,                      // number of freshly allocated buffers.
+    recycled:       usize,                      // number of reused buffers.
 }
 
 impl BytesSlab {
@@ -24,8 +34,25 @@ impl BytesSlab {
             stash: Vec::new(),
             shift,
             valid: 0,
+            allocated: 1,
+            recycled: 0,
         }
     }
+    /// The number of buffers allocated afresh, including the initial buffer.
+    pub fn allocated(&self) -> usize {
+        self.allocated
+    }
+    /// The number of buffers reused once all references to their previous contents were dropped.
+    pub fn recycled(&self) -> usize {
+        self.recycled
+    }
+    /// The bytes of the buffers the slab retains, either in use or stashed for reuse.
+    ///
+    /// Buffers shared with workers are retained until they can be reused, unless the slab has
+    /// moved to larger buffers, after which they are released once all references are dropped.
+    pub fn retained_bytes(&self) -> usize {
+        (1 + self.in_progress.len() + self.stash.len()) << self.shift
+    }
     /// The empty region of the slab.
     pub fn empty(&mut self) -> &mut [u8] {
         &mut self.buffer[self.valid..]
@@ -82,7 +109,16 @@ impl BytesSlab {
                 self.in_progress.retain(|x| x.is_some());
             }
 
-            let new_buffer = self.stash.pop().unwrap_or_else(|| Bytes::from(vec![0; 1 << self.shift].into_boxed_slice()));
+            let new_buffer = match self.stash.pop() {
+                Some(buffer) => {
+                    self.recycled += 1;
+                    buffer
+                },
+                None => {
+                    self.allocated += 1;
+                    Bytes::from(vec![0; 1 << self.shift].into_boxed_slice())
+                },
+            };
             let old_buffer = ::std::mem::replace(&mut self.buffer, new_buffer);
 
             self.buffer[.. self.valid].copy_from_slice(&old_buffer[.. self.valid]);
@@ -91,4 +127,26 @@ impl BytesSlab {
             }
         }
     }
-}
\ No newline at end of file
+}
+/// Counts of the buffers of a slab, published by the thread that owns it for others to read.
+#[derive(Debug, Default)]
+pub(crate) struct SlabCounters {
+    allocated: AtomicUsize,
+    recycled: AtomicUsize,
+    retained_bytes: AtomicUsize,
+}
+
+impl SlabCounters {
+    /// Records the current counts of `slab`.
+    pub(crate) fn publish(&self, slab: &BytesSlab) {
+        self.allocated.store(slab.allocated(), Ordering::Relaxed);
+        self.recycled.store(slab.recycled(), Ordering::Relaxed);
+        self.retained_bytes.store(slab.retained_bytes(), Ordering::Relaxed);
+    }
+    /// Adds the most recently published counts to `stats`.
+    pub(crate) fn add_to(&self, stats: &mut BufferStats) {
+        stats.allocated += self.allocated.load(Ordering::Relaxed);
+        stats.recycled += self.recycled.load(Ordering::Relaxed);
+        stats.retained_bytes += self.retained_bytes.load(Ordering::Relaxed);
+    }
+}
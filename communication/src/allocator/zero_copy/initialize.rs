@@ -3,9 +3,10 @@
 use std::sync::Arc;
 // use crate::allocator::Process;
 use crate::allocator::process::ProcessBuilder;
-use crate::networking::create_sockets;
-use super::tcp::{send_loop, recv_loop};
-use super::allocator::{TcpBuilder, new_vector};
+use crate::networking::{create_sockets, exchange_thread_counts, Compression};
+use super::bytes_slab::SlabCounters;
+use super::tcp::{send_loop, recv_loop_counted};
+use super::allocator::{TcpBuilder, new_vector_from_counts};
 use super::stream::Stream;
 
 /// Join handles for send and receive threads.
@@ -34,16 +35,24 @@ use crate::logging::{CommunicationSetup, CommunicationEvent};
 use logging_core::Logger;
 
 /// Initializes network connections
+///
+/// The processes may have different numbers of worker `threads`, which they exchange once
+/// connected. Workers are indexed contiguously in the order of their processes.
 pub fn initialize_networking(
     addresses: Vec<String>,
     my_index: usize,
     threads: usize,
     noisy: bool,
+    compression: Option<Compression>,
     log_sender: Box<dyn Fn(CommunicationSetup)->Option<Logger<CommunicationEvent, CommunicationSetup>>+Send+Sync>)
 -> ::std::io::Result<(Vec<TcpBuilder<ProcessBuilder>>, CommsGuard)>
 {
-    let sockets = create_sockets(addresses, my_index, noisy)?;
-    initialize_networking_from_sockets(sockets, my_index, threads, log_sender)
+    if let Some(compression) = compression {
+        compression.check_available()?;
+    }
+    let mut sockets = create_sockets(addresses, my_index, noisy, compression)?;
+    let threads = exchange_thread_counts(&mut sockets, my_index, threads)?;
+    initialize_networking_from_counts(sockets, my_index, threads, compression, log_sender)
 }
 
 /// Initialize send and recv threads from sockets.
@@ -54,12 +63,45 @@ pub fn initialize_networking(
 /// It is important that the `sockets` argument contain sockets for each remote process, in order, and
 /// with position `my_index` set to `None`.
 pub fn initialize_networking_from_sockets<S: Stream + 'static>(
-    mut sockets: Vec<Option<S>>,
+    sockets: Vec<Option<S>>,
+    my_index: usize,
+    threads: usize,
+    log_sender: Box<dyn Fn(CommunicationSetup)->Option<Logger<CommunicationEvent, CommunicationSetup>>+Send+Sync>)
+-> ::std::io::Result<(Vec<TcpBuilder<ProcessBuilder>>, CommsGuard)>
+{
+    initialize_networking_from_sockets_compressed(sockets, my_index, threads, None, log_sender)
+}
+
+/// Initialize send and recv threads from sockets, compressing the data sent through them.
+///
+/// This method behaves as `initialize_networking_from_sockets`, except that each message's data
+/// is compressed with `compression`. It is the caller's responsibility to ensure that the remote
+/// processes use the same compression.
+pub fn initialize_networking_from_sockets_compressed<S: Stream + 'static>(
+    sockets: Vec<Option<S>>,
     my_index: usize,
     threads: usize,
+    compression: Option<Compression>,
+    log_sender: Box<dyn Fn(CommunicationSetup)->Option<Logger<CommunicationEvent, CommunicationSetup>>+Send+Sync>)
+-> ::std::io::Result<(Vec<TcpBuilder<ProcessBuilder>>, CommsGuard)>
+{
+    let threads = vec![threads; sockets.len()];
+    initialize_networking_from_counts(sockets, my_index, threads, compression, log_sender)
+}
+
+/// Initialize send and recv threads from sockets, where process `p` has `threads[p]` workers.
+fn initialize_networking_from_counts<S: Stream + 'static>(
+    mut sockets: Vec<Option<S>>,
+    my_index: usize,
+    threads: Vec<usize>,
+    compression: Option<Compression>,
     log_sender: Box<dyn Fn(CommunicationSetup)->Option<Logger<CommunicationEvent, CommunicationSetup>>+Send+Sync>)
 -> ::std::io::Result<(Vec<TcpBuilder<ProcessBuilder>>, CommsGuard)>
 {
+    if let Some(compression) = compression {
+        compression.check_available()?;
+    }
+
     // Sockets are expected to be blocking,
     for socket in sockets.iter_mut() {
         if let Some(socket) = socket {
@@ -68,10 +110,15 @@ pub fn initialize_networking_from_sockets<S: Stream + 'static>(
     }
 
     let log_sender = Arc::new(log_sender);
-    let processes = sockets.len();
+    let worker_offset: usize = threads[.. my_index].iter().sum();
+
+    let process_allocators = crate::allocator::process::Process::new_vector(threads[my_index]);
+    let (builders, promises, futures) = new_vector_from_counts(process_allocators, my_index, &threads);
 
-    let process_allocators = crate::allocator::process::Process::new_vector(threads);
-    let (builders, promises, futures) = new_vector(process_allocators, my_index, processes);
+    // Counts of the receive buffers of each remote process, reported by every local worker.
+    let slabs: Vec<Arc<SlabCounters>> = sockets.iter().flatten().map(|_| Arc::new(SlabCounters::default())).collect();
+    let builders = builders.into_iter().map(|builder| builder.with_slabs(slabs.clone())).collect();
+    let mut slabs_iter = slabs.into_iter();
 
     let mut promises_iter = promises.into_iter();
     let mut futures_iter = futures.into_iter();
@@ -97,13 +144,14 @@ pub fn initialize_networking_from_sockets<S: Stream + 'static>(
                         remote: Some(index),
                     });
 
-                    send_loop(stream, remote_recv, my_index, index, logger);
+                    send_loop(stream, remote_recv, my_index, index, compression, logger);
                 })?;
 
             send_guards.push(join_guard);
         }
 
         let remote_send = futures_iter.next().unwrap();
+        let slab = slabs_iter.next().unwrap();
 
         {
             // let remote_sends = remote_sends.clone();
@@ -118,7 +166,7 @@ pub fn initialize_networking_from_sockets<S: Stream + 'static>(
                         sender: false,
                         remote: Some(index),
                     });
-                    recv_loop(stream, remote_send, threads * my_index, my_index, index, logger);
+                    recv_loop_counted(stream, remote_send, worker_offset, my_index, index, compression, &slab, logger);
                 })?;
 
             recv_guards.push(join_guard);
@@ -127,3 +175,78 @@ pub fn initialize_networking_from_sockets<S: Stream + 'static>(
 
     Ok((builders, CommsGuard { send_guards, recv_guards }))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use crate::{initialize_from, Allocate, Message};
+    use crate::allocator::zero_copy::stream::MemoryStream;
+    use crate::networking::exchange_thread_counts;
+    use super::initialize_networking_from_counts;
+
+    /// Connects processes with different numbers of threads through in-memory streams, and checks
+    /// that workers agree on the number of peers, are indexed contiguously by process, and
+    /// receive exactly the messages sent to their index.
+    #[test]
+    fn uneven_thread_counts() {
+        let threads = vec![3, 1, 2];
+        let processes = threads.len();
+        let peers: usize = threads.iter().sum();
+
+        let mut sockets: Vec<Vec<Option<MemoryStream>>> = (0 .. processes).map(|_| (0 .. processes).map(|_| None).collect()).collect();
+        for source in 0 .. processes {
+            for target in source + 1 .. processes {
+                let (one, two) = MemoryStream::pair();
+                sockets[source][target] = Some(one);
+                sockets[target][source] = Some(two);
+            }
+        }
+
+        // Each process only learns the others' counts through the exchange, as in `initialize_networking`.
+        let exchanges: Vec<_> = sockets.into_iter().enumerate().map(|(process, mut sockets)| {
+            let count = threads[process];
+            thread::spawn(move || {
+                let counts = exchange_thread_counts(&mut sockets, process, count).unwrap();
+                (sockets, counts)
+            })
+        }).collect();
+
+        let mut builders = Vec::with_capacity(peers);
+        let mut guards = Vec::with_capacity(processes);
+        for (process, exchange) in exchanges.into_iter().enumerate() {
+            let (sockets, counts) = exchange.join().unwrap();
+            assert_eq!(counts, threads);
+            let (stuff, guard) = initialize_networking_from_counts(sockets, process, counts, None, Box::new(|_| None)).unwrap();
+            builders.extend(stuff);
+            guards.push(guard);
+        }
+
+        let guards = initialize_from(builders, Box::new(guards), move |mut allocator| {
+            let index = allocator.index();
+            assert_eq!(allocator.peers(), peers);
+            let (mut senders, mut receiver) = allocator.allocate(0);
+            for (target, sender) in senders.iter_mut().enumerate() {
+                sender.send(Message::from_typed((index, target)));
+                sender.done();
+            }
+
+            let mut sources = Vec::new();
+            while sources.len() < peers {
+                allocator.receive();
+                while let Some(message) = receiver.recv() {
+                    let (source, target) = *message;
+                    assert_eq!(target, index);
+                    sources.push(source);
+                }
+                allocator.release();
+            }
+            sources.sort();
+            assert_eq!(sources, (0 .. peers).collect::<Vec<_>>());
+            index
+        }).unwrap();
+
+        // Workers are indexed contiguously, in the order of their processes.
+        let indices: Vec<usize> = guards.join().into_iter().map(|result| result.unwrap()).collect();
+        assert_eq!(indices, (0 .. peers).collect::<Vec<_>>());
+    }
+}
@@ -1,7 +1,7 @@
 //! Push and Pull implementations wrapping serialized data.
 
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 
 use bytes::arc::Bytes;
@@ -21,6 +21,7 @@ use super::bytes_exchange::{BytesPush, SendEndpoint};
 pub struct Pusher<T, P: BytesPush> {
     header:     MessageHeader,
     sender:     Rc<RefCell<SendEndpoint<P>>>,
+    sent:       Rc<Cell<usize>>,
     phantom:    ::std::marker::PhantomData<T>,
 }
 
@@ -30,9 +31,15 @@ impl<T, P: BytesPush> Pusher<T, P> {
         Pusher {
             header,
             sender,
+            sent:       Rc::new(Cell::new(0)),
             phantom:    ::std::marker::PhantomData,
         }
     }
+    /// Adds the bytes of each serialized message sent, excluding its header, to `sent`.
+    pub fn with_counter(mut self, sent: Rc<Cell<usize>>) -> Self {
+        self.sent = sent;
+        self
+    }
 }
 
 impl<T:Data, P: BytesPush> Push<Message<T>> for Pusher<T, P> {
@@ -46,6 +53,10 @@ impl<T:Data, P: BytesPush> Push<Message<T>> for Pusher<T, P> {
             header.length = element.length_in_bytes();
             assert!(header.length > 0);
 
+            // check that the element survives serialization, at the point of sending it.
+            #[cfg(all(feature = "validate-serde", debug_assertions))]
+            element.validate_round_trip();
+
             // acquire byte buffer and write header, element.
             let mut borrow = self.sender.borrow_mut();
             {
@@ -56,6 +67,7 @@ impl<T:Data, P: BytesPush> Push<Message<T>> for Pusher<T, P> {
                 element.into_bytes(writer);
             }
             borrow.make_valid(header.required_bytes());
+            self.sent.set(self.sent.get() + header.length);
         }
     }
 }
@@ -107,6 +119,7 @@ pub struct PullerInner<T> {
     _canary: Canary,
     current: Option<Message<T>>,
     receiver: Rc<RefCell<VecDeque<Bytes>>>,     // source of serialized buffers
+    received: Rc<Cell<usize>>,                  // bytes of the buffers received
 }
 
 impl<T:Data> PullerInner<T> {
@@ -117,8 +130,14 @@ impl<T:Data> PullerInner<T> {
             _canary,
             current: None,
             receiver,
+            received: Rc::new(Cell::new(0)),
         }
     }
+    /// Adds the bytes of each serialized message received, excluding its header, to `received`.
+    pub fn with_counter(mut self, received: Rc<Cell<usize>>) -> Self {
+        self.received = received;
+        self
+    }
 }
 
 impl<T:Data> Pull<Message<T>> for PullerInner<T> {
@@ -130,11 +149,15 @@ impl<T:Data> Pull<Message<T>> for PullerInner<T> {
             inner
         }
         else {
+            let received = &self.received;
             self.current =
             self.receiver
                 .borrow_mut()
                 .pop_front()
-                .map(|bytes| unsafe { Message::from_bytes(bytes) });
+                .map(|bytes| {
+                    received.set(received.get() + bytes.len());
+                    unsafe { Message::from_bytes(bytes) }
+                });
 
             &mut self.current
         }
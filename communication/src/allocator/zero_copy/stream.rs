@@ -1,7 +1,9 @@
 //! Abstractions over network streams.
 
 use std::io;
+use std::collections::VecDeque;
 use std::net::{TcpStream, Shutdown};
+use std::sync::{Arc, Condvar, Mutex};
 #[cfg(unix)]
 use std::os::unix::net::UnixStream;
 
@@ -45,3 +47,94 @@ impl Stream for UnixStream {
         self.shutdown(how)
     }
 }
+
+/// An in-memory stream, connected to another in-memory stream.
+///
+/// Bytes written to one end of a pair are read from the other, without any network. Reads
+/// block until bytes are available, or return zero once the other end has shut down writing.
+/// The streams are always blocking, and do not bound the bytes buffered between them.
+#[derive(Clone)]
+pub struct MemoryStream {
+    incoming: Arc<Pipe>,
+    outgoing: Arc<Pipe>,
+}
+
+/// Bytes in transit in one direction, and whether the writer has shut down.
+#[derive(Default)]
+struct Pipe {
+    state: Mutex<(VecDeque<u8>, bool)>,
+    available: Condvar,
+}
+
+impl Pipe {
+    fn close(&self) {
+        self.state.lock().expect("poisoned pipe").1 = true;
+        self.available.notify_all();
+    }
+}
+
+impl MemoryStream {
+    /// Creates a pair of connected in-memory streams.
+    pub fn pair() -> (MemoryStream, MemoryStream) {
+        let forward = Arc::new(Pipe::default());
+        let reverse = Arc::new(Pipe::default());
+        let one = MemoryStream { incoming: forward.clone(), outgoing: reverse.clone() };
+        let two = MemoryStream { incoming: reverse, outgoing: forward };
+        (one, two)
+    }
+}
+
+impl io::Read for MemoryStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut state = self.incoming.state.lock().expect("poisoned pipe");
+        while state.0.is_empty() && !state.1 {
+            state = self.incoming.available.wait(state).expect("poisoned pipe");
+        }
+        let length = ::std::cmp::min(buf.len(), state.0.len());
+        for (target, source) in buf.iter_mut().zip(state.0.drain(.. length)) {
+            *target = source;
+        }
+        Ok(length)
+    }
+}
+
+impl io::Write for MemoryStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.outgoing.state.lock().expect("poisoned pipe");
+        if state.1 {
+            return Err(io::Error::new(io::ErrorKind::BrokenPipe, "stream shut down"));
+        }
+        state.0.extend(buf.iter().cloned());
+        self.outgoing.available.notify_all();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Stream for MemoryStream {
+    fn try_clone(&self) -> io::Result<Self> {
+        Ok(self.clone())
+    }
+
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        if nonblocking {
+            Err(io::Error::new(io::ErrorKind::Other, "in-memory streams are always blocking"))
+        }
+        else {
+            Ok(())
+        }
+    }
+
+    fn shutdown(&self, how: Shutdown) -> io::Result<()> {
+        if how != Shutdown::Read {
+            self.outgoing.close();
+        }
+        if how != Shutdown::Write {
+            self.incoming.close();
+        }
+        Ok(())
+    }
+}
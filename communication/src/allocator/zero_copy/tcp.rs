@@ -3,9 +3,11 @@
 use std::io::{self, Write};
 use crossbeam_channel::{Sender, Receiver};
 
-use crate::networking::MessageHeader;
+use bytes::arc::Bytes;
 
-use super::bytes_slab::BytesSlab;
+use crate::networking::{Compression, MessageHeader};
+
+use super::bytes_slab::{BytesSlab, SlabCounters};
 use super::bytes_exchange::MergeQueue;
 use super::stream::Stream;
 
@@ -29,12 +31,33 @@ fn tcp_panic(context: &'static str, cause: io::Error) -> ! {
 /// If the stream ends without being shut down, or if reading from the stream fails, the
 /// receive thread panics with a message that starts with "timely communication error:"
 /// in an attempt to take down the computation and cause the failures to cascade.
+///
+/// If `compression` is set, the data of each message is decompressed before it is handed off,
+/// which requires copying it out of the shared receive buffer.
 pub fn recv_loop<S>(
+    reader: S,
+    targets: Vec<Receiver<MergeQueue>>,
+    worker_offset: usize,
+    process: usize,
+    remote: usize,
+    compression: Option<Compression>,
+    logger: Option<Logger<CommunicationEvent, CommunicationSetup>>)
+where
+    S: Stream,
+{
+    recv_loop_counted(reader, targets, worker_offset, process, remote, compression, &SlabCounters::default(), logger)
+}
+
+/// Reads from a stream as `recv_loop`, publishing the counts of its receive buffers to `slab`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn recv_loop_counted<S>(
     mut reader: S,
     targets: Vec<Receiver<MergeQueue>>,
     worker_offset: usize,
     process: usize,
     remote: usize,
+    compression: Option<Compression>,
+    slab: &SlabCounters,
     mut logger: Option<Logger<CommunicationEvent, CommunicationSetup>>)
 where
     S: Stream,
@@ -63,6 +86,7 @@ where
     while active {
 
         buffer.ensure_capacity(1);
+        slab.publish(&buffer);
 
         assert!(!buffer.empty().is_empty());
 
@@ -85,7 +109,7 @@ where
 
             // TODO: Consolidate message sequences sent to the same worker?
             let peeled_bytes = header.required_bytes();
-            let bytes = buffer.extract(peeled_bytes);
+            let mut bytes = buffer.extract(peeled_bytes);
 
             // Record message receipt.
             logger.as_mut().map(|logger| {
@@ -93,6 +117,16 @@ where
             });
 
             if header.length > 0 {
+                if let Some(compression) = compression {
+                    let data = compression
+                        .decompress(&bytes[::std::mem::size_of::<MessageHeader>() ..])
+                        .unwrap_or_else(|e| tcp_panic("decompressing data", e));
+                    let header = MessageHeader { length: data.len(), ..header };
+                    let mut message = Vec::with_capacity(header.required_bytes());
+                    header.write_to(&mut message).unwrap_or_else(|e| tcp_panic("decompressing data", e));
+                    message.extend_from_slice(&data[..]);
+                    bytes = Bytes::from(message);
+                }
                 stageds[header.target - worker_offset].push(bytes);
             }
             else {
@@ -128,12 +162,16 @@ where
 /// If writing to the stream fails, the send thread panics with a message that starts with
 /// "timely communication error:" in an attempt to take down the computation and cause the
 /// failures to cascade.
+///
+/// If `compression` is set, the data of each message is compressed separately, and its header
+/// records the compressed length.
 pub fn send_loop<S: Stream>(
     // TODO: Maybe we don't need BufWriter with consolidation in writes.
     writer: S,
     sources: Vec<Sender<MergeQueue>>,
     process: usize,
     remote: usize,
+    compression: Option<Compression>,
     mut logger: Option<Logger<CommunicationEvent, CommunicationSetup>>)
 {
 
@@ -184,7 +222,21 @@ pub fn send_loop<S: Stream>(
                     }
                 });
 
-                writer.write_all(&bytes[..]).unwrap_or_else(|e| tcp_panic("writing data", e));
+                if let Some(compression) = compression {
+                    let mut offset = 0;
+                    while let Some(header) = MessageHeader::try_read(&mut bytes[offset..]) {
+                        let start = offset + ::std::mem::size_of::<MessageHeader>();
+                        let end = offset + header.required_bytes();
+                        let data = compression.compress(&bytes[start .. end]);
+                        let header = MessageHeader { length: data.len(), ..header };
+                        header.write_to(&mut writer).unwrap_or_else(|e| tcp_panic("writing data", e));
+                        writer.write_all(&data[..]).unwrap_or_else(|e| tcp_panic("writing data", e));
+                        offset = end;
+                    }
+                }
+                else {
+                    writer.write_all(&bytes[..]).unwrap_or_else(|e| tcp_panic("writing data", e));
+                }
             }
         }
     }
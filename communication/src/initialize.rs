@@ -12,7 +12,9 @@ use std::any::Any;
 use crate::allocator::thread::ThreadBuilder;
 use crate::allocator::{AllocateBuilder, Process, Generic, GenericBuilder};
 use crate::allocator::zero_copy::allocator_process::ProcessBuilder;
-use crate::allocator::zero_copy::initialize::initialize_networking;
+use crate::allocator::zero_copy::initialize::{initialize_networking, initialize_networking_from_sockets};
+use crate::allocator::zero_copy::stream::MemoryStream;
+use crate::networking::Compression;
 
 use crate::logging::{CommunicationSetup, CommunicationEvent};
 use logging_core::Logger;
@@ -29,7 +31,7 @@ pub enum Config {
     ProcessBinary(usize),
     /// Expect multiple processes.
     Cluster {
-        /// Number of per-process worker threads
+        /// Number of worker threads in this process, which may differ between processes
         threads: usize,
         /// Identity of this process
         process: usize,
@@ -37,9 +39,22 @@ pub enum Config {
         addresses: Vec<String>,
         /// Verbosely report connection process
         report: bool,
+        /// Compression of the data exchanged between processes, which all processes must agree on
+        compression: Option<Compression>,
         /// Closure to create a new logger for a communication thread
         log_fn: Box<dyn Fn(CommunicationSetup) -> Option<Logger<CommunicationEvent, CommunicationSetup>> + Send + Sync>,
-    }
+    },
+    /// Simulate multiple processes within this process, connected by in-memory streams.
+    ///
+    /// Each simulated process has its own send and receive threads, and data between them is
+    /// serialized exactly as for `Cluster`, but is exchanged in memory rather than over TCP.
+    /// Workers of all processes run in this process, indexed contiguously by process.
+    ClusterInMemory {
+        /// Number of simulated processes
+        processes: usize,
+        /// Number of worker threads in each simulated process
+        threads: usize,
+    },
 }
 
 impl Debug for Config {
@@ -48,14 +63,20 @@ impl Debug for Config {
             Config::Thread => write!(f, "Config::Thread()"),
             Config::Process(n) => write!(f, "Config::Process({})", n),
             Config::ProcessBinary(n) => write!(f, "Config::ProcessBinary({})", n),
-            Config::Cluster { threads, process, addresses, report, .. } => f
+            Config::Cluster { threads, process, addresses, report, compression, .. } => f
                 .debug_struct("Config::Cluster")
                 .field("threads", threads)
                 .field("process", process)
                 .field("addresses", addresses)
                 .field("report", report)
+                .field("compression", compression)
                 // TODO: Use `.finish_non_exhaustive()` after rust/#67364 lands
-                .finish()
+                .finish(),
+            Config::ClusterInMemory { processes, threads } => f
+                .debug_struct("Config::ClusterInMemory")
+                .field("processes", processes)
+                .field("threads", threads)
+                .finish(),
         }
     }
 }
@@ -75,13 +96,18 @@ impl Config {
         opts.optopt("w", "threads", "number of per-process worker threads", "NUM");
         opts.optopt("p", "process", "identity of this process", "IDX");
         opts.optopt("n", "processes", "number of processes", "NUM");
-        opts.optopt("h", "hostfile", "text file whose lines are process addresses", "FILE");
+        opts.optopt("h", "hostfile", "text file whose lines are process addresses (host or host:port)", "FILE");
         opts.optflag("r", "report", "reports connection progress");
         opts.optflag("z", "zerocopy", "enable zero-copy for intra-process communication");
     }
 
     /// Instantiates a configuration based upon the parsed options in `matches`.
     ///
+    /// Each line of a hostfile is the address of the process of the same index, either as
+    /// `host:port` or as `host`, which uses port 2101 plus the index of the process, as when
+    /// no hostfile is supplied. IPv6 hosts must be enclosed in brackets, as in `[::1]:2101`.
+    /// A malformed line is reported with its line number.
+    ///
     /// The `matches` object must have been constructed from a
     /// [`getopts::Options`] which contained at least the options installed by
     /// [`Self::install_options`].
@@ -101,8 +127,11 @@ impl Config {
             if let Some(hosts) = matches.opt_str("h") {
                 let file = ::std::fs::File::open(hosts.clone()).map_err(|e| e.to_string())?;
                 let reader = ::std::io::BufReader::new(file);
-                for line in reader.lines().take(processes) {
-                    addresses.push(line.map_err(|e| e.to_string())?);
+                for (index, line) in reader.lines().take(processes).enumerate() {
+                    let line = line.map_err(|e| e.to_string())?;
+                    let address = parse_address(&line, 2101 + index)
+                        .map_err(|e| format!("{} line {}: {}", hosts, index + 1, e))?;
+                    addresses.push(address);
                 }
                 if addresses.len() < processes {
                     return Err(format!("could only read {} addresses from {}, but -n: {}", addresses.len(), hosts, processes));
@@ -120,6 +149,7 @@ impl Config {
                 process,
                 addresses,
                 report,
+                compression: None,
                 log_fn: Box::new( | _ | None),
             })
         } else if threads > 1 {
@@ -159,18 +189,76 @@ impl Config {
             Config::ProcessBinary(threads) => {
                 Ok((ProcessBuilder::new_vector(threads).into_iter().map(|x| GenericBuilder::ProcessBinary(x)).collect(), Box::new(())))
             },
-            Config::Cluster { threads, process, addresses, report, log_fn } => {
-                match initialize_networking(addresses, process, threads, report, log_fn) {
+            Config::Cluster { threads, process, addresses, report, compression, log_fn } => {
+                match initialize_networking(addresses, process, threads, report, compression, log_fn) {
                     Ok((stuff, guard)) => {
                         Ok((stuff.into_iter().map(|x| GenericBuilder::ZeroCopy(x)).collect(), Box::new(guard)))
                     },
                     Err(err) => Err(format!("failed to initialize networking: {}", err))
                 }
             },
+            Config::ClusterInMemory { processes, threads } => {
+                let mut sockets: Vec<Vec<Option<MemoryStream>>> = (0 .. processes).map(|_| (0 .. processes).map(|_| None).collect()).collect();
+                for source in 0 .. processes {
+                    for target in source + 1 .. processes {
+                        let (one, two) = MemoryStream::pair();
+                        sockets[source][target] = Some(one);
+                        sockets[target][source] = Some(two);
+                    }
+                }
+                let mut builders = Vec::with_capacity(processes * threads);
+                let mut guards = Vec::with_capacity(processes);
+                for (process, sockets) in sockets.into_iter().enumerate() {
+                    match initialize_networking_from_sockets(sockets, process, threads, Box::new(|_| None)) {
+                        Ok((stuff, guard)) => {
+                            builders.extend(stuff.into_iter().map(|x| GenericBuilder::ZeroCopy(x)));
+                            guards.push(guard);
+                        },
+                        Err(err) => return Err(format!("failed to initialize in-memory networking: {}", err)),
+                    }
+                }
+                Ok((builders, Box::new(guards)))
+            },
         }
     }
 }
 
+/// Parses a hostfile line of the form `host` or `host:port`, using `default_port` for the former.
+#[cfg(feature = "getopts")]
+fn parse_address(line: &str, default_port: usize) -> Result<String, String> {
+    let line = line.trim();
+    let (host, port) = if line.starts_with('[') {
+        let end = line.find(']').ok_or_else(|| format!("unclosed bracket in {:?}", line))?;
+        match &line[end + 1 ..] {
+            "" => (&line[..= end], None),
+            rest if rest.starts_with(':') => (&line[..= end], Some(&rest[1 ..])),
+            _ => return Err(format!("unexpected text after bracketed host in {:?}", line)),
+        }
+    }
+    else {
+        match line.rfind(':') {
+            Some(split) => (&line[.. split], Some(&line[split + 1 ..])),
+            None => (line, None),
+        }
+    };
+    if host.is_empty() || host == "[]" {
+        return Err(format!("missing host in {:?}", line));
+    }
+    if host.contains(char::is_whitespace) || (!host.starts_with('[') && host.contains(':')) {
+        return Err(format!("invalid host {:?}", host));
+    }
+    let port = match port {
+        Some(port) => port.parse::<u16>().map_err(|e| format!("invalid port {:?} in {:?}: {}", port, line, e))?,
+        None => {
+            if default_port > usize::from(u16::MAX) {
+                return Err(format!("default port {} out of range for {:?}", default_port, line));
+            }
+            default_port as u16
+        }
+    };
+    Ok(format!("{}:{}", host, port))
+}
+
 /// Initializes communication and executes a distributed computation.
 ///
 /// This method allocates an `allocator::Generic` for each thread, spawns local worker threads,
@@ -305,13 +393,47 @@ where
     T: Send+'static,
     F: Fn(<A as AllocateBuilder>::Allocator)->T+Send+Sync+'static
 {
+    initialize_from_with(builders, others, ThreadConfig::default(), func)
+}
+
+/// Initializes computation and runs a distributed computation, with configured worker threads.
+///
+/// This behaves as `initialize_from`, except that `threads` determines the name of each worker
+/// thread and, optionally, the core it is pinned to.
+///
+/// # Examples
+/// ```
+/// use timely_communication::{Allocate, ThreadConfig};
+///
+/// let (builders, others) = timely_communication::Config::Process(2).try_build().unwrap();
+/// let threads = ThreadConfig::default().name(|index| format!("my-worker-{}", index));
+/// let guards = timely_communication::initialize_from_with(builders, others, threads, |allocator| {
+///     assert_eq!(std::thread::current().name(), Some(&*format!("my-worker-{}", allocator.index())));
+/// });
+/// guards.unwrap().join();
+/// ```
+pub fn initialize_from_with<A, T, F>(
+    builders: Vec<A>,
+    others: Box<dyn Any+Send>,
+    threads: ThreadConfig,
+    func: F,
+) -> Result<WorkerGuards<T>,String>
+where
+    A: AllocateBuilder+'static,
+    T: Send+'static,
+    F: Fn(<A as AllocateBuilder>::Allocator)->T+Send+Sync+'static
+{
+    let cores = resolve_cores(&threads, builders.len())?;
     let logic = Arc::new(func);
     let mut guards = Vec::new();
-    for (index, builder) in builders.into_iter().enumerate() {
+    for ((index, builder), core) in builders.into_iter().enumerate().zip(cores) {
         let clone = logic.clone();
         guards.push(thread::Builder::new()
-                            .name(format!("timely:work-{}", index))
+                            .name((threads.name)(index))
                             .spawn(move || {
+                                if let Some(core) = core {
+                                    pin_current_thread(core);
+                                }
                                 let communicator = builder.build();
                                 (*clone)(communicator)
                             })
@@ -321,6 +443,105 @@ where
     Ok(WorkerGuards { guards, others })
 }
 
+/// Names and core placement for worker threads, used by `initialize_from_with`.
+///
+/// By default, worker threads are named `timely:work-{index}` and are not pinned to cores.
+pub struct ThreadConfig {
+    /// Produces the name of the thread for each worker index.
+    name: Box<dyn Fn(usize) -> String + Send + Sync>,
+    /// Produces the core, if any, to pin the thread of each worker index to.
+    affinity: Option<Box<dyn Fn(usize) -> Option<usize> + Send + Sync>>,
+}
+
+impl Default for ThreadConfig {
+    fn default() -> Self {
+        ThreadConfig {
+            name: Box::new(|index| format!("timely:work-{}", index)),
+            affinity: None,
+        }
+    }
+}
+
+impl Debug for ThreadConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ThreadConfig")
+            .field("affinity", &self.affinity.is_some())
+            .finish()
+    }
+}
+
+impl ThreadConfig {
+    /// Sets the function naming the thread of each worker index.
+    pub fn name<F: Fn(usize) -> String + Send + Sync + 'static>(mut self, name: F) -> Self {
+        self.name = Box::new(name);
+        self
+    }
+
+    /// Sets the function choosing the core to pin the thread of each worker index to.
+    ///
+    /// Cores are identified as by the `core_affinity` crate, and a worker for which the function
+    /// returns `None` is not pinned. Pinning requires the `core_affinity` feature: without it, or
+    /// if a chosen core is not available, `initialize_from_with` returns an error. Pinning is
+    /// otherwise best effort, and a worker whose thread the platform refuses to pin runs unpinned.
+    pub fn affinity<F: Fn(usize) -> Option<usize> + Send + Sync + 'static>(mut self, affinity: F) -> Self {
+        self.affinity = Some(Box::new(affinity));
+        self
+    }
+
+    /// Pins the thread of each worker index to the core with the same identifier.
+    ///
+    /// This is the same as `self.affinity(Some)`, and is suited to running one process per machine.
+    pub fn pin_to_cores(self) -> Self {
+        self.affinity(Some)
+    }
+}
+
+/// A core that a worker thread can be pinned to.
+#[cfg(feature = "core_affinity")]
+type Core = core_affinity::CoreId;
+/// A core that a worker thread can be pinned to, of which there are none without `core_affinity`.
+#[cfg(not(feature = "core_affinity"))]
+type Core = std::convert::Infallible;
+
+/// Resolves the core, if any, to pin each of `workers` threads to, or an error if one is not available.
+#[cfg(feature = "core_affinity")]
+fn resolve_cores(threads: &ThreadConfig, workers: usize) -> Result<Vec<Option<Core>>, String> {
+    let affinity = match threads.affinity.as_ref() {
+        Some(affinity) => affinity,
+        None => return Ok(vec![None; workers]),
+    };
+    let available = core_affinity::get_core_ids()
+        .ok_or_else(|| "failed to list the cores available to pin worker threads to".to_string())?;
+    (0 .. workers).map(|index| match affinity(index) {
+        Some(core) => available.iter().find(|core_id| core_id.id == core).cloned().map(Some)
+            .ok_or_else(|| format!("cannot pin worker {} to core {}, which is not available", index, core)),
+        None => Ok(None),
+    }).collect()
+}
+
+/// Resolves the core, if any, to pin each of `workers` threads to, or an error if one is requested.
+#[cfg(not(feature = "core_affinity"))]
+fn resolve_cores(threads: &ThreadConfig, workers: usize) -> Result<Vec<Option<Core>>, String> {
+    if threads.affinity.is_some() {
+        return Err("pinning worker threads to cores requires the `core_affinity` feature".to_string());
+    }
+    Ok(vec![None; workers])
+}
+
+/// Pins the current thread to `core`.
+#[cfg(feature = "core_affinity")]
+fn pin_current_thread(core: Core) {
+    // The core is known to exist, but the platform may still refuse, in which case the worker
+    // runs unpinned: placement only affects performance, and is not worth failing the worker for.
+    let _ = core_affinity::set_for_current(core);
+}
+
+/// Pins the current thread to `core`, of which there are none.
+#[cfg(not(feature = "core_affinity"))]
+fn pin_current_thread(core: Core) {
+    match core { }
+}
+
 /// Maintains `JoinHandle`s for worker threads.
 pub struct WorkerGuards<T:Send+'static> {
     guards: Vec<::std::thread::JoinHandle<T>>,
@@ -340,19 +561,90 @@ impl<T:Send+'static> WorkerGuards<T> {
     }
 
     /// Waits on the worker threads and returns the results they produce.
+    ///
+    /// A worker that panicked is reported with the message it panicked with.
     pub fn join(mut self) -> Vec<Result<T, String>> {
         self.guards
             .drain(..)
-            .map(|guard| guard.join().map_err(|e| format!("{:?}", e)))
+            .map(|guard| guard.join().map_err(|e| panic_message(&*e)))
             .collect()
     }
 }
 
 impl<T:Send+'static> Drop for WorkerGuards<T> {
     fn drop(&mut self) {
+        // Join all workers before reporting a panic, and only report the first.
+        let mut panicked = None;
         for guard in self.guards.drain(..) {
-            guard.join().expect("Worker panic");
+            if let Err(e) = guard.join() {
+                panicked.get_or_insert_with(|| panic_message(&*e));
+            }
         }
         // println!("WORKER THREADS JOINED");
+        // Panicking while already panicking would abort the process.
+        if let Some(message) = panicked {
+            if !thread::panicking() {
+                panic!("Worker panic: {}", message);
+            }
+        }
+    }
+}
+
+/// Extracts the message from the payload of a panic.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    }
+    else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    }
+    else {
+        "unknown panic payload".to_string()
+    }
+}
+
+#[cfg(all(test, feature = "getopts"))]
+mod tests {
+    use super::parse_address;
+
+    #[test]
+    fn parse_address_default_port() {
+        assert_eq!(parse_address("localhost", 2101), Ok("localhost:2101".to_string()));
+        assert_eq!(parse_address("  10.0.0.1\t", 2101), Ok("10.0.0.1:2101".to_string()));
+    }
+
+    #[test]
+    fn parse_address_host_port() {
+        assert_eq!(parse_address("localhost:1234", 2101), Ok("localhost:1234".to_string()));
+        assert_eq!(parse_address("10.0.0.1:80", 2101), Ok("10.0.0.1:80".to_string()));
+    }
+
+    #[test]
+    fn parse_address_ipv6() {
+        assert_eq!(parse_address("[::1]", 2101), Ok("[::1]:2101".to_string()));
+        assert_eq!(parse_address("[fe80::1]:1234", 2101), Ok("[fe80::1]:1234".to_string()));
+        assert!(parse_address("[::1]1234", 2101).is_err());
+        assert!(parse_address("::1", 2101).is_err());
+    }
+
+    #[test]
+    fn parse_address_bad_port() {
+        assert!(parse_address("localhost:", 2101).is_err());
+        assert!(parse_address("localhost:http", 2101).is_err());
+        assert!(parse_address("localhost:65536", 2101).is_err());
+        assert!(parse_address("localhost", 65536).is_err());
+    }
+
+    #[test]
+    fn parse_address_empty_host() {
+        assert!(parse_address("", 2101).is_err());
+        assert!(parse_address(":1234", 2101).is_err());
+        assert!(parse_address("[]:1234", 2101).is_err());
+    }
+
+    #[test]
+    fn parse_address_unclosed_bracket() {
+        assert!(parse_address("[::1", 2101).is_err());
+        assert!(parse_address("[::1:1234", 2101).is_err());
     }
 }
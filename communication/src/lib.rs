@@ -10,6 +10,8 @@
 //!
 //! To be communicated, a type must implement the [`Serialize`](serde::Serialize) trait when using the
 //! `bincode` feature or the [`Abomonation`](abomonation::Abomonation) trait when not.
+//! With the `validate-serde` feature, debug builds check that each message decodes from its own
+//! encoding as it is serialized for sending, and panic naming the type if it does not.
 //!
 //! Channel endpoints also implement a lower-level `push` and `pull` interface (through the [`Push`](Push) and [`Pull`](Pull)
 //! traits), which is used for more precise control of resources.
@@ -104,8 +106,9 @@ use abomonation::Abomonation;
 
 pub use allocator::Generic as Allocator;
 pub use allocator::Allocate;
-pub use initialize::{initialize, initialize_from, Config, WorkerGuards};
+pub use initialize::{initialize, initialize_from, initialize_from_with, Config, ThreadConfig, WorkerGuards};
 pub use message::Message;
+pub use networking::Compression;
 
 /// A composite trait for types that may be used with channels.
 #[cfg(not(feature = "bincode"))]
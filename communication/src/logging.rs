@@ -21,6 +21,10 @@ pub enum CommunicationEvent {
 }
 
 /// An observed message.
+///
+/// Send threads record one event for each serialized message they write, and receive threads
+/// for each message they read. The header identifies the channel, source and target workers,
+/// and the message's length in bytes on the wire, excluding the header itself.
 #[derive(Abomonation, Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct MessageEvent {
     /// true for send event, false for receive event
@@ -29,6 +33,13 @@ pub struct MessageEvent {
     pub header: crate::networking::MessageHeader,
 }
 
+impl MessageEvent {
+    /// The number of bytes the message occupies on the wire, including its header.
+    pub fn wire_bytes(&self) -> usize {
+        self.header.required_bytes()
+    }
+}
+
 /// Starting or stopping communication threads.
 #[derive(Abomonation, Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct StateEvent {
@@ -153,6 +153,22 @@ impl<T: Data> Message<T> {
             },
         }
     }
+
+    /// Checks that the binary representation decodes, panicking if it does not.
+    ///
+    /// Abomonation's encoding includes memory addresses, and so a decoded message is not
+    /// re-encoded for comparison. Instead, the encoding must decode and measure the same length.
+    #[cfg(all(feature = "validate-serde", debug_assertions))]
+    pub(crate) fn validate_round_trip(&self) {
+        let name = ::std::any::type_name::<T>();
+        let mut encoded = Vec::with_capacity(self.length_in_bytes());
+        self.into_bytes(&mut encoded);
+        assert_eq!(encoded.len(), self.length_in_bytes(), "validate-serde: {} wrote a length other than it measured", name);
+        let length = encoded.len();
+        let decoded = unsafe { abomonation::abomonated::Abomonated::<T, _>::new(encoded) }
+            .unwrap_or_else(|| panic!("validate-serde: {} failed to decode its own encoding", name));
+        assert_eq!(abomonation::measure::<T>(&*decoded), length, "validate-serde: {} decoded to a value of a different length", name);
+    }
 }
 
 #[cfg(feature = "bincode")]
@@ -190,6 +206,19 @@ impl<T: Data> Message<T> {
             },
         }
     }
+
+    /// Checks that the binary representation decodes and re-encodes identically, panicking if not.
+    #[cfg(all(feature = "validate-serde", debug_assertions))]
+    pub(crate) fn validate_round_trip(&self) {
+        let name = ::std::any::type_name::<T>();
+        let mut encoded = Vec::with_capacity(self.length_in_bytes());
+        self.into_bytes(&mut encoded);
+        assert_eq!(encoded.len(), self.length_in_bytes(), "validate-serde: {} wrote a length other than it measured", name);
+        let decoded: T = ::bincode::deserialize(&encoded[..])
+            .unwrap_or_else(|e| panic!("validate-serde: {} failed to decode its own encoding: {}", name, e));
+        let reencoded = ::bincode::serialize(&decoded).expect("bincode::serialize() failed");
+        assert!(encoded == reencoded, "validate-serde: {} does not round-trip through its encoding", name);
+    }
 }
 
 impl<T> ::std::ops::Deref for Message<T> {
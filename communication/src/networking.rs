@@ -1,7 +1,7 @@
 //! Networking code for sending and receiving fixed size `Vec<u8>` between machines.
 
 use std::io;
-use std::io::{Read, Result};
+use std::io::{Read, Write, Result};
 use std::net::{TcpListener, TcpStream};
 use std::sync::Arc;
 use std::thread;
@@ -15,6 +15,57 @@ use abomonation::{encode, decode};
 // other traffic on the same port.
 const HANDSHAKE_MAGIC: u64 = 0xc2f1fb770118add9;
 
+/// Compression applied to the data of each message sent between processes.
+///
+/// Each message is compressed independently, so that received messages remain independently
+/// decodable without reference to other messages. All processes of a computation must use
+/// the same compression, which is checked when their connections are established.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Compression {
+    /// LZ4 block compression, which requires the `lz4` feature.
+    Lz4,
+}
+
+impl Compression {
+    /// The code exchanged in the connection handshake to identify a compression.
+    fn handshake_code(compression: Option<Compression>) -> u64 {
+        match compression {
+            None => 0,
+            Some(Compression::Lz4) => 1,
+        }
+    }
+
+    /// Returns an error if support for the compression was not compiled in.
+    pub fn check_available(&self) -> Result<()> {
+        match *self {
+            Compression::Lz4 if cfg!(feature = "lz4") => Ok(()),
+            Compression::Lz4 => Err(io::Error::new(io::ErrorKind::Other,
+                "LZ4 compression requires the `lz4` feature")),
+        }
+    }
+
+    /// Compresses the data of a single message.
+    pub(crate) fn compress(&self, bytes: &[u8]) -> Vec<u8> {
+        match *self {
+            #[cfg(feature = "lz4")]
+            Compression::Lz4 => lz4_flex::block::compress_prepend_size(bytes),
+            #[cfg(not(feature = "lz4"))]
+            Compression::Lz4 => { let _ = bytes; unreachable!("LZ4 compression requires the `lz4` feature") },
+        }
+    }
+
+    /// Decompresses the data of a single message, as produced by `compress`.
+    pub(crate) fn decompress(&self, bytes: &[u8]) -> Result<Vec<u8>> {
+        match *self {
+            #[cfg(feature = "lz4")]
+            Compression::Lz4 => lz4_flex::block::decompress_size_prepended(bytes)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            #[cfg(not(feature = "lz4"))]
+            Compression::Lz4 => { let _ = bytes; unreachable!("LZ4 compression requires the `lz4` feature") },
+        }
+    }
+}
+
 /// Framing data for each `Vec<u8>` transmission, indicating a typed channel, the source and
 /// destination workers, and the length in bytes.
 #[derive(Abomonation, Debug, PartialEq, Eq, Hash, Clone, Copy)]
@@ -63,13 +114,15 @@ impl MessageHeader {
 ///
 /// The item at index i in the resulting vec, is a Some(TcpSocket) to process i, except
 /// for item `my_index` which is None (no socket to self).
-pub fn create_sockets(addresses: Vec<String>, my_index: usize, noisy: bool) -> Result<Vec<Option<TcpStream>>> {
+///
+/// Connections are rejected with an error if the remote process uses a different `compression`.
+pub fn create_sockets(addresses: Vec<String>, my_index: usize, noisy: bool, compression: Option<Compression>) -> Result<Vec<Option<TcpStream>>> {
 
     let hosts1 = Arc::new(addresses);
     let hosts2 = hosts1.clone();
 
-    let start_task = thread::spawn(move || start_connections(hosts1, my_index, noisy));
-    let await_task = thread::spawn(move || await_connections(hosts2, my_index, noisy));
+    let start_task = thread::spawn(move || start_connections(hosts1, my_index, noisy, compression));
+    let await_task = thread::spawn(move || await_connections(hosts2, my_index, noisy, compression));
 
     let mut results = start_task.join().unwrap()?;
     results.push(None);
@@ -82,17 +135,45 @@ pub fn create_sockets(addresses: Vec<String>, my_index: usize, noisy: bool) -> R
 }
 
 
+/// Exchanges the number of worker threads of each process over connected sockets.
+///
+/// Each process writes its own count to every socket before reading the counts of the others,
+/// so that no process blocks on a peer that is itself blocked reading. The result contains the
+/// count of each process, including `threads` for `my_index`.
+pub fn exchange_thread_counts<S: Read + Write>(sockets: &mut [Option<S>], my_index: usize, threads: usize) -> Result<Vec<usize>> {
+    for socket in sockets.iter_mut().flatten() {
+        unsafe { encode(&(threads as u64), socket) }?;
+    }
+    let mut counts = Vec::with_capacity(sockets.len());
+    for (index, socket) in sockets.iter_mut().enumerate() {
+        if let Some(socket) = socket {
+            let mut buffer = [0u8;8];
+            socket.read_exact(&mut buffer)?;
+            let count = unsafe { decode::<u64>(&mut buffer) }.expect("failed to decode thread count").0.clone() as usize;
+            if count == 0 {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                    format!("worker {} reported zero threads", index)));
+            }
+            counts.push(count);
+        }
+        else {
+            assert_eq!(index, my_index);
+            counts.push(threads);
+        }
+    }
+    Ok(counts)
+}
+
 /// Result contains connections [0, my_index - 1].
-pub fn start_connections(addresses: Arc<Vec<String>>, my_index: usize, noisy: bool) -> Result<Vec<Option<TcpStream>>> {
-    let results = addresses.iter().take(my_index).enumerate().map(|(index, address)| {
+pub fn start_connections(addresses: Arc<Vec<String>>, my_index: usize, noisy: bool, compression: Option<Compression>) -> Result<Vec<Option<TcpStream>>> {
+    addresses.iter().take(my_index).enumerate().map(|(index, address)| {
         loop {
             match TcpStream::connect(address) {
                 Ok(mut stream) => {
                     stream.set_nodelay(true).expect("set_nodelay call failed");
-                    unsafe { encode(&HANDSHAKE_MAGIC, &mut stream) }.expect("failed to encode/send handshake magic");
-                    unsafe { encode(&(my_index as u64), &mut stream) }.expect("failed to encode/send worker index");
+                    connect_handshake(&mut stream, my_index, index, compression)?;
                     if noisy { println!("worker {}:\tconnection to worker {}", my_index, index); }
-                    break Some(stream);
+                    break Ok(Some(stream));
                 },
                 Err(error) => {
                     println!("worker {}:\terror connecting to worker {}: {}; retrying", my_index, index, error);
@@ -100,30 +181,118 @@ pub fn start_connections(addresses: Arc<Vec<String>>, my_index: usize, noisy: bo
                 },
             }
         }
-    }).collect();
-
-    Ok(results)
+    }).collect()
 }
 
 /// Result contains connections [my_index + 1, addresses.len() - 1].
-pub fn await_connections(addresses: Arc<Vec<String>>, my_index: usize, noisy: bool) -> Result<Vec<Option<TcpStream>>> {
+pub fn await_connections(addresses: Arc<Vec<String>>, my_index: usize, noisy: bool, compression: Option<Compression>) -> Result<Vec<Option<TcpStream>>> {
     let mut results: Vec<_> = (0..(addresses.len() - my_index - 1)).map(|_| None).collect();
     let listener = TcpListener::bind(&addresses[my_index][..])?;
 
     for _ in (my_index + 1) .. addresses.len() {
         let mut stream = listener.accept()?.0;
         stream.set_nodelay(true).expect("set_nodelay call failed");
-        let mut buffer = [0u8;16];
-        stream.read_exact(&mut buffer)?;
-        let (magic, mut buffer) = unsafe { decode::<u64>(&mut buffer) }.expect("failed to decode magic");
-        if magic != &HANDSHAKE_MAGIC {
-            return Err(io::Error::new(io::ErrorKind::InvalidData,
-                "received incorrect timely handshake"));
-        }
-        let identifier = unsafe { decode::<u64>(&mut buffer) }.expect("failed to decode worker index").0.clone() as usize;
+        let identifier = accept_handshake(&mut stream, my_index, compression)?;
         results[identifier - my_index - 1] = Some(stream);
         if noisy { println!("worker {}:\tconnection from worker {}", my_index, identifier); }
     }
 
     Ok(results)
 }
+
+/// Introduces worker `my_index` to worker `index` over a stream it connected.
+///
+/// Returns an error if the remote worker replies with a different compression.
+fn connect_handshake<S: Read + Write>(stream: &mut S, my_index: usize, index: usize, compression: Option<Compression>) -> Result<()> {
+    let compression_code = Compression::handshake_code(compression);
+    unsafe { encode(&HANDSHAKE_MAGIC, stream) }.expect("failed to encode/send handshake magic");
+    unsafe { encode(&(my_index as u64), stream) }.expect("failed to encode/send worker index");
+    unsafe { encode(&compression_code, stream) }.expect("failed to encode/send compression");
+    let mut buffer = [0u8;8];
+    stream.read_exact(&mut buffer)?;
+    let remote_code = unsafe { decode::<u64>(&mut buffer) }.expect("failed to decode compression").0.clone();
+    if remote_code != compression_code {
+        return Err(io::Error::new(io::ErrorKind::InvalidData,
+            format!("worker {} uses a different compression than worker {}", index, my_index)));
+    }
+    Ok(())
+}
+
+/// Accepts the introduction of a worker over a stream accepted by worker `my_index`.
+///
+/// Returns the index of the remote worker, or an error if it sent an incorrect handshake or
+/// uses a different compression.
+fn accept_handshake<S: Read + Write>(stream: &mut S, my_index: usize, compression: Option<Compression>) -> Result<usize> {
+    let compression_code = Compression::handshake_code(compression);
+    let mut buffer = [0u8;24];
+    stream.read_exact(&mut buffer)?;
+    let (magic, mut buffer) = unsafe { decode::<u64>(&mut buffer) }.expect("failed to decode magic");
+    if magic != &HANDSHAKE_MAGIC {
+        return Err(io::Error::new(io::ErrorKind::InvalidData,
+            "received incorrect timely handshake"));
+    }
+    let (identifier, mut buffer) = unsafe { decode::<u64>(&mut buffer) }.expect("failed to decode worker index");
+    let identifier = identifier.clone() as usize;
+    let remote_code = unsafe { decode::<u64>(&mut buffer) }.expect("failed to decode compression").0.clone();
+    // Reply with our compression, so that the connecting worker can also detect a mismatch.
+    unsafe { encode(&compression_code, stream) }?;
+    if remote_code != compression_code {
+        return Err(io::Error::new(io::ErrorKind::InvalidData,
+            format!("worker {} uses a different compression than worker {}", identifier, my_index)));
+    }
+    Ok(identifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use crate::allocator::zero_copy::stream::MemoryStream;
+    use super::{Compression, accept_handshake, connect_handshake};
+
+    /// Runs the handshake between worker 0 accepting and worker 1 connecting.
+    fn handshake(accepting: Option<Compression>, connecting: Option<Compression>) -> (super::Result<usize>, super::Result<()>) {
+        let (mut one, mut two) = MemoryStream::pair();
+        let connect = thread::spawn(move || connect_handshake(&mut two, 1, 0, connecting));
+        let accepted = accept_handshake(&mut one, 0, accepting);
+        (accepted, connect.join().unwrap())
+    }
+
+    #[test]
+    fn handshake_accepts_same_compression() {
+        let (accepted, connected) = handshake(None, None);
+        assert_eq!(accepted.unwrap(), 1);
+        connected.unwrap();
+        let (accepted, connected) = handshake(Some(Compression::Lz4), Some(Compression::Lz4));
+        assert_eq!(accepted.unwrap(), 1);
+        connected.unwrap();
+    }
+
+    #[test]
+    fn handshake_rejects_different_compression() {
+        let (accepted, connected) = handshake(None, Some(Compression::Lz4));
+        assert!(accepted.is_err());
+        assert!(connected.is_err());
+        let (accepted, connected) = handshake(Some(Compression::Lz4), None);
+        assert!(accepted.is_err());
+        assert!(connected.is_err());
+    }
+
+    #[cfg(feature = "lz4")]
+    #[test]
+    fn lz4_round_trips() {
+        let repetitive = vec![7u8; 1 << 16];
+        let mixed: Vec<u8> = (0 .. 1 << 12).map(|i: u32| (i.wrapping_mul(2654435761) >> 24) as u8).collect();
+        for bytes in [Vec::new(), vec![1u8], repetitive, mixed].iter() {
+            let compressed = Compression::Lz4.compress(bytes);
+            assert_eq!(&Compression::Lz4.decompress(&compressed).unwrap(), bytes);
+        }
+        assert!(Compression::Lz4.compress(&[7u8; 1 << 16]).len() < 1 << 12);
+    }
+
+    #[cfg(feature = "lz4")]
+    #[test]
+    fn lz4_rejects_truncated_data() {
+        let compressed = Compression::Lz4.compress(&[7u8; 1 << 10]);
+        assert!(Compression::Lz4.decompress(&compressed[.. compressed.len() - 1]).is_err());
+    }
+}
@@ -0,0 +1,37 @@
+use timely_communication::{Allocate, Config, Message};
+
+/// Exchanges rounds of 64 KiB messages between two in-memory processes, and checks the buffer
+/// counts each worker reports after the last round.
+#[test]
+fn receive_buffers_are_recycled() {
+    let config = Config::ClusterInMemory { processes: 2, threads: 1 };
+    let guards = timely_communication::initialize(config, |mut allocator| {
+        let other = 1 - allocator.index();
+        let (mut senders, mut receiver) = allocator.allocate(0);
+        for _round in 0 .. 10 {
+            for _ in 0 .. 64 {
+                senders[other].send(Message::from_typed(vec![0u8; 1 << 16]));
+            }
+            senders[other].done();
+
+            // Drop each message once received, so that its buffer can be reused.
+            let mut received = 0;
+            while received < 64 {
+                allocator.receive();
+                while let Some(message) = receiver.recv() {
+                    assert_eq!(message.len(), 1 << 16);
+                    received += 1;
+                }
+                allocator.release();
+            }
+        }
+        allocator.buffer_stats()
+    }).unwrap();
+
+    for result in guards.join() {
+        let stats = result.unwrap();
+        assert!(stats.allocated >= 1, "{:?}", stats);
+        assert!(stats.recycled >= 1, "{:?}", stats);
+        assert!(stats.retained_bytes >= 1 << 20, "{:?}", stats);
+    }
+}
@@ -335,7 +335,7 @@ mod serde {
 
 mod container {
     use std::ops::Deref;
-    use crate::{Container, SizableContainer};
+    use crate::{Container, ContainerBytes, SizableContainer};
 
     use crate::columnation::{Columnation, TimelyStack};
 
@@ -368,6 +368,12 @@ mod container {
         }
     }
 
+    impl<T: Columnation + 'static> ContainerBytes for TimelyStack<T> {
+        fn length_in_bytes(&self) -> usize {
+            self.summed_heap_size().0
+        }
+    }
+
     impl<T: Columnation + 'static> SizableContainer for TimelyStack<T> {
         fn capacity(&self) -> usize {
             self.capacity()
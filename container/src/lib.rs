@@ -61,6 +61,18 @@ pub trait Container: Default + Clone + 'static {
     /// Returns an iterator that drains the contents of this container.
     /// Drain leaves the container in an undefined state.
     fn drain(&mut self) -> Self::DrainIter<'_>;
+
+    /// Moves all elements of `other` to the end of `self`, leaving `other` empty.
+    ///
+    /// After the call, `other.len()` is zero but `other` may retain its allocations, so that it
+    /// can be reused. The default implementation drains `other` and pushes each item into `self`;
+    /// containers that can move their contents wholesale should override it.
+    fn append(&mut self, other: &mut Self) where for<'a> Self: PushInto<Self::Item<'a>> {
+        for item in other.drain() {
+            self.push(item);
+        }
+        other.clear();
+    }
 }
 
 /// A container that can absorb items of a specific type.
@@ -101,6 +113,14 @@ pub trait ContainerBuilder: Default + 'static {
     fn extract(&mut self) -> Option<&mut Self::Container>;
     /// Extract assembled containers and any unfinished data.
     fn finish(&mut self) -> Option<&mut Self::Container>;
+    /// Indicate that roughly `hint` items are about to be pushed.
+    ///
+    /// Builders can use the hint to size the containers they allocate next. The hint is
+    /// advisory, and the default implementation ignores it.
+    #[inline]
+    fn capacity_hint(&mut self, hint: usize) {
+        let _ = hint;
+    }
 }
 
 /// A default container builder that uses length and preferred capacity to chunk data.
@@ -114,6 +134,8 @@ pub struct CapacityContainerBuilder<C>{
     empty: Option<C>,
     /// Completed containers pending to be sent.
     pending: VecDeque<C>,
+    /// The most recent capacity hint, if any.
+    hint: Option<usize>,
 }
 
 /// A container that can be sized and reveals its capacity.
@@ -122,27 +144,45 @@ pub trait SizableContainer: Container {
     fn capacity(&self) -> usize;
     /// Return the preferred capacity of the container.
     fn preferred_capacity() -> usize;
+    /// Return the preferred capacity of the container, when roughly `hint` items are expected.
+    ///
+    /// The default implementation ignores the hint and returns [`Self::preferred_capacity`].
+    #[inline]
+    fn preferred_capacity_for(hint: usize) -> usize {
+        let _ = hint;
+        Self::preferred_capacity()
+    }
     /// Reserve space for `additional` elements, possibly increasing the capacity of the container.
     fn reserve(&mut self, additional: usize);
 }
 
+/// A container that can estimate the number of bytes its contents occupy.
+pub trait ContainerBytes: Container {
+    /// An estimate of the bytes occupied by the elements of the container.
+    ///
+    /// The estimate covers the elements themselves, and not unused capacity. Implementations
+    /// need not account for heap allocations owned by individual elements.
+    fn length_in_bytes(&self) -> usize;
+}
+
 impl<C: Container> ContainerBuilder for CapacityContainerBuilder<C> {
     type Container = C;
 
     #[inline]
     fn push<T>(&mut self, item: T) where C: SizableContainer + PushInto<T> {
+        let preferred_capacity = self.hint.map_or_else(C::preferred_capacity, C::preferred_capacity_for);
         if self.current.capacity() == 0 {
             self.current = self.empty.take().unwrap_or_default();
             // Discard any non-uniform capacity container.
-            if self.current.capacity() != C::preferred_capacity() {
+            if self.current.capacity() != preferred_capacity {
                 self.current = C::default();
             }
             // Protect against non-emptied containers.
             self.current.clear();
         }
         // Ensure capacity
-        if self.current.capacity() < C::preferred_capacity() {
-            self.current.reserve(C::preferred_capacity() - self.current.len());
+        if self.current.capacity() < preferred_capacity {
+            self.current.reserve(preferred_capacity - self.current.len());
         }
 
         // Push item
@@ -188,6 +228,11 @@ impl<C: Container> ContainerBuilder for CapacityContainerBuilder<C> {
         }
         self.extract()
     }
+
+    #[inline]
+    fn capacity_hint(&mut self, hint: usize) {
+        self.hint = Some(hint);
+    }
 }
 
 impl<T: Clone + 'static> Container for Vec<T> {
@@ -215,6 +260,10 @@ impl<T: Clone + 'static> Container for Vec<T> {
     fn drain(&mut self) -> Self::DrainIter<'_> {
         self.drain(..)
     }
+
+    fn append(&mut self, other: &mut Self) {
+        Vec::append(self, other)
+    }
 }
 
 impl<T: Clone + 'static> SizableContainer for Vec<T> {
@@ -231,6 +280,12 @@ impl<T: Clone + 'static> SizableContainer for Vec<T> {
     }
 }
 
+impl<T: Clone + 'static> ContainerBytes for Vec<T> {
+    fn length_in_bytes(&self) -> usize {
+        self.len() * std::mem::size_of::<T>()
+    }
+}
+
 impl<T> PushInto<T> for Vec<T> {
     #[inline]
     fn push_into(&mut self, item: T) {
@@ -257,7 +312,7 @@ mod rc {
     use std::ops::Deref;
     use std::rc::Rc;
 
-    use crate::Container;
+    use crate::{Container, ContainerBytes};
 
     impl<T: Container> Container for Rc<T> {
         type ItemRef<'a> = T::ItemRef<'a> where Self: 'a;
@@ -292,13 +347,19 @@ mod rc {
             self.iter()
         }
     }
+
+    impl<T: ContainerBytes> ContainerBytes for Rc<T> {
+        fn length_in_bytes(&self) -> usize {
+            self.deref().length_in_bytes()
+        }
+    }
 }
 
 mod arc {
     use std::ops::Deref;
     use std::sync::Arc;
 
-    use crate::Container;
+    use crate::{Container, ContainerBytes};
 
     impl<T: Container> Container for Arc<T> {
         type ItemRef<'a> = T::ItemRef<'a> where Self: 'a;
@@ -333,6 +394,12 @@ mod arc {
             self.iter()
         }
     }
+
+    impl<T: ContainerBytes> ContainerBytes for Arc<T> {
+        fn length_in_bytes(&self) -> usize {
+            self.deref().length_in_bytes()
+        }
+    }
 }
 
 /// A container that can partition itself into pieces.
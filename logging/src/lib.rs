@@ -13,6 +13,8 @@ pub struct Registry<Id> {
     map: HashMap<String, (Box<dyn Any>, Box<dyn Flush>)>,
     /// An instant common to all logging statements.
     time: Instant,
+    /// A clock overriding `time` for loggers inserted from now on, if set.
+    clock: Option<Rc<dyn Fn() -> Duration>>,
 }
 
 impl<Id: Clone+'static> Registry<Id> {
@@ -32,10 +34,39 @@ impl<Id: Clone+'static> Registry<Id> {
         name: &str,
         action: F) -> Option<Box<dyn Any>>
     {
-        let logger = Logger::<T, Id>::new(self.time, Duration::default(), self.id.clone(), action);
+        let logger = match &self.clock {
+            Some(clock) => Logger::<T, Id>::new_with_clock(clock.clone(), self.id.clone(), action),
+            None => Logger::<T, Id>::new(self.time, Duration::default(), self.id.clone(), action),
+        };
+        self.insert_logger(name, logger)
+    }
+
+    /// Binds a log name to an action on log event batches, with events timestamped by `clock`.
+    ///
+    /// This behaves as `insert`, except that the logger reads the time from `clock` rather
+    /// than from the registry. The clock should not go backwards, as the action expects
+    /// non-decreasing timestamps.
+    pub fn insert_with_clock<T: 'static, C: Fn() -> Duration+'static, F: FnMut(&Duration, &mut Vec<(Duration, Id, T)>)+'static>(
+        &mut self,
+        name: &str,
+        clock: C,
+        action: F) -> Option<Box<dyn Any>>
+    {
+        let logger = Logger::<T, Id>::new_with_clock(Rc::new(clock), self.id.clone(), action);
         self.insert_logger(name, logger)
     }
 
+    /// Sets the clock used to timestamp events of loggers subsequently bound with `insert`.
+    ///
+    /// By default, events are timestamped with the time elapsed since the registry's creation
+    /// instant. A clock synchronized across processes, for example one reading the system time
+    /// since the Unix epoch on NTP-synchronized hosts, allows logs from several processes to be
+    /// merged. Loggers inserted before the call keep their existing time base. The clock should not
+    /// go backwards, as actions expect non-decreasing timestamps.
+    pub fn set_clock<C: Fn() -> Duration+'static>(&mut self, clock: C) {
+        self.clock = Some(Rc::new(clock));
+    }
+
     /// Binds a log name to a logger.
     pub fn insert_logger<T: 'static>(
         &mut self,
@@ -68,6 +99,7 @@ impl<Id: Clone+'static> Registry<Id> {
         Registry {
             id,
             time,
+            clock: None,
             map: HashMap::new(),
         }
     }
@@ -106,6 +138,8 @@ struct LoggerInner<T, E, A: ?Sized + FnMut(&Duration, &mut Vec<(Duration, E, T)>
     time:   Instant,
     /// offset to allow re-calibration.
     offset: Duration,
+    /// clock overriding `time` and `offset`, if set.
+    clock: Option<Rc<dyn Fn() -> Duration>>,
     /// shared buffer of accumulated log events
     buffer: Vec<(Duration, E, T)>,
     /// action to take on full log buffers.
@@ -122,6 +156,25 @@ impl<T, E: Clone> Logger<T, E> {
             id,
             time,
             offset,
+            clock: None,
+            action,
+            buffer: Vec::with_capacity(LoggerInner::<T, E, F>::buffer_capacity()),
+        };
+        let inner = Rc::new(RefCell::new(inner));
+        Logger { inner }
+    }
+
+    /// Allocates a new shareable logger bound to a write destination, whose events are
+    /// timestamped by `clock`.
+    pub fn new_with_clock<F>(clock: Rc<dyn Fn() -> Duration>, id: E, action: F) -> Self
+    where
+        F: FnMut(&Duration, &mut Vec<(Duration, E, T)>)+'static
+    {
+        let inner = LoggerInner {
+            id,
+            time: Instant::now(),
+            offset: Duration::default(),
+            clock: Some(clock),
             action,
             buffer: Vec::with_capacity(LoggerInner::<T, E, F>::buffer_capacity()),
         };
@@ -167,6 +220,16 @@ impl<T, E: Clone> Logger<T, E> {
     }
 }
 
+impl<T, E, A: ?Sized + FnMut(&Duration, &mut Vec<(Duration, E, T)>)> LoggerInner<T, E, A> {
+    /// The current time, as read from the clock if set.
+    fn now(&self) -> Duration {
+        match &self.clock {
+            Some(clock) => clock(),
+            None => self.time.elapsed() + self.offset,
+        }
+    }
+}
+
 impl<T, E: Clone, A: ?Sized + FnMut(&Duration, &mut Vec<(Duration, E, T)>)> LoggerInner<T, E, A> {
 
     /// The upper limit for buffers to allocate, size in bytes. [Self::buffer_capacity] converts
@@ -190,7 +253,7 @@ impl<T, E: Clone, A: ?Sized + FnMut(&Duration, &mut Vec<(Duration, E, T)>)> Logg
     pub fn log_many<I>(&mut self, events: I)
         where I: IntoIterator, I::Item: Into<T>
     {
-        let elapsed = self.time.elapsed() + self.offset;
+        let elapsed = self.now();
         for event in events {
             self.buffer.push((elapsed, self.id.clone(), event.into()));
             if self.buffer.len() == self.buffer.capacity() {
@@ -228,6 +291,7 @@ where
             .field("id", &self.id)
             .field("time", &self.time)
             .field("offset", &self.offset)
+            .field("clock", &self.clock.as_ref().map(|_| "Fn"))
             .field("action", &"FnMut")
             .field("buffer", &self.buffer)
             .finish()
@@ -248,7 +312,7 @@ impl<T, E> Flush for Logger<T, E> {
 
 impl<T, E, A: ?Sized + FnMut(&Duration, &mut Vec<(Duration, E, T)>)> Flush for LoggerInner<T, E, A> {
     fn flush(&mut self) {
-        let elapsed = self.time.elapsed() + self.offset;
+        let elapsed = self.now();
         if !self.buffer.is_empty() {
             (self.action)(&elapsed, &mut self.buffer);
             self.buffer.clear();
@@ -0,0 +1,187 @@
+//! Snapshots and restores of operator state, for fault tolerance.
+//!
+//! An operator that wants its state to survive a failure holds that state in an
+//! `Rc<RefCell<S>>` for some `S: Checkpoint`, and registers it under a name with `register`
+//! while the dataflow is built. `Worker::checkpoint` then collects a snapshot of every registered
+//! state, and `Worker::restore` hands those snapshots back to the states registered under the
+//! same names when the dataflow is next built, whether by this worker or by its replacement.
+//!
+//! # Consistency
+//!
+//! A snapshot is only meaningful if it is taken when no records are in flight, as otherwise
+//! records sent before the snapshot but received after it would be lost (or counted twice, if
+//! the sender replays them). `Worker::checkpoint` first quiesces the worker: it steps the worker
+//! until a step schedules no operators, at which point every record sent between operators of
+//! this worker has been received, and no operator has outstanding work it has asked to do.
+//! The snapshots are then taken without running any operator, and so describe one cut of the
+//! computation.
+//!
+//! This cut is consistent for a single worker. With multiple workers, records may still be in
+//! flight between workers, and each worker quiesces independently. A consistent cut across
+//! workers additionally requires that all workers hold their inputs at the same times, and
+//! that each worker checkpoints only once its probes show that those times are complete, so
+//! that no worker has records left to send to another.
+//!
+//! Timely does not know where the application's inputs are in their sources. The position of
+//! each input, such as an offset into a log, should be registered as state alongside the
+//! operators, so that a restored computation resumes its inputs where the snapshot left them.
+//! Operators that request a delayed activation, for example with `Activator::activate_after`,
+//! do not prevent quiescence, and should snapshot whatever they are waiting to act on. An
+//! operator that activates itself on every step never quiesces, and the checkpoint never returns.
+//!
+//! # Examples
+//! ```
+//! use std::cell::RefCell;
+//! use std::rc::Rc;
+//! use timely::bytes::arc::Bytes;
+//! use timely::checkpoint::{self, Checkpoint};
+//! use timely::dataflow::InputHandle;
+//! use timely::dataflow::operators::{Input, Inspect, Probe};
+//!
+//! struct Count(u64);
+//!
+//! impl Checkpoint for Count {
+//!     fn snapshot(&self) -> Bytes { Bytes::from(self.0.to_le_bytes().to_vec()) }
+//!     fn restore(&mut self, state: Bytes) {
+//!         let mut bytes = [0u8; 8];
+//!         bytes.copy_from_slice(&state[..]);
+//!         self.0 = u64::from_le_bytes(bytes);
+//!     }
+//! }
+//!
+//! timely::execute_from_args(std::env::args(), |worker| {
+//!
+//!     // Builds a dataflow that counts its input records.
+//!     let build = |worker: &mut timely::worker::Worker<_>, input: &mut InputHandle<u64, u64>| {
+//!         let count = Rc::new(RefCell::new(Count(0)));
+//!         worker.dataflow(|scope| {
+//!             checkpoint::register(scope, "count", count.clone());
+//!             scope.input_from(input)
+//!                  .inspect(move |_| count.borrow_mut().0 += 1)
+//!                  .probe()
+//!         })
+//!     };
+//!
+//!     let index = worker.next_dataflow_index();
+//!     let mut input = InputHandle::new();
+//!     let probe = build(worker, &mut input);
+//!     for round in 0 .. 10 {
+//!         input.send(round);
+//!     }
+//!     input.advance_to(1);
+//!     worker.step_while(|| probe.less_than(input.time()));
+//!
+//!     let snapshot = worker.checkpoint();
+//!
+//!     // Rebuilding after a restore hands the snapshot back to the new count.
+//!     worker.drop_dataflow(index);
+//!     worker.restore(snapshot);
+//!     let mut input = InputHandle::new();
+//!     build(worker, &mut input);
+//!     let snapshot = worker.checkpoint();
+//!     assert_eq!(&snapshot.states["count"][..], &10u64.to_le_bytes()[..]);
+//! }).unwrap();
+//! ```
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::{Rc, Weak};
+
+use crate::bytes::arc::Bytes;
+use crate::worker::AsWorker;
+
+/// State that can be snapshotted and restored.
+pub trait Checkpoint {
+    /// Serializes the current state.
+    fn snapshot(&self) -> Bytes;
+    /// Replaces the current state with one produced by `snapshot`.
+    fn restore(&mut self, state: Bytes);
+}
+
+/// The snapshots of the registered states of a worker, by name.
+#[derive(Default)]
+pub struct Snapshot {
+    /// The snapshot of each state.
+    pub states: BTreeMap<String, Bytes>,
+}
+
+impl ::std::fmt::Debug for Snapshot {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        f.debug_map()
+            .entries(self.states.iter().map(|(name, state)| (name, state.len())))
+            .finish()
+    }
+}
+
+/// The registered states of a worker, and the snapshots awaiting their registration.
+#[derive(Default)]
+pub struct Registry {
+    states: BTreeMap<String, Weak<RefCell<dyn Checkpoint>>>,
+    pending: HashMap<String, Bytes>,
+}
+
+impl Registry {
+    /// Registers `state` under `name`, restoring it from a pending snapshot if there is one.
+    ///
+    /// This method panics if a state that is still live was registered under the same name.
+    pub fn register<S: Checkpoint + 'static>(&mut self, name: &str, state: Rc<RefCell<S>>) {
+        if self.states.get(name).map_or(false, |state| state.strong_count() > 0) {
+            panic!("checkpoint: state {:?} is already registered", name);
+        }
+        if let Some(snapshot) = self.pending.remove(name) {
+            state.borrow_mut().restore(snapshot);
+        }
+        let state: Rc<RefCell<dyn Checkpoint>> = state;
+        self.states.insert(name.to_owned(), Rc::downgrade(&state));
+    }
+
+    /// Snapshots the live states, and forgets those that have been dropped.
+    pub fn snapshot(&mut self) -> Snapshot {
+        let mut states = BTreeMap::new();
+        self.states.retain(|name, state| {
+            if let Some(state) = state.upgrade() {
+                states.insert(name.clone(), state.borrow().snapshot());
+                true
+            }
+            else { false }
+        });
+        Snapshot { states }
+    }
+
+    /// Retains the states of `snapshot`, to restore each when a state is next registered under its name.
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        self.pending.extend(snapshot.states);
+    }
+}
+
+impl ::std::fmt::Debug for Registry {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        f.debug_struct("Registry")
+            .field("states", &self.states.keys().collect::<Vec<_>>())
+            .field("pending", &self.pending.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Returns the checkpoint registry of the worker, creating it if necessary.
+pub fn registry<A: AsWorker>(worker: &A) -> Rc<RefCell<Registry>> {
+    let mut resources = worker.resources();
+    if let Some(registry) = resources.get::<Registry>() {
+        registry
+    }
+    else {
+        resources.register(Registry::default);
+        resources.get::<Registry>().expect("checkpoint registry just registered")
+    }
+}
+
+/// Registers `state` under `name` with the checkpoint registry of the worker.
+///
+/// If the worker was restored from a snapshot with a state of this name, `state` is restored
+/// from it before this method returns. The registry does not keep `state` alive, and a state that
+/// has been dropped, for example along with its dataflow, is no longer snapshotted.
+///
+/// This method panics if a state that is still live was registered under the same name.
+pub fn register<A: AsWorker, S: Checkpoint + 'static>(worker: &A, name: &str, state: Rc<RefCell<S>>) {
+    registry(worker).borrow_mut().register(name, state);
+}
@@ -0,0 +1,145 @@
+//! A container of opaque byte payloads.
+//!
+//! Forwarding nodes, such as the shards of a routing proxy, often move records between workers
+//! without looking inside them. A `BytesBatch` holds each record as an uninterpreted byte slice,
+//! so that such nodes can exchange records with `exchange`, routing on a prefix or a hash of the
+//! payload, while only the endpoints that produce and consume the records decode them.
+//!
+//! The payloads are stored contiguously, and so are serialized as two vectors rather than as
+//! individual records. Without the `bincode` feature, received batches are decoded in place,
+//! pointing into the bytes they arrived in, and payloads are not copied until they are sent on.
+//!
+//! # Examples
+//! ```
+//! use timely::dataflow::InputHandleCore;
+//! use timely::dataflow::channels::bytes::BytesBatch;
+//! use timely::dataflow::operators::{Exchange, Inspect, Probe};
+//! use timely::dataflow::operators::core::Input;
+//!
+//! timely::execute(timely::Config::process(2), |worker| {
+//!     let mut input = InputHandleCore::<u64, BytesBatch>::new();
+//!     let probe = worker.dataflow(|scope| {
+//!         scope.input_from(&mut input)
+//!              .exchange(|payload| payload[0] as u64)
+//!              .inspect(|payload| println!("seen: {:?}", payload))
+//!              .probe()
+//!     });
+//!
+//!     input.send(&b"\x00hello"[..]);
+//!     input.send(&b"\x01world"[..]);
+//!     input.advance_to(1);
+//!     worker.step_while(|| probe.less_than(input.time()));
+//! }).unwrap();
+//! ```
+
+use crate::container::{Container, ContainerBytes, PushInto, SizableContainer};
+
+/// A container of byte slices, stored contiguously.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Abomonation, Serialize, Deserialize)]
+pub struct BytesBatch {
+    /// The concatenated payloads.
+    data: Vec<u8>,
+    /// The offset in `data` at which each payload ends.
+    bounds: Vec<usize>,
+}
+
+impl BytesBatch {
+    /// Allocates an empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The payload at `index`, if it exists.
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        let upper = *self.bounds.get(index)?;
+        let lower = if index == 0 { 0 } else { self.bounds[index - 1] };
+        Some(&self.data[lower .. upper])
+    }
+}
+
+/// An iterator over the payloads of a `BytesBatch`.
+#[derive(Clone, Debug)]
+pub struct BytesIter<'a> {
+    batch: &'a BytesBatch,
+    index: usize,
+}
+
+impl<'a> Iterator for BytesIter<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let payload = self.batch.get(self.index)?;
+        self.index += 1;
+        Some(payload)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.batch.bounds.len() - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl Container for BytesBatch {
+    type ItemRef<'a> = &'a [u8];
+    type Item<'a> = &'a [u8];
+
+    fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    fn clear(&mut self) {
+        self.data.clear();
+        self.bounds.clear();
+    }
+
+    type Iter<'a> = BytesIter<'a>;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        BytesIter { batch: self, index: 0 }
+    }
+
+    type DrainIter<'a> = BytesIter<'a>;
+
+    fn drain(&mut self) -> Self::DrainIter<'_> {
+        BytesIter { batch: self, index: 0 }
+    }
+}
+
+impl SizableContainer for BytesBatch {
+    fn capacity(&self) -> usize {
+        self.bounds.capacity()
+    }
+
+    fn preferred_capacity() -> usize {
+        crate::container::buffer::default_capacity::<usize>()
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.bounds.reserve(additional);
+    }
+}
+
+impl ContainerBytes for BytesBatch {
+    fn length_in_bytes(&self) -> usize {
+        self.data.len()
+    }
+}
+
+impl PushInto<&[u8]> for BytesBatch {
+    fn push_into(&mut self, item: &[u8]) {
+        self.data.extend_from_slice(item);
+        self.bounds.push(self.data.len());
+    }
+}
+
+impl PushInto<&&[u8]> for BytesBatch {
+    fn push_into(&mut self, item: &&[u8]) {
+        self.push_into(*item)
+    }
+}
+
+impl PushInto<Vec<u8>> for BytesBatch {
+    fn push_into(&mut self, item: Vec<u8>) {
+        self.push_into(&item[..])
+    }
+}
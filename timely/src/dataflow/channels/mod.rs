@@ -9,6 +9,7 @@ pub mod pushers;
 pub mod pullers;
 /// Parallelization contracts, describing how data must be exchanged between operators.
 pub mod pact;
+pub mod bytes;
 
 /// The input to and output from timely dataflow communication channels.
 pub type Bundle<T, C> = crate::communication::Message<Message<T, C>>;
@@ -8,6 +8,9 @@
 //! The progress tracking logic assumes that this number is independent of the pact used.
 
 use std::{fmt::{self, Debug}, marker::PhantomData};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
 
 use crate::Container;
 use crate::communication::allocator::thread::{ThreadPusher, ThreadPuller};
@@ -44,7 +47,7 @@ impl<T: 'static, C: Container> ParallelizationContract<T, C> for Pipeline {
 }
 
 /// An exchange between multiple observers by data
-pub struct ExchangeCore<C, F> { hash_func: F, phantom: PhantomData<C> }
+pub struct ExchangeCore<C, F> { hash_func: F, mix: Option<fn(u64) -> u64>, phantom: PhantomData<C> }
 
 /// [ExchangeCore] specialized to vector-based containers.
 pub type Exchange<D, F> = ExchangeCore<Vec<D>, F>;
@@ -58,6 +61,54 @@ where
     pub fn new(func: F) -> ExchangeCore<C, F> {
         ExchangeCore {
             hash_func:  func,
+            mix:        None,
+            phantom:    PhantomData,
+        }
+    }
+
+    /// Applies `mix` to the result of the distribution function before it selects a worker.
+    ///
+    /// Records are routed to the worker indicated by the distribution function modulo the number
+    /// of workers, or by its low bits when that number is a power of two. Keys with structure in
+    /// those bits, such as multiples of a factor of the number of workers, are then spread over only
+    /// some of the workers. A mixing function such as [fibonacci_mix] spreads them evenly, at the cost
+    /// of a multiplication per record. Without a mixing function, the distribution function is used
+    /// as is, so that callers can route records to chosen workers.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Inspect};
+    /// use timely::dataflow::operators::generic::operator::Operator;
+    /// use timely::dataflow::channels::pact::{Exchange, fibonacci_mix};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..1000u64)
+    ///         .to_stream(scope)
+    ///         .unary(Exchange::new(|x: &u64| *x * 6).with_mixer(fibonacci_mix), "Mixed", |_, _| {
+    ///             let mut vector = Vec::new();
+    ///             move |input, output| {
+    ///                 input.for_each(|time, data| {
+    ///                     data.swap(&mut vector);
+    ///                     output.session(&time).give_container(&mut vector);
+    ///                 });
+    ///             }
+    ///         })
+    ///         .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    pub fn with_mixer(mut self, mix: fn(u64) -> u64) -> ExchangeCore<C, F> {
+        self.mix = Some(mix);
+        self
+    }
+
+    /// Bounds the number of records in flight from each worker to each other worker by `limit`.
+    ///
+    /// See [ExchangeCredited] for details.
+    pub fn with_credits(self, limit: usize) -> ExchangeCredited<C, F> {
+        ExchangeCredited {
+            hash_func:  self.hash_func,
+            mix:        self.mix,
+            limit,
             phantom:    PhantomData,
         }
     }
@@ -75,7 +126,7 @@ where
     fn connect<A: AsWorker>(self, allocator: &mut A, identifier: usize, address: &[usize], logging: Option<Logger>) -> (Self::Pusher, Self::Puller) {
         let (senders, receiver) = allocator.allocate::<Message<T, C>>(identifier, address);
         let senders = senders.into_iter().enumerate().map(|(i,x)| LogPusher::new(x, allocator.index(), i, identifier, logging.clone())).collect::<Vec<_>>();
-        (ExchangePusher::new(senders, self.hash_func), LogPuller::new(receiver, allocator.index(), identifier, logging.clone()))
+        (ExchangePusher::with_mix(senders, self.hash_func, self.mix), LogPuller::new(receiver, allocator.index(), identifier, logging.clone()))
     }
 }
 
@@ -85,6 +136,278 @@ impl<C, F> Debug for ExchangeCore<C, F> {
     }
 }
 
+/// Mixes the bits of `hash` by fibonacci hashing, for use with [ExchangeCore::with_mixer].
+///
+/// The hash is multiplied by 2^64 divided by the golden ratio, which moves entropy from its low
+/// bits to its high bits, and the halves of the product are then swapped so that the well-mixed
+/// high bits select the worker.
+#[inline]
+pub fn fibonacci_mix(hash: u64) -> u64 {
+    hash.wrapping_mul(0x9e37_79b9_7f4a_7c15).rotate_left(32)
+}
+
+/// An exchange between multiple observers by data, bounding the records in flight between workers.
+///
+/// Each worker may have at most `limit` records in flight to each other worker, where records
+/// are in flight from when they are sent until the receiving worker has pulled them from its
+/// input. Messages beyond the limit are held back by the sending worker, and sent as receivers
+/// return credit for the records they have pulled. A message larger than `limit` is sent once
+/// nothing else is in flight to its target. This bounds records rather than bytes, and holds
+/// back data at the sender rather than blocking it, so the sender's memory is not bounded.
+///
+/// Credit is returned on a separate channel whenever the receiving operator drains its input,
+/// and received credit is applied whenever the operator reads its input. Credit must flow even
+/// while data is held back, or both would wait on each other: the receiving operator must read
+/// its input whenever it is scheduled, as operators that drain their input with `for_each` or
+/// `next` do, and arriving credit schedules it for this purpose.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::{ToStream, Inspect};
+/// use timely::dataflow::operators::generic::operator::Operator;
+/// use timely::dataflow::channels::pact::Exchange;
+///
+/// timely::example(|scope| {
+///     (0..1000u64)
+///         .to_stream(scope)
+///         .unary(Exchange::new(|x: &u64| *x).with_credits(100), "Credited", |_, _| {
+///             let mut vector = Vec::new();
+///             move |input, output| {
+///                 input.for_each(|time, data| {
+///                     data.swap(&mut vector);
+///                     output.session(&time).give_container(&mut vector);
+///                 });
+///             }
+///         })
+///         .inspect(|x| println!("seen: {:?}", x));
+/// });
+/// ```
+pub struct ExchangeCredited<C, F> { hash_func: F, mix: Option<fn(u64) -> u64>, limit: usize, phantom: PhantomData<C> }
+
+impl<T: Timestamp, C, H: 'static> ParallelizationContract<T, C> for ExchangeCredited<C, H>
+where
+    C: Data + PushPartitioned,
+    for<'a> H: FnMut(&C::Item<'a>) -> u64
+{
+    type Pusher = ExchangePusher<T, C, LogPusher<T, C, CreditPusher<T, C>>, H>;
+    type Puller = LogPuller<T, C, CreditPuller<T, C>>;
+
+    fn connect<A: AsWorker>(self, allocator: &mut A, identifier: usize, address: &[usize], logging: Option<Logger>) -> (Self::Pusher, Self::Puller) {
+        let (senders, receiver) = allocator.allocate::<Message<T, C>>(identifier, address);
+        // Credit is returned on a channel of its own, which all workers allocate identically.
+        let credit_identifier = allocator.new_identifier();
+        let (credit_senders, credit_receiver) = allocator.allocate::<(usize, usize)>(credit_identifier, address);
+
+        let peers = senders.len();
+        let state = Rc::new(RefCell::new(CreditState {
+            held: (0 .. peers).map(|_| VecDeque::new()).collect(),
+            in_flight: vec![0; peers],
+            limit: self.limit,
+            pushers: senders,
+        }));
+        let senders = (0 .. peers).map(|i| {
+            let pusher = CreditPusher { target: i, state: state.clone() };
+            LogPusher::new(pusher, allocator.index(), i, identifier, logging.clone())
+        }).collect::<Vec<_>>();
+        let puller = CreditPuller {
+            puller: receiver,
+            state,
+            consumed: vec![0; peers],
+            credit_senders,
+            credit_receiver,
+            index: allocator.index(),
+        };
+        (ExchangePusher::with_mix(senders, self.hash_func, self.mix), LogPuller::new(puller, allocator.index(), identifier, logging))
+    }
+}
+
+impl<C, F> Debug for ExchangeCredited<C, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExchangeCredited").field("limit", &self.limit).finish()
+    }
+}
+
+/// Per-worker state of a credited exchange, shared by its pushers and its puller.
+struct CreditState<T, C> {
+    /// Pushers to each worker.
+    pushers: Vec<Box<dyn Push<Bundle<T, C>>>>,
+    /// Messages held back for each worker, for lack of credit.
+    held: Vec<VecDeque<Bundle<T, C>>>,
+    /// Records sent to each worker, for which credit has not yet been returned.
+    in_flight: Vec<usize>,
+    /// The bound on records in flight to each worker.
+    limit: usize,
+}
+
+impl<T, C: Container> CreditState<T, C> {
+    /// Sends held messages to `target` in order, for as long as credit allows.
+    fn release(&mut self, target: usize) {
+        while let Some(bundle) = self.held[target].front() {
+            let records = bundle.data.len();
+            if self.in_flight[target] > 0 && self.in_flight[target] + records > self.limit {
+                break;
+            }
+            self.in_flight[target] += records;
+            let mut bundle = self.held[target].pop_front();
+            self.pushers[target].push(&mut bundle);
+        }
+    }
+    /// Returns credit for `records` records received by `target`, and sends what it allows.
+    fn credit(&mut self, target: usize, records: usize) {
+        self.in_flight[target] = self.in_flight[target].saturating_sub(records);
+        if !self.held[target].is_empty() {
+            self.release(target);
+            self.pushers[target].done();
+        }
+    }
+}
+
+/// Pushes messages to one worker of a credited exchange, holding them back while credit is exhausted.
+pub struct CreditPusher<T, C> {
+    target: usize,
+    state: Rc<RefCell<CreditState<T, C>>>,
+}
+
+impl<T, C: Container> Push<Bundle<T, C>> for CreditPusher<T, C> {
+    #[inline]
+    fn push(&mut self, element: &mut Option<Bundle<T, C>>) {
+        let mut state = self.state.borrow_mut();
+        if let Some(bundle) = element.take() {
+            state.held[self.target].push_back(bundle);
+            state.release(self.target);
+        }
+        else {
+            state.pushers[self.target].done();
+        }
+    }
+}
+
+/// Pulls messages from a credited exchange, returning credit to their senders and applying
+/// credit returned by other workers.
+pub struct CreditPuller<T, C> {
+    puller: Box<dyn Pull<Bundle<T, C>>>,
+    state: Rc<RefCell<CreditState<T, C>>>,
+    /// Records pulled from each worker, for which credit has not yet been returned.
+    consumed: Vec<usize>,
+    credit_senders: Vec<Box<dyn Push<crate::communication::Message<(usize, usize)>>>>,
+    credit_receiver: Box<dyn Pull<crate::communication::Message<(usize, usize)>>>,
+    index: usize,
+}
+
+impl<T, C: Container> Pull<Bundle<T, C>> for CreditPuller<T, C> {
+    #[inline]
+    fn pull(&mut self) -> &mut Option<Bundle<T, C>> {
+        while let Some(message) = self.credit_receiver.recv() {
+            let (source, records) = *message;
+            self.state.borrow_mut().credit(source, records);
+        }
+        let result = self.puller.pull();
+        if let Some(bundle) = result {
+            self.consumed[bundle.from] += bundle.data.len();
+        }
+        else {
+            // The input is drained for now; return the credit accumulated for each sender.
+            for (source, records) in self.consumed.iter_mut().enumerate() {
+                if *records > 0 {
+                    self.credit_senders[source].send(crate::communication::Message::from_typed((self.index, *records)));
+                    self.credit_senders[source].done();
+                    *records = 0;
+                }
+            }
+        }
+        result
+    }
+}
+
+/// An exchange between multiple observers that balances the number of bytes sent to each.
+///
+/// Rather than hashing records, each record is sent to the worker that has so far been sent
+/// the fewest bytes, as estimated by the supplied size function. The assignment depends only
+/// on the order of records presented to the pact, and so is deterministic given the same input.
+/// Records with equal keys are not guaranteed to arrive at the same worker; use [ExchangeCore]
+/// if the downstream operator requires this.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::{ToStream, Inspect};
+/// use timely::dataflow::operators::generic::operator::Operator;
+/// use timely::dataflow::channels::pact::ExchangeBalanced;
+///
+/// timely::example(|scope| {
+///     (0..10u64)
+///         .map(|x| vec![0u8; x as usize])
+///         .to_stream(scope)
+///         .unary(ExchangeBalanced::new(|x: &Vec<u8>| x.len()), "Balanced", |_, _| {
+///             let mut vector = Vec::new();
+///             move |input, output| {
+///                 input.for_each(|time, data| {
+///                     data.swap(&mut vector);
+///                     output.session(&time).give_container(&mut vector);
+///                 });
+///             }
+///         })
+///         .inspect(|x| println!("seen: {:?}", x.len()));
+/// });
+/// ```
+pub struct ExchangeBalanced<C, F> { size_func: F, phantom: PhantomData<C> }
+
+impl<C, F> ExchangeBalanced<C, F>
+where
+    C: PushPartitioned,
+    for<'a> F: FnMut(&C::Item<'a>)->usize
+{
+    /// Allocates a new `ExchangeBalanced` pact from a function estimating the size of a record in bytes.
+    pub fn new(func: F) -> ExchangeBalanced<C, F> {
+        ExchangeBalanced {
+            size_func:  func,
+            phantom:    PhantomData,
+        }
+    }
+}
+
+/// Routing function produced by `ExchangeBalanced`, mapping records to pusher indices.
+type BalancedRoute<C> = Box<dyn for<'a> FnMut(&<C as Container>::Item<'a>) -> u64>;
+
+impl<T: Timestamp, C, F: 'static> ParallelizationContract<T, C> for ExchangeBalanced<C, F>
+where
+    C: Data + PushPartitioned,
+    for<'a> F: FnMut(&C::Item<'a>) -> usize
+{
+    type Pusher = ExchangePusher<T, C, LogPusher<T, C, Box<dyn Push<Bundle<T, C>>>>, BalancedRoute<C>>;
+    type Puller = LogPuller<T, C, Box<dyn Pull<Bundle<T, C>>>>;
+
+    fn connect<A: AsWorker>(self, allocator: &mut A, identifier: usize, address: &[usize], logging: Option<Logger>) -> (Self::Pusher, Self::Puller) {
+        let (senders, receiver) = allocator.allocate::<Message<T, C>>(identifier, address);
+        let senders = senders.into_iter().enumerate().map(|(i,x)| LogPusher::new(x, allocator.index(), i, identifier, logging.clone())).collect::<Vec<_>>();
+
+        // Bytes sent to each worker so far. Ties are broken starting from this worker's own
+        // index, so that workers do not all direct their first records at worker zero.
+        let peers = senders.len();
+        let offset = allocator.index();
+        let mut loads = vec![0usize; peers];
+        let mut size_func = self.size_func;
+        let route: BalancedRoute<C> = Box::new(move |datum| {
+            let mut target = offset % peers;
+            for step in 1 .. peers {
+                let index = (offset + step) % peers;
+                if loads[index] < loads[target] {
+                    target = index;
+                }
+            }
+            loads[target] = loads[target].saturating_add(size_func(datum));
+            target as u64
+        });
+
+        (ExchangePusher::new(senders, route), LogPuller::new(receiver, allocator.index(), identifier, logging.clone()))
+    }
+}
+
+impl<C, F> Debug for ExchangeBalanced<C, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExchangeBalanced").finish()
+    }
+}
+
 /// Wraps a `Message<T,D>` pusher to provide a `Push<(T, Content<D>)>`.
 #[derive(Debug)]
 pub struct LogPusher<T, C, P: Push<Bundle<T, C>>> {
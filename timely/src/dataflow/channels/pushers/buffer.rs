@@ -144,11 +144,55 @@ where
         self.buffer.give_container(container)
     }
 
+    /// Provide a container at the time specified by the [Session], and send it immediately.
+    ///
+    /// This is `give_container` followed by `flush`.
+    pub fn give_container_now(&mut self, container: &mut CB::Container) {
+        self.give_container(container);
+        self.flush();
+    }
+
+    /// Sends all data given to the session so far, rather than waiting for containers to fill.
+    ///
+    /// The builder's partial containers are sent, and the downstream pushers are asked to send
+    /// any data they buffer, as happens when the output handle is dropped. Sent data is counted
+    /// for progress tracking as usual. Flushing frequently sends smaller containers, which trades
+    /// throughput for latency.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::ToStream;
+    /// use timely::dataflow::operators::generic::operator::Operator;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    ///
+    /// timely::example(|scope| {
+    ///     (0u64..10).to_stream(scope)
+    ///         .unary(Pipeline, "SendEagerly", |_cap, _info| {
+    ///             let mut vector = Vec::new();
+    ///             move |input, output| {
+    ///                 while let Some((time, data)) = input.next() {
+    ///                     data.swap(&mut vector);
+    ///                     output.session(&time).give_container_now(&mut vector);
+    ///                 }
+    ///             }
+    ///         });
+    /// });
+    /// ```
+    pub fn flush(&mut self) {
+        self.buffer.cease();
+    }
+
     /// Access the builder. Immutable access to prevent races with flushing
     /// the underlying buffer.
     pub fn builder(&self) -> &CB {
         self.buffer.builder()
     }
+
+    /// Indicates that roughly `hint` records are about to be given, so that the builder
+    /// can size the containers it allocates next.
+    pub fn capacity_hint(&mut self, hint: usize) {
+        self.buffer.builder.capacity_hint(hint);
+    }
 }
 
 impl<'a, T, CB, P: Push<Bundle<T, CB::Container>>+'a> Session<'a, T, CB, P>
@@ -164,6 +208,11 @@ where
     }
 
     /// Provides an iterator of records at the time specified by the `Session`.
+    ///
+    /// Each container the builder completes is sent as soon as it is complete, so the records
+    /// held back by the session are bounded by the builder's chunking, whatever the length of
+    /// the iterator. With the default `CapacityContainerBuilder`, this is one container of the
+    /// preferred capacity.
     #[inline]
     pub fn give_iterator<I>(&mut self, iter: I)
     where
@@ -201,6 +250,8 @@ where
         self.buffer.give(data);
     }
     /// Transmits records produced by an iterator.
+    ///
+    /// As with `Session::give_iterator`, completed containers are sent as they fill.
     #[inline]
     pub fn give_iterator<I, D>(&mut self, iter: I)
     where
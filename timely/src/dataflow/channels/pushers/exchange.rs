@@ -15,6 +15,8 @@ where
     buffers: Vec<C>,
     current: Option<T>,
     hash_func: H,
+    /// Applied to the result of `hash_func` before it is reduced to a pusher index.
+    mix: Option<fn(u64) -> u64>,
 }
 
 impl<T: Clone, C: PushPartitioned, P: Push<Bundle<T, C>>, H>  Exchange<T, C, P, H>
@@ -23,6 +25,10 @@ where
 {
     /// Allocates a new `Exchange` from a supplied set of pushers and a distribution function.
     pub fn new(pushers: Vec<P>, key: H) -> Exchange<T, C, P, H> {
+        Self::with_mix(pushers, key, None)
+    }
+    /// Allocates a new `Exchange` that applies `mix`, if supplied, to the result of the distribution function.
+    pub fn with_mix(pushers: Vec<P>, key: H, mix: Option<fn(u64) -> u64>) -> Exchange<T, C, P, H> {
         let mut buffers = vec![];
         for _ in 0..pushers.len() {
             buffers.push(Default::default());
@@ -30,6 +36,7 @@ where
         Exchange {
             pushers,
             hash_func: key,
+            mix,
             buffers,
             current: None,
         }
@@ -70,6 +77,7 @@ where
             self.current = Some(time.clone());
 
             let hash_func = &mut self.hash_func;
+            let mix = self.mix;
 
             // if the number of pushers is a power of two, use a mask
             if (self.pushers.len() & (self.pushers.len() - 1)) == 0 {
@@ -77,7 +85,10 @@ where
                 let pushers = &mut self.pushers;
                 data.push_partitioned(
                     &mut self.buffers,
-                    move |datum| ((hash_func)(datum) & mask) as usize,
+                    move |datum| {
+                        let hash = (hash_func)(datum);
+                        (mix.map_or(hash, |mix| mix(hash)) & mask) as usize
+                    },
                     |index, buffer| {
                             Message::push_at(buffer, time.clone(), &mut pushers[index]);
                     }
@@ -89,7 +100,10 @@ where
                 let pushers = &mut self.pushers;
                 data.push_partitioned(
                     &mut self.buffers,
-                    move |datum| ((hash_func)(datum) % num_pushers) as usize,
+                    move |datum| {
+                        let hash = (hash_func)(datum);
+                        (mix.map_or(hash, |mix| mix(hash)) % num_pushers) as usize
+                    },
                     |index, buffer| {
                         Message::push_at(buffer, time.clone(), &mut pushers[index]);
                     }
@@ -18,6 +18,8 @@ pub use self::scopes::{Scope, ScopeParent};
 
 pub use self::operators::core::input::Handle as InputHandleCore;
 pub use self::operators::input::Handle as InputHandle;
+pub use self::operators::core::input::AutoHandle as AutoInputHandleCore;
+pub use self::operators::input::AutoHandle as AutoInputHandle;
 pub use self::operators::probe::Handle as ProbeHandle;
 
 pub mod operators;
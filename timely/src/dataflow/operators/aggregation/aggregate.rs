@@ -5,7 +5,9 @@ use std::collections::HashMap;
 use crate::{Data, ExchangeData};
 use crate::dataflow::{Stream, Scope};
 use crate::dataflow::operators::generic::operator::Operator;
+use crate::dataflow::operators::generic::FrontierNotificator;
 use crate::dataflow::channels::pact::Exchange;
+use crate::memory::MemoryAccount;
 
 /// Generic intra-timestamp aggregation
 ///
@@ -22,6 +24,11 @@ pub trait Aggregate<S: Scope, K: ExchangeData+Hash, V: ExchangeData> {
     /// Aggregation happens within each time, and results are produced once the
     /// time is complete.
     ///
+    /// With a limit configured by `operator_memory_limit`, the operator reports the number of
+    /// aggregates it holds, times the size of a key and its aggregate, to a [MemoryAccount].
+    /// As aggregates are only emitted once their time is complete, the operator does not
+    /// release them under `MemoryPolicy::Flush`.
+    ///
     /// # Examples
     /// ```
     /// use timely::dataflow::operators::{ToStream, Map, Inspect};
@@ -60,6 +67,31 @@ pub trait Aggregate<S: Scope, K: ExchangeData+Hash, V: ExchangeData> {
     ///         .inspect(|x| assert!(*x == (0, 5) || *x == (1, 5)));
     /// });
     /// ```
+    ///
+    /// Counting words is an aggregation keyed by the word, whose state is a count. The state of
+    /// each time is released once the counts for that time have been emitted.
+    ///
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Map, Inspect};
+    /// use timely::dataflow::operators::aggregation::Aggregate;
+    ///
+    /// timely::example(|scope| {
+    ///
+    ///     vec!["a rose", "is a rose", "is a rose"]
+    ///         .to_stream(scope)
+    ///         .flat_map(|line| line.split_whitespace().map(|word| (word.to_owned(), 1)).collect::<Vec<_>>())
+    ///         .aggregate(
+    ///             |_word, count, total| { *total += count; },
+    ///             |word, total: u64| (word, total),
+    ///             |word| word.len() as u64
+    ///         )
+    ///         .inspect(|(word, total)| match word.as_str() {
+    ///             "a" | "rose" => assert_eq!(*total, 3),
+    ///             "is" => assert_eq!(*total, 2),
+    ///             _ => panic!("unexpected word: {}", word),
+    ///         });
+    /// });
+    /// ```
     fn aggregate<R: Data, D: Default+'static, F: Fn(&K, V, &mut D)+'static, E: Fn(K, D)->R+'static, H: Fn(&K)->u64+'static>(
         &self,
         fold: F,
@@ -77,28 +109,41 @@ impl<S: Scope, K: ExchangeData+Hash+Eq, V: ExchangeData> Aggregate<S, K, V> for
 
         let mut aggregates = HashMap::new();
         let mut vector = Vec::new();
-        self.unary_notify(Exchange::new(move |&(ref k, _)| hash(k)), "Aggregate", vec![], move |input, output, notificator| {
+        let scope = self.scope();
+        // Built with `unary_frontier`, rather than `unary_notify`, for the operator info of the account.
+        self.unary_frontier(Exchange::new(move |&(ref k, _)| hash(k)), "Aggregate", move |_, info| {
 
-            // read each input, fold into aggregates
-            input.for_each(|time, data| {
-                data.swap(&mut vector);
-                let agg_time = aggregates.entry(time.time().clone()).or_insert_with(HashMap::new);
-                for (key, val) in vector.drain(..) {
-                    let agg = agg_time.entry(key.clone()).or_insert_with(Default::default);
-                    fold(&key, val, agg);
-                }
-                notificator.notify_at(time.retain());
-            });
+            let mut account = MemoryAccount::new(&scope, &info, "Aggregate");
+            let mut notificator = FrontierNotificator::new();
+            move |input, output| {
+
+                // read each input, fold into aggregates
+                input.for_each(|time, data| {
+                    data.swap(&mut vector);
+                    let agg_time = aggregates.entry(time.time().clone()).or_insert_with(HashMap::new);
+                    for (key, val) in vector.drain(..) {
+                        let agg = agg_time.entry(key.clone()).or_insert_with(Default::default);
+                        fold(&key, val, agg);
+                    }
+                    notificator.notify_at(time.retain());
+                });
 
-            // pop completed aggregates, send along whatever
-            notificator.for_each(|time,_,_| {
-                if let Some(aggs) = aggregates.remove(time.time()) {
-                    let mut session = output.session(&time);
-                    for (key, agg) in aggs {
-                        session.give(emit(key, agg));
+                // pop completed aggregates, send along whatever
+                notificator.for_each(&[input.frontier()], |time, _| {
+                    if let Some(aggs) = aggregates.remove(time.time()) {
+                        let mut session = output.session(&time);
+                        for (key, agg) in aggs {
+                            session.give(emit(key, agg));
+                        }
                     }
+                });
+
+                // Aggregates cannot be emitted before their time completes, so only report them.
+                if let Some(account) = account.as_mut() {
+                    let entries: usize = aggregates.values().map(HashMap::len).sum();
+                    account.report(entries * ::std::mem::size_of::<(K, D)>());
                 }
-            });
+            }
         })
 
     }
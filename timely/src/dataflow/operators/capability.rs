@@ -88,6 +88,14 @@ impl<T: Timestamp> Capability<T> {
         &self.time
     }
 
+    /// Returns true if the capability can be delayed or downgraded to `time`.
+    ///
+    /// This is the case exactly when `self.time` is less or equal to `time`, and is the condition
+    /// under which `delayed` and `downgrade` do not panic.
+    pub fn can_downgrade_to(&self, time: &T) -> bool {
+        self.time.less_equal(time)
+    }
+
     /// Makes a new capability for a timestamp `new_time` greater or equal to the timestamp of
     /// the source capability (`self`).
     ///
@@ -117,7 +125,7 @@ impl<T: Timestamp> Capability<T> {
     ///
     /// Returns [`None`] `self.time` is not less or equal to `new_time`.
     pub fn try_delayed(&self, new_time: &T) -> Option<Capability<T>> {
-        if self.time.less_equal(new_time) {
+        if self.can_downgrade_to(new_time) {
             Some(Self::new(new_time.clone(), self.internal.clone()))
         } else {
             None
@@ -383,6 +391,9 @@ impl<T: Timestamp> Drop for ActivateCapability<T> {
 }
 
 /// A set of capabilities, for possibly incomparable times.
+///
+/// The set holds the minimal antichain of its capabilities' times: inserting or downgrading to a
+/// time that is greater or equal to a held time releases the redundant capability.
 #[derive(Clone, Debug)]
 pub struct CapabilitySet<T: Timestamp> {
     elements: Vec<Capability<T>>,
@@ -503,22 +514,21 @@ impl<T: Timestamp> CapabilitySet<T> {
 
     /// Attempts to downgrade the set of capabilities to correspond with the times in `frontier`.
     ///
-    /// Returns [`None`] if any element of `frontier` is not greater or equal to some element of `self.elements`.
-    ///
-    /// **Warning**: If an error is returned the capability set may be in an inconsistent state and can easily
-    /// cause logic errors within the program if not properly handled.
+    /// Returns an error if any element of `frontier` is not greater or equal to some element of `self.elements`,
+    /// in which case the set is left unchanged.
     ///
+    /// The times in `frontier` need not form an antichain; redundant times are discarded as by `insert`.
     pub fn try_downgrade<B, F>(&mut self, frontier: F) -> Result<(), DowngradeError>
     where
         B: borrow::Borrow<T>,
         F: IntoIterator<Item = B>,
     {
-        let count = self.elements.len();
+        let mut downgraded = Self::with_capacity(self.elements.len());
         for time in frontier.into_iter() {
             let capability = self.try_delayed(time.borrow()).ok_or(DowngradeError(()))?;
-            self.elements.push(capability);
+            downgraded.insert(capability);
         }
-        self.elements.drain(..count);
+        *self = downgraded;
 
         Ok(())
     }
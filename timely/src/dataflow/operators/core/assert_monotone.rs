@@ -0,0 +1,46 @@
+//! Asserts that records arrive at times the input frontier has not passed.
+
+use crate::Container;
+use crate::dataflow::{Scope, StreamCore};
+
+/// Asserts that records arrive at times the input frontier has not passed.
+pub trait AssertMonotone<G: Scope, C: Container> {
+    /// Panics if a record's time is not greater or equal to the input frontier.
+    ///
+    /// The frontier is the one presented to the operator when it receives the record, and the
+    /// panic message reports both the time and the frontier. Progress tracking should prevent such
+    /// records, and this operator is intended as a guard behind sources that may violate it, so
+    /// that their errors are caught where they enter the dataflow rather than downstream. Records
+    /// are otherwise passed through unchanged. Use `flag_late` to report late records without
+    /// panicking.
+    ///
+    /// The check is only compiled in with debug assertions. Without them, as in release builds,
+    /// the stream is returned as is, without an operator.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::core::{ToStream, AssertMonotone, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .container::<Vec<_>>()
+    ///            .assert_monotone()
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn assert_monotone(&self) -> StreamCore<G, C>;
+}
+
+impl<G: Scope, C: Container> AssertMonotone<G, C> for StreamCore<G, C> {
+    #[cfg(debug_assertions)]
+    fn assert_monotone(&self) -> StreamCore<G, C> {
+        crate::dataflow::operators::core::flag_late::check_times(self, "AssertMonotone", |time, frontier, _| {
+            panic!("assert_monotone: record at time {:?} is not beyond the input frontier {:?}", time, &*frontier);
+        })
+    }
+
+    #[cfg(not(debug_assertions))]
+    fn assert_monotone(&self) -> StreamCore<G, C> {
+        self.clone()
+    }
+}
@@ -0,0 +1,118 @@
+//! Synchronizes all workers at each time.
+
+use std::collections::HashMap;
+
+use crate::Container;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::operators::Broadcast;
+use crate::dataflow::operators::frontier_stream::frontier_changes;
+use crate::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use crate::dataflow::{Scope, Stream, StreamCore};
+use crate::progress::{Antichain, Timestamp};
+
+/// Extension trait for synchronizing workers at each time.
+pub trait Barrier<G: Scope, C: Container> {
+    /// Holds the records of each time until every worker has signaled that the time is complete.
+    ///
+    /// Each worker signals whenever the frontier of its input advances, by sending the new
+    /// frontier to every worker as a token. A worker releases the records of a time once the
+    /// latest tokens of all workers, including its own, show the time complete, and so no worker
+    /// sends on the records of a time before every worker has received all of its records for
+    /// that time. This is the synchronization of a bulk synchronous round, made explicit rather
+    /// than implied by the progress tracking of the stream.
+    ///
+    /// The synchronization is not free. Each advance of a worker's frontier sends a token to
+    /// every worker, which is quadratic in the number of workers, and the records of a time are
+    /// delayed, and buffered, for at least the round trip of the slowest worker's token.
+    ///
+    /// The barrier cannot deadlock a dataflow in which its input does not depend on its output.
+    /// A worker's token depends only on the frontier of its input, and never on records the barrier
+    /// holds, so each worker signals a time complete as soon as its input is; the tokens of all
+    /// workers then arrive and release the time. If the output is fed back to the input through
+    /// a loop, the records held at a time hold back the input only at later times, by at least
+    /// the loop's summary, and the tokens for the time are sent regardless.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Exchange, Inspect};
+    /// use timely::dataflow::operators::core::Barrier;
+    ///
+    /// timely::execute(timely::Config::process(2), |worker| {
+    ///     worker.dataflow::<u64,_,_>(|scope| {
+    ///         (0..10).to_stream(scope)
+    ///                .exchange(|x| *x)
+    ///                .barrier()
+    ///                .inspect(|x| println!("released: {:?}", x));
+    ///     });
+    /// }).unwrap();
+    /// ```
+    fn barrier(&self) -> StreamCore<G, C>;
+}
+
+impl<G: Scope, C: Container> Barrier<G, C> for StreamCore<G, C> {
+    fn barrier(&self) -> StreamCore<G, C> {
+        let tokens = signal_frontiers(self).broadcast();
+
+        let mut builder = OperatorBuilder::new("Barrier".to_owned(), self.scope());
+
+        let mut input = builder.new_input(self, Pipeline);
+        let mut token_input = builder.new_input(&tokens, Pipeline);
+        let (mut output, stream) = builder.new_output();
+
+        builder.build_with_info(move |_, info| {
+            // The latest frontier each worker has signaled.
+            let mut signaled = vec![Antichain::from_elem(G::Timestamp::minimum()); info.peers()];
+            // The records held at each time, and the capability to send them.
+            let mut stash = HashMap::new();
+            let mut tokens = Vec::new();
+            move |_frontiers| {
+                input.for_each(|time, data| {
+                    let mut container = C::default();
+                    data.swap(&mut container);
+                    stash.entry(time.time().clone())
+                         .or_insert_with(|| (time.retain(), Vec::new()))
+                         .1
+                         .push(container);
+                });
+
+                token_input.for_each(|_time, data| {
+                    data.swap(&mut tokens);
+                    for (worker, frontier) in tokens.drain(..) {
+                        let frontier = Antichain::from(frontier);
+                        // Tokens arrive in order, but only advances are retained.
+                        if frontier.elements().iter().all(|time| signaled[worker].less_equal(time)) {
+                            signaled[worker] = frontier;
+                        }
+                    }
+                });
+
+                let mut output_handle = output.activate();
+                stash.retain(|time, (cap, containers)| {
+                    if signaled.iter().any(|frontier| frontier.less_equal(time)) {
+                        true
+                    }
+                    else {
+                        let mut session = output_handle.session(&*cap);
+                        for container in containers.iter_mut() {
+                            session.give_container(container);
+                        }
+                        false
+                    }
+                });
+            }
+        });
+
+        stream
+    }
+}
+
+/// Sends the frontier of `stream` as a token each time it advances, identified by worker.
+///
+/// Each token is sent at an element of the previous frontier, so that a token can be sent when
+/// the frontier becomes empty.
+fn signal_frontiers<G: Scope, C: Container>(stream: &StreamCore<G, C>) -> Stream<G, (usize, Vec<G::Timestamp>)> {
+    let index = stream.scope().index();
+    frontier_changes(stream, "BarrierSignal", move |previous, frontier| {
+        previous.first().map(|time| (time.clone(), (index, frontier.to_vec())))
+    })
+}
@@ -0,0 +1,136 @@
+//! Coalesces small containers into larger ones.
+
+use std::collections::HashMap;
+
+use crate::container::{Container, ContainerBytes, PushInto};
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Scope, StreamCore};
+use crate::dataflow::operators::generic::operator::Operator;
+use crate::memory::MemoryAccount;
+
+/// Extension trait for coalescing containers.
+pub trait Coalesce<G: Scope, C: Container> {
+    /// Buffers the records at each time until at least `min_records` have accumulated, or the
+    /// time is complete, and then sends them as a single container.
+    ///
+    /// The operator holds a capability for each time with buffered records, and sends the buffer
+    /// once the input frontier advances past its time, so that no records are retained once their
+    /// time is complete. Buffers are combined with `Container::append`, and records at each time
+    /// remain in the order they arrived.
+    ///
+    /// This is useful ahead of an exchange for sources that produce many small containers, as
+    /// each container sent to another worker carries a fixed overhead.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use timely::dataflow::operators::{ToStream, Inspect};
+    /// use timely::dataflow::operators::core::Coalesce;
+    ///
+    /// let lengths = Arc::new(Mutex::new(Vec::new()));
+    /// let sink = Arc::clone(&lengths);
+    ///
+    /// timely::example(move |scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .coalesce(1024)
+    ///            .inspect_batch(move |_t, xs| sink.lock().unwrap().push(xs.len()));
+    /// });
+    ///
+    /// // Fewer than `min_records` records, sent once their time completes.
+    /// assert_eq!(*lengths.lock().unwrap(), vec![10]);
+    /// ```
+    fn coalesce(&self, min_records: usize) -> StreamCore<G, C>;
+
+    /// Coalesces as `coalesce`, and reports the bytes buffered to a [MemoryAccount].
+    ///
+    /// If the buffered bytes exceed the limit configured with `operator_memory_limit` under
+    /// `MemoryPolicy::Flush`, the operator sends all of its buffers, whatever their sizes.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Inspect};
+    /// use timely::dataflow::operators::core::Coalesce;
+    /// use timely::worker::MemoryPolicy;
+    ///
+    /// let mut config = timely::Config::thread();
+    /// config.worker = config.worker.operator_memory_limit(1 << 20, MemoryPolicy::Flush);
+    /// timely::execute(config, |worker| {
+    ///     worker.dataflow::<u64,_,_>(|scope| {
+    ///         (0..10).to_stream(scope)
+    ///                .coalesce_accounted(1024)
+    ///                .inspect(|x| println!("seen: {:?}", x));
+    ///     });
+    /// }).unwrap();
+    /// ```
+    fn coalesce_accounted(&self, min_records: usize) -> StreamCore<G, C>
+    where
+        C: ContainerBytes;
+}
+
+impl<G: Scope, C: Container> Coalesce<G, C> for StreamCore<G, C>
+where
+    for<'a> C: PushInto<C::Item<'a>>
+{
+    fn coalesce(&self, min_records: usize) -> StreamCore<G, C> {
+        coalesce_with(self, min_records, None)
+    }
+
+    fn coalesce_accounted(&self, min_records: usize) -> StreamCore<G, C>
+    where
+        C: ContainerBytes,
+    {
+        coalesce_with(self, min_records, Some(C::length_in_bytes))
+    }
+}
+
+/// Coalesces the containers of `stream`, reporting the bytes `length_in_bytes` estimates, if any.
+fn coalesce_with<G: Scope, C: Container>(stream: &StreamCore<G, C>, min_records: usize, length_in_bytes: Option<fn(&C)->usize>) -> StreamCore<G, C>
+where
+    for<'a> C: PushInto<C::Item<'a>>
+{
+    let mut container = Default::default();
+    // Buffered records, and the capability to send them, by time.
+    let mut stash = HashMap::new();
+    let scope = stream.scope();
+    stream.unary_frontier(Pipeline, "Coalesce", move |_, info| {
+        let mut account = length_in_bytes.and_then(|length| {
+            MemoryAccount::new(&scope, &info, "Coalesce").map(|account| (account, length))
+        });
+        move |input, output| {
+            input.for_each(|time, data| {
+                data.swap(&mut container);
+                let (_, buffer) = stash
+                    .entry(time.time().clone())
+                    .or_insert_with(|| (time.retain(), C::default()));
+                buffer.append(&mut container);
+                if buffer.len() >= min_records {
+                    let (cap, mut buffer) = stash.remove(time.time()).unwrap();
+                    output.session(&cap).give_container(&mut buffer);
+                }
+            });
+
+            // Send the buffers of complete times, releasing their capabilities.
+            let frontier = input.frontier();
+            stash.retain(|time, (cap, buffer)| {
+                if frontier.less_equal(time) {
+                    true
+                }
+                else {
+                    output.session(&*cap).give_container(buffer);
+                    false
+                }
+            });
+
+            // Send all buffers if they exceed the operator's memory limit.
+            if let Some((account, length_in_bytes)) = account.as_mut() {
+                let bytes = stash.values().map(|(_, buffer)| length_in_bytes(buffer)).sum();
+                if account.report(bytes) {
+                    for (_, (cap, mut buffer)) in stash.drain() {
+                        output.session(&cap).give_container(&mut buffer);
+                    }
+                    account.report(0);
+                }
+            }
+        }
+    })
+}
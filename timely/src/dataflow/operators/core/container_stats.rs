@@ -0,0 +1,70 @@
+//! Reports the lengths and sizes of containers flowing through a stream.
+
+use crate::container::ContainerBytes;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Scope, StreamCore};
+use crate::dataflow::operators::generic::operator::Operator;
+use crate::logging::ContainerStatsEvent;
+
+/// Extension trait for reporting container statistics.
+pub trait ContainerStats<G: Scope, C: ContainerBytes> {
+    /// Passes containers through unchanged, logging the length of each and its estimated size
+    /// in bytes to the `"timely"` logger as a `ContainerStatsEvent`.
+    ///
+    /// The events identify the operator by its worker-unique identifier, and can be aggregated
+    /// into per-operator histograms by a `HistogramLogger`. The logger is looked up when the
+    /// operator is constructed, and if none is registered the operator only forwards containers.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Inspect};
+    /// use timely::dataflow::operators::core::ContainerStats;
+    /// use timely::logging::{HistogramLogger, TimelyEvent};
+    ///
+    /// let snapshot = timely::execute_directly(|worker| {
+    ///     let histograms = HistogramLogger::new();
+    ///     let mut logger = histograms.clone();
+    ///     worker.log_register().insert::<TimelyEvent,_>("timely", move |time, data| {
+    ///         logger.publish_batch(time, data);
+    ///     });
+    ///
+    ///     worker.dataflow::<u64,_,_>(|scope| {
+    ///         (0u64 .. 10).to_stream(scope)
+    ///                     .container_stats()
+    ///                     .inspect(|x| println!("seen: {:?}", x));
+    ///     });
+    ///     while worker.step() { }
+    ///
+    ///     histograms.snapshot()
+    /// });
+    ///
+    /// let lengths = snapshot.container_lengths.values().next().unwrap();
+    /// let bytes = snapshot.container_bytes.values().next().unwrap();
+    /// assert_eq!(lengths.sum(), 10);
+    /// assert_eq!(bytes.sum(), 80);
+    /// ```
+    fn container_stats(&self) -> StreamCore<G, C>;
+}
+
+impl<G: Scope, C: ContainerBytes> ContainerStats<G, C> for StreamCore<G, C> {
+    fn container_stats(&self) -> StreamCore<G, C> {
+        let logging = self.scope().logging();
+        let mut container = Default::default();
+        self.unary(Pipeline, "ContainerStats", move |_, info| {
+            let id = info.global_id;
+            move |input, output| {
+                input.for_each(|time, data| {
+                    data.swap(&mut container);
+                    if let Some(logging) = &logging {
+                        logging.log(ContainerStatsEvent {
+                            id,
+                            length: container.len(),
+                            bytes: container.length_in_bytes(),
+                        });
+                    }
+                    output.session(&time).give_container(&mut container);
+                });
+            }
+        })
+    }
+}
@@ -1,10 +1,15 @@
 //! Exchange records between workers.
 
+use std::collections::{BTreeMap, HashMap};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
 use crate::ExchangeData;
 use crate::container::PushPartitioned;
 use crate::dataflow::channels::pact::ExchangeCore;
 use crate::dataflow::operators::generic::operator::Operator;
 use crate::dataflow::{Scope, StreamCore};
+use crate::worker::AsWorker;
 
 /// Exchange records between workers.
 pub trait Exchange<C: PushPartitioned> {
@@ -26,6 +31,90 @@ pub trait Exchange<C: PushPartitioned> {
     fn exchange<F: 'static>(&self, route: F) -> Self
     where
         for<'a> F: FnMut(&C::Item<'a>) -> u64;
+
+    /// Exchange records between workers, caching the routing decision per key.
+    ///
+    /// The `key` closure extracts a cheap, hashable key from each record, and `route`
+    /// maps that key to a `u64` determining the destination worker, as in `exchange`.
+    /// The results of `route` are kept in a bounded least-recently-used cache, which
+    /// pays off when computing the route is expensive and keys recur, as in skewed
+    /// workloads.
+    ///
+    /// The `route` closure must be deterministic: a key must always map to the same
+    /// value, as cached and freshly computed routes are used interchangeably, and
+    /// evicted keys are recomputed.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Exchange, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .exchange_cached(|x| *x % 3, |k| *k)
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn exchange_cached<K, F, R>(&self, key: F, route: R) -> Self
+    where
+        K: Hash + Eq + Clone + 'static,
+        for<'a> F: FnMut(&C::Item<'a>) -> K + 'static,
+        R: FnMut(&K) -> u64 + 'static;
+
+    /// Distributes records evenly between workers, regardless of their content.
+    ///
+    /// Each worker sends its records to the workers in turn, starting from its own index, so
+    /// that the number of records a worker sends to each is balanced. Unlike `exchange`, records
+    /// with equal keys are not brought to the same worker, and so this is only appropriate when
+    /// the downstream work is per-record and independent of where and in which order records
+    /// arrive, for example to undo the skew of a preceding key-based exchange. The assignment
+    /// depends only on the order of records at each worker, and so is deterministic for a fixed
+    /// number of workers.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Exchange, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .exchange(|_| 0)
+    ///            .rebalance()
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn rebalance(&self) -> Self;
+
+    /// Exchange records between workers, sending records with pinned keys to fixed workers.
+    ///
+    /// The `key` closure extracts a key from each record. Records whose key is present in `pins`
+    /// are sent to the worker it maps the key to, and other records are routed by applying
+    /// `default_route` to their key, as in `exchange`. This co-locates groups of related keys,
+    /// for example all keys of one tenant, on a chosen worker.
+    ///
+    /// The map is shared rather than copied, and so can be cloned cheaply into each worker's
+    /// dataflow. It is consulted once per record.
+    ///
+    /// This method panics when the dataflow is built if a pinned worker index is not less than
+    /// the number of workers.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    /// use std::sync::Arc;
+    /// use timely::dataflow::operators::{ToStream, Exchange, Inspect};
+    ///
+    /// let pins = Arc::new(vec![(3, 0), (7, 0)].into_iter().collect::<HashMap<u64, usize>>());
+    ///
+    /// timely::example(move |scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .exchange_pinned(|x| *x, pins, |k| *k)
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn exchange_pinned<K, F, R>(&self, key: F, pins: Arc<HashMap<K, usize>>, default_route: R) -> Self
+    where
+        K: Hash + Eq + 'static,
+        for<'a> F: FnMut(&C::Item<'a>) -> K + 'static,
+        R: FnMut(&K) -> u64 + 'static;
 }
 
 impl<G: Scope, C> Exchange<C> for StreamCore<G, C>
@@ -46,4 +135,179 @@ where
             }
         })
     }
+
+    fn exchange_cached<K, F, R>(&self, mut key: F, mut route: R) -> StreamCore<G, C>
+    where
+        K: Hash + Eq + Clone + 'static,
+        for<'a> F: FnMut(&C::Item<'a>) -> K + 'static,
+        R: FnMut(&K) -> u64 + 'static,
+    {
+        let mut cache = RouteCache::new(ROUTE_CACHE_CAPACITY);
+        self.exchange(move |item| cache.route(key(item), &mut route))
+    }
+
+    fn rebalance(&self) -> StreamCore<G, C> {
+        let mut counter = self.scope().index() as u64;
+        self.exchange(move |_| {
+            let route = counter;
+            counter = counter.wrapping_add(1);
+            route
+        })
+    }
+
+    fn exchange_pinned<K, F, R>(&self, mut key: F, pins: Arc<HashMap<K, usize>>, mut default_route: R) -> StreamCore<G, C>
+    where
+        K: Hash + Eq + 'static,
+        for<'a> F: FnMut(&C::Item<'a>) -> K + 'static,
+        R: FnMut(&K) -> u64 + 'static,
+    {
+        let peers = self.scope().peers();
+        if let Some(worker) = pins.values().find(|worker| **worker >= peers) {
+            panic!("exchange_pinned: key pinned to worker {}, but there are only {} workers", worker, peers);
+        }
+        self.exchange(move |item| {
+            let key = key(item);
+            match pins.get(&key) {
+                Some(worker) => *worker as u64,
+                None => default_route(&key),
+            }
+        })
+    }
+}
+
+/// Returns a routing function for `exchange` that hashes the key extracted by `key`.
+///
+/// The hash function is fixed and unseeded, so that the same key is routed to the same worker
+/// on every run, which makes exchange-based tests reproducible. The hashers of the standard
+/// library offer no such guarantee: `RandomState` is seeded randomly for each process, and the
+/// algorithm behind `DefaultHasher` may change between Rust releases.
+///
+/// The route depends on the bytes a key feeds to its `Hash` implementation. These are stable for
+/// a given platform and version of the key's type, but may differ between platforms, for example
+/// for `usize` keys on machines of different word sizes.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::{ToStream, Exchange, Inspect};
+/// use timely::dataflow::operators::core::exchange::route_by_key;
+///
+/// timely::example(|scope| {
+///     vec![("a", 1), ("b", 2), ("a", 3)]
+///         .into_iter()
+///         .to_stream(scope)
+///         .exchange(route_by_key(|x: &(&str, u64)| x.0))
+///         .inspect(|x| println!("seen: {:?}", x));
+/// });
+/// ```
+pub fn route_by_key<D, K, F>(mut key: F) -> impl FnMut(&D) -> u64
+where
+    K: Hash,
+    F: FnMut(&D) -> K,
+{
+    move |datum| {
+        let mut hasher = FnvHasher::default();
+        key(datum).hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// The 64-bit FNV-1a hash function, chosen for being fixed, simple, and fast on short keys.
+struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        FnvHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for FnvHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Number of distinct keys retained by `exchange_cached`.
+const ROUTE_CACHE_CAPACITY: usize = 1024;
+
+/// A bounded least-recently-used cache from keys to routes.
+struct RouteCache<K> {
+    capacity: usize,
+    /// Logical clock, advanced on each access.
+    clock: u64,
+    /// Cached route and last access time for each key.
+    routes: HashMap<K, (u64, u64)>,
+    /// Keys ordered by last access time, oldest first.
+    recency: BTreeMap<u64, K>,
+}
+
+impl<K: Hash + Eq + Clone> RouteCache<K> {
+    fn new(capacity: usize) -> Self {
+        RouteCache {
+            capacity,
+            clock: 0,
+            routes: HashMap::new(),
+            recency: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the route for `key`, computing it with `route` if it is not cached.
+    fn route<R: FnMut(&K) -> u64>(&mut self, key: K, route: &mut R) -> u64 {
+        self.clock += 1;
+        if let Some((cached, accessed)) = self.routes.get_mut(&key) {
+            let key = self.recency.remove(accessed).expect("cached key missing from recency order");
+            *accessed = self.clock;
+            let cached = *cached;
+            self.recency.insert(self.clock, key);
+            return cached;
+        }
+
+        if self.routes.len() >= self.capacity {
+            let oldest = self.recency.keys().next().copied();
+            if let Some(evicted) = oldest.and_then(|time| self.recency.remove(&time)) {
+                self.routes.remove(&evicted);
+            }
+        }
+
+        let computed = route(&key);
+        self.recency.insert(self.clock, key.clone());
+        self.routes.insert(key, (computed, self.clock));
+        computed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RouteCache, route_by_key};
+
+    #[test]
+    fn route_cache_evicts_least_recently_used() {
+        let mut calls = 0;
+        let mut route = |key: &u64| { calls += 1; *key * 10 };
+        let mut cache = RouteCache::new(2);
+
+        assert_eq!(cache.route(1, &mut route), 10);
+        assert_eq!(cache.route(2, &mut route), 20);
+        // Touch `1`, so that `2` is the least recently used key.
+        assert_eq!(cache.route(1, &mut route), 10);
+        assert_eq!(cache.route(3, &mut route), 30);
+        assert_eq!(cache.route(1, &mut route), 10);
+        assert_eq!(cache.route(2, &mut route), 20);
+        drop(route);
+        assert_eq!(calls, 4);
+    }
+
+    #[test]
+    fn route_by_key_is_stable() {
+        let mut route = route_by_key(|x: &(&str, u64)| x.0);
+        // FNV-1a of the bytes written by `str::hash`, which appends a 0xff terminator.
+        assert_eq!(route(&("a", 0)), route(&("a", 1)));
+        assert_eq!(route(&("", 0)), 0xaf64_724c_8602_eb6e);
+    }
 }
@@ -1,8 +1,8 @@
 //! Filters a stream by a predicate.
-use crate::container::{Container, SizableContainer, PushInto};
+use crate::container::{CapacityContainerBuilder, Container, SizableContainer, PushInto};
 use crate::dataflow::channels::pact::Pipeline;
 use crate::dataflow::{Scope, StreamCore};
-use crate::dataflow::operators::generic::operator::Operator;
+use crate::dataflow::operators::generic::builder_rc::OperatorBuilder;
 
 /// Extension trait for filtering.
 pub trait Filter<C: Container> {
@@ -27,14 +27,23 @@ where
     for<'a> C: PushInto<C::Item<'a>>
 {
     fn filter<P: FnMut(&C::Item<'_>)->bool+'static>(&self, mut predicate: P) -> StreamCore<G, C> {
-        let mut container = Default::default();
-        self.unary(Pipeline, "Filter", move |_,_| move |input, output| {
-            input.for_each(|time, data| {
-                data.swap(&mut container);
-                if !container.is_empty() {
-                    output.session(&time).give_iterator(container.drain().filter(&mut predicate));
-                }
-            });
-        })
+        let mut builder = OperatorBuilder::new("Filter".to_owned(), self.scope());
+        let mut input = builder.new_input(self, Pipeline);
+        let (mut output, stream) = builder.new_output_synchronous::<CapacityContainerBuilder<C>>();
+
+        builder.build(move |_| {
+            let mut container = Default::default();
+            move |_frontiers| {
+                let mut output = output.activate();
+                input.for_each(|time, data| {
+                    data.swap(&mut container);
+                    if !container.is_empty() {
+                        output.session(&time).give_iterator(container.drain().filter(&mut predicate));
+                    }
+                });
+            }
+        });
+
+        stream
     }
 }
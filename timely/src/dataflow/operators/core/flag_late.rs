@@ -0,0 +1,73 @@
+//! Reports records that arrive at times the input frontier has already passed.
+
+use crate::Container;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use crate::dataflow::{Scope, StreamCore};
+use crate::progress::frontier::AntichainRef;
+
+/// Reports records that arrive at times the input frontier has already passed.
+pub trait FlagLate<G: Scope, C: Container> {
+    /// Calls `on_late` for each record whose time is not greater or equal to the input frontier.
+    ///
+    /// The frontier is the one presented to the operator when it receives the record. All records
+    /// are passed through unchanged, late or not. Progress tracking should prevent late records,
+    /// and this operator is intended to debug operators and inputs that appear to violate it.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::core::{ToStream, FlagLate};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .container::<Vec<_>>()
+    ///            .flag_late(|time, x| println!("late record at {:?}: {:?}", time, x));
+    /// });
+    /// ```
+    fn flag_late<F>(&self, on_late: F) -> StreamCore<G, C>
+    where
+        for<'a> F: FnMut(&G::Timestamp, C::ItemRef<'a>)+'static;
+}
+
+impl<G: Scope, C: Container> FlagLate<G, C> for StreamCore<G, C> {
+    fn flag_late<F>(&self, mut on_late: F) -> StreamCore<G, C>
+    where
+        for<'a> F: FnMut(&G::Timestamp, C::ItemRef<'a>)+'static,
+    {
+        check_times(self, "FlagLate", move |time, _frontier, container| {
+            for datum in container.iter() {
+                on_late(time, datum);
+            }
+        })
+    }
+}
+
+/// Passes `stream` through an operator that calls `on_late` with each container whose time is
+/// not greater or equal to the input frontier, along with that frontier.
+pub(crate) fn check_times<G, C, F>(stream: &StreamCore<G, C>, name: &str, mut on_late: F) -> StreamCore<G, C>
+where
+    G: Scope,
+    C: Container,
+    F: FnMut(&G::Timestamp, AntichainRef<G::Timestamp>, &C)+'static,
+{
+    let mut builder = OperatorBuilder::new(name.to_owned(), stream.scope());
+
+    let mut input = builder.new_input(stream, Pipeline);
+    let (mut output, stream) = builder.new_output();
+
+    builder.build(move |_| {
+        let mut container = Default::default();
+        move |frontiers| {
+            let mut output_handle = output.activate();
+            input.for_each(|time, data| {
+                data.swap(&mut container);
+                if !frontiers.less_equal(0, time.time()) {
+                    on_late(time.time(), frontiers.frontier(0), &container);
+                }
+                output_handle.session(&time).give_container(&mut container);
+            });
+        }
+    });
+
+    stream
+}
@@ -0,0 +1,47 @@
+//! Consumes a stream, calling a closure on each record.
+
+use crate::Container;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Scope, StreamCore};
+use crate::dataflow::operators::generic::Operator;
+
+/// Extension trait for consuming a stream with a closure.
+pub trait ForEach<G: Scope, C: Container> {
+    /// Calls `func` on each record of the stream, and produces no stream.
+    ///
+    /// This is a terminal counterpart of `inspect`, for records that are only wanted for their
+    /// side effects. The operator has no output, and so allocates no downstream channel and
+    /// copies no records for one. Records are drained from their containers and handed to `func`
+    /// by value. The operator still participates in progress tracking like any other, and its
+    /// dataflow does not complete until it has consumed every record.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, ForEach};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .for_each(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn for_each<F>(&self, func: F)
+    where
+        for<'a> F: FnMut(C::Item<'a>)+'static;
+}
+
+impl<G: Scope, C: Container> ForEach<G, C> for StreamCore<G, C> {
+    fn for_each<F>(&self, mut func: F)
+    where
+        for<'a> F: FnMut(C::Item<'a>)+'static,
+    {
+        let mut container = Default::default();
+        self.sink(Pipeline, "ForEach", move |input| {
+            input.for_each(|_time, data| {
+                data.swap(&mut container);
+                for datum in container.drain() {
+                    func(datum);
+                }
+            });
+        });
+    }
+}
@@ -360,9 +360,37 @@ impl<T: Timestamp, C: Container> Handle<T, C> {
     ///
     /// This method allows timely dataflow to issue progress notifications as it can now determine
     /// that this input can no longer produce data at earlier timestamps.
+    ///
+    /// This method panics if `next` is not greater or equal to the current epoch.
     pub fn advance_to(&mut self, next: T) {
-        // Assert that we do not rewind time.
-        assert!(self.now_at.less_equal(&next));
+        if let Err(message) = self.try_advance_to(next) {
+            panic!("{}", message);
+        }
+    }
+
+    /// Attempts to advance the current epoch to `next`.
+    ///
+    /// Returns an error describing both times if `next` is not greater or equal to the current
+    /// epoch, in which case the epoch is unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::core::input::Handle;
+    ///
+    /// let mut input = Handle::<u64, Vec<u64>>::new();
+    /// assert!(input.try_advance_to(5).is_ok());
+    /// assert!(input.try_advance_to(3).is_err());
+    /// assert_eq!(input.time(), &5);
+    /// ```
+    pub fn try_advance_to(&mut self, next: T) -> Result<(), String> {
+        // Refuse to rewind time.
+        if !self.now_at.less_equal(&next) {
+            return Err(format!(
+                "Attempted to advance input handle from {:?} to {:?}, which is not greater or equal to the current epoch.",
+                self.now_at,
+                next,
+            ));
+        }
         // Flush buffers if time has actually changed.
         if !self.now_at.eq(&next) {
             self.close_epoch();
@@ -371,13 +399,55 @@ impl<T: Timestamp, C: Container> Handle<T, C> {
                 progress.borrow_mut().update(self.now_at.clone(), 1);
             }
         }
+        Ok(())
     }
 
     /// Closes the input.
     ///
     /// This method allows timely dataflow to issue all progress notifications blocked by this input
     /// and to begin to shut down operators, as this input can no longer produce data.
-    pub fn close(self) { }
+    ///
+    /// Closing sends any buffered records and then withdraws the input's capability entirely,
+    /// so that the input's contribution to downstream frontiers becomes empty. This differs from
+    /// advancing to a large but finite time, after which the frontier is that time: operators must
+    /// then assume that records may still arrive at it or later times, and retain state and pending
+    /// notifications accordingly. Once all inputs of an operator are closed, it observes an empty
+    /// input frontier, and can release all of its per-time state at once.
+    ///
+    /// Dropping the handle has the same effect; `close` makes the intent explicit.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use timely::dataflow::operators::core::{Input, InspectCore};
+    /// use timely::dataflow::operators::core::input::Handle;
+    ///
+    /// let frontiers = Arc::new(Mutex::new(Vec::new()));
+    /// let sink = Arc::clone(&frontiers);
+    ///
+    /// timely::execute_directly(move |worker| {
+    ///     let mut input = Handle::<u64, Vec<u64>>::new();
+    ///     worker.dataflow(|scope| {
+    ///         scope.input_from(&mut input)
+    ///              .inspect_container(move |event| {
+    ///                  if let Err(frontier) = event {
+    ///                      sink.lock().unwrap().push(frontier.to_vec());
+    ///                  }
+    ///              });
+    ///     });
+    ///
+    ///     input.send(0);
+    ///     input.advance_to(u64::max_value());
+    ///     worker.step_while(|| frontiers.lock().unwrap().last() != Some(&vec![u64::max_value()]));
+    ///     input.close();
+    ///     while worker.step() { }
+    ///     assert_eq!(frontiers.lock().unwrap().last(), Some(&vec![]));
+    /// });
+    /// ```
+    pub fn close(self) {
+        // Dropping the handle closes its epoch without opening a new one.
+        drop(self);
+    }
 
     /// Reports the current epoch.
     pub fn epoch(&self) -> &T {
@@ -425,6 +495,42 @@ impl<T: Timestamp, C: SizableContainer> Handle<T, C> {
             self.flush();
         }
     }
+
+    /// Sends the records of an iterator into the corresponding timely dataflow `Stream`, at the current epoch.
+    ///
+    /// Records are buffered and sent in batches, as with `send`. To hand over a container that is
+    /// already assembled, use `send_batch` instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::*;
+    /// use timely::dataflow::operators::core::{Input, Inspect};
+    /// use timely::dataflow::operators::core::input::Handle;
+    ///
+    /// // construct and execute a timely dataflow
+    /// timely::execute(Config::thread(), |worker| {
+    ///
+    ///     // add an input and base computation off of it
+    ///     let mut input = Handle::new();
+    ///     worker.dataflow(|scope| {
+    ///         scope.input_from(&mut input)
+    ///              .container::<Vec<_>>()
+    ///              .inspect(|x| println!("hello {:?}", x));
+    ///     });
+    ///
+    ///     // introduce input, advance computation
+    ///     for round in 0..10 {
+    ///         input.send_iterator(round * 10 .. (round + 1) * 10);
+    ///         input.advance_to(round + 1);
+    ///         worker.step();
+    ///     }
+    /// });
+    /// ```
+    pub fn send_iterator<I>(&mut self, iter: I) where I: IntoIterator, C: PushInto<I::Item> {
+        for data in iter {
+            self.send(data);
+        }
+    }
 }
 
 impl<T: Timestamp, C: Container> Default for Handle<T, C> {
@@ -438,3 +544,113 @@ impl<T:Timestamp, C: Container> Drop for Handle<T, C> {
         self.close_epoch();
     }
 }
+
+/// An input handle that advances its own epoch to the times of the records it is sent.
+///
+/// The handle wraps a [`Handle`] along with a function from records to their times. Each record
+/// is sent at its own time, and the epoch is advanced to that time first if it is later than the
+/// current epoch. Records must be sent in an order of non-decreasing times, and the handle panics
+/// on a record whose time is earlier than the current epoch.
+///
+/// As with `Handle`, the epoch of the last records remains open until the handle is advanced past
+/// it, closed, or dropped.
+///
+/// # Examples
+/// ```
+/// use std::sync::{Arc, Mutex};
+/// use timely::dataflow::operators::core::input::AutoHandle;
+/// use timely::dataflow::operators::Inspect;
+///
+/// let seen = Arc::new(Mutex::new(Vec::new()));
+/// let sink = Arc::clone(&seen);
+///
+/// timely::execute_directly(move |worker| {
+///     let mut input = AutoHandle::<u64, Vec<(u64, char)>, _>::new(|record: &(u64, char)| record.0);
+///     worker.dataflow(|scope| {
+///         input.to_stream(scope)
+///              .inspect_time(move |time, record| sink.lock().unwrap().push((*time, record.1)));
+///     });
+///
+///     input.send_iterator(vec![(0, 'a'), (0, 'b'), (2, 'c'), (5, 'd')]);
+///     assert_eq!(input.time(), &5);
+///     input.close();
+/// });
+///
+/// assert_eq!(*seen.lock().unwrap(), vec![(0, 'a'), (0, 'b'), (2, 'c'), (5, 'd')]);
+/// ```
+#[derive(Debug)]
+pub struct AutoHandle<T: Timestamp, C: Container, F> {
+    handle: Handle<T, C>,
+    time_of: F,
+}
+
+impl<T: Timestamp, C: Container, F> AutoHandle<T, C, F> {
+    /// Allocates a new input handle that determines the time of each record with `time_of`.
+    pub fn new(time_of: F) -> Self {
+        Self::from_handle(Handle::new(), time_of)
+    }
+
+    /// Wraps an existing input handle, which may already be connected to dataflows.
+    pub fn from_handle(handle: Handle<T, C>, time_of: F) -> Self {
+        Self { handle, time_of }
+    }
+
+    /// Creates an input stream from the handle in the supplied scope.
+    pub fn to_stream<G: Scope>(&mut self, scope: &mut G) -> StreamCore<G, C>
+    where
+        T: TotalOrder,
+        G: ScopeParent<Timestamp=T>,
+    {
+        self.handle.to_stream(scope)
+    }
+
+    /// Advances the current epoch to `next`, without sending records.
+    ///
+    /// This method panics if `next` is not greater or equal to the current epoch.
+    pub fn advance_to(&mut self, next: T) {
+        self.handle.advance_to(next);
+    }
+
+    /// Closes the input, as `Handle::close` does.
+    pub fn close(self) {
+        self.handle.close();
+    }
+
+    /// Reports the current timestamp.
+    pub fn time(&self) -> &T {
+        self.handle.time()
+    }
+
+    /// Unwraps the underlying input handle.
+    pub fn into_inner(self) -> Handle<T, C> {
+        self.handle
+    }
+}
+
+impl<T: Timestamp, C: SizableContainer, F> AutoHandle<T, C, F> {
+    /// Sends one record at the time `time_of` reports for it, first advancing the epoch to that
+    /// time if it is later than the current epoch.
+    ///
+    /// This method panics if the time of `data` is earlier than the current epoch.
+    pub fn send<D>(&mut self, data: D) where C: PushInto<D>, F: FnMut(&D) -> T {
+        let time = (self.time_of)(&data);
+        if !self.handle.time().eq(&time) {
+            if !self.handle.time().less_equal(&time) {
+                panic!(
+                    "AutoHandle: record at time {:?} sent after the epoch advanced to {:?}; record times must not decrease.",
+                    time,
+                    self.handle.time(),
+                );
+            }
+            self.handle.advance_to(time);
+        }
+        self.handle.send(data);
+    }
+
+    /// Sends the records of an iterator, each as by `send`.
+    pub fn send_iterator<I>(&mut self, iter: I) where I: IntoIterator, C: PushInto<I::Item>, F: FnMut(&I::Item) -> T {
+        for data in iter {
+            self.send(data);
+        }
+    }
+}
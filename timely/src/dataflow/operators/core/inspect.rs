@@ -1,9 +1,11 @@
 //! Extension trait and implementation for observing and action on streamed data.
 
 use crate::Container;
+use crate::container::CapacityContainerBuilder;
 use crate::dataflow::channels::pact::Pipeline;
 use crate::dataflow::{Scope, StreamCore};
 use crate::dataflow::operators::generic::Operator;
+use crate::dataflow::operators::generic::builder_rc::OperatorBuilder;
 
 /// Methods to inspect records and batches of records on a stream.
 pub trait Inspect<G: Scope, C: Container>: InspectCore<G, C> + Sized {
@@ -91,6 +93,27 @@ pub trait Inspect<G: Scope, C: Container>: InspectCore<G, C> + Sized {
 }
 
 impl<G: Scope, C: Container> Inspect<G, C> for StreamCore<G, C> {
+    // Without frontiers to report, the operator need only be scheduled when it receives data.
+    fn inspect_batch(&self, mut func: impl FnMut(&G::Timestamp, &C)+'static) -> Self {
+        let mut builder = OperatorBuilder::new("InspectBatch".to_owned(), self.scope());
+        let mut input = builder.new_input(self, Pipeline);
+        let (mut output, stream) = builder.new_output_synchronous::<CapacityContainerBuilder<C>>();
+
+        builder.build(move |_| {
+            let mut vector = Default::default();
+            move |_frontiers| {
+                let mut output = output.activate();
+                input.for_each(|time, data| {
+                    data.swap(&mut vector);
+                    func(&time, &vector);
+                    output.session(&time).give_container(&mut vector);
+                });
+            }
+        });
+
+        stream
+    }
+
     fn inspect_core<F>(&self, mut func: F) -> Self where F: FnMut(Result<(&G::Timestamp, &C), &[G::Timestamp]>) + 'static {
         self.inspect_container(move |r| func(r))
     }
@@ -1,9 +1,9 @@
 //! Extension methods for `StreamCore` based on record-by-record transformation.
 
-use crate::container::{Container, SizableContainer, PushInto};
+use crate::container::{CapacityContainerBuilder, Container, SizableContainer, PushInto};
 use crate::dataflow::{Scope, StreamCore};
 use crate::dataflow::channels::pact::Pipeline;
-use crate::dataflow::operators::generic::operator::Operator;
+use crate::dataflow::operators::generic::builder_rc::OperatorBuilder;
 
 /// Extension trait for `Stream`.
 pub trait Map<S: Scope, C: Container> {
@@ -53,19 +53,31 @@ pub trait Map<S: Scope, C: Container> {
 impl<S: Scope, C: Container> Map<S, C> for StreamCore<S, C> {
     // TODO : This would be more robust if it captured an iterator and then pulled an appropriate
     // TODO : number of elements from the iterator. This would allow iterators that produce many
-    // TODO : records without taking arbitrarily long and arbitrarily much memory.
+    // TODO : records without taking arbitrarily long. Output containers are sent as they fill,
+    // TODO : but all of them are enqueued for the downstream operator before it can run.
     fn flat_map<C2, I, L>(&self, mut logic: L) -> StreamCore<S, C2>
     where
         I: IntoIterator,
         C2: SizableContainer + PushInto<I::Item>,
         L: FnMut(C::Item<'_>)->I + 'static,
     {
-        let mut container = Default::default();
-        self.unary(Pipeline, "FlatMap", move |_,_| move |input, output| {
-            input.for_each(|time, data| {
-                data.swap(&mut container);
-                output.session(&time).give_iterator(container.drain().flat_map(&mut logic));
-            });
-        })
+        let mut builder = OperatorBuilder::new("FlatMap".to_owned(), self.scope());
+        let mut input = builder.new_input(self, Pipeline);
+        let (mut output, stream) = builder.new_output_synchronous::<CapacityContainerBuilder<C2>>();
+
+        builder.build(move |_| {
+            let mut container = Default::default();
+            move |_frontiers| {
+                let mut output = output.activate();
+                input.for_each(|time, data| {
+                    data.swap(&mut container);
+                    let mut session = output.session(&time);
+                    session.capacity_hint(container.len());
+                    session.give_iterator(container.drain().flat_map(&mut logic));
+                });
+            }
+        });
+
+        stream
     }
 }
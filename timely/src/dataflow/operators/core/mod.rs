@@ -1,12 +1,18 @@
 //! Extension traits for `StreamCore` implementing various operators that
 //! are independent of specific container types.
 
+pub mod assert_monotone;
+pub mod barrier;
 pub mod capture;
+pub mod coalesce;
 pub mod concat;
+pub mod container_stats;
 pub mod enterleave;
 pub mod exchange;
 pub mod feedback;
 pub mod filter;
+pub mod flag_late;
+pub mod for_each;
 pub mod input;
 pub mod inspect;
 pub mod map;
@@ -17,17 +23,25 @@ pub mod reclock;
 pub mod to_stream;
 pub mod unordered_input;
 
+pub use assert_monotone::AssertMonotone;
+pub use barrier::Barrier;
 pub use capture::Capture;
+pub use coalesce::Coalesce;
 pub use concat::{Concat, Concatenate};
+pub use container_stats::ContainerStats;
 pub use enterleave::{Enter, Leave};
 pub use exchange::Exchange;
 pub use feedback::{Feedback, LoopVariable, ConnectLoop};
 pub use filter::Filter;
+pub use flag_late::FlagLate;
+pub use for_each::ForEach;
 pub use input::Input;
 pub use inspect::{Inspect, InspectCore};
 pub use map::Map;
 pub use ok_err::OkErr;
 pub use probe::Probe;
 pub use to_stream::ToStream;
+#[cfg(feature = "async")]
+pub use to_stream::ToStreamAsync;
 pub use reclock::Reclock;
 pub use unordered_input::{UnorderedInput, UnorderedHandle};
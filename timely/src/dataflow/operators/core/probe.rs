@@ -5,6 +5,7 @@ use std::cell::RefCell;
 
 use crate::progress::Timestamp;
 use crate::progress::frontier::{AntichainRef, MutableAntichain};
+use crate::order::{PartialOrder, TotalOrder};
 use crate::dataflow::channels::pushers::Counter as PushCounter;
 use crate::dataflow::channels::pushers::buffer::Buffer as PushBuffer;
 use crate::dataflow::channels::pact::Pipeline;
@@ -95,6 +96,7 @@ impl<G: Scope, C: Container> Probe<G, C> for StreamCore<G, C> {
         let mut output = PushBuffer::new(PushCounter::new(tee));
 
         let shared_frontier = Rc::downgrade(&handle.frontier);
+        let shared_observed = Rc::downgrade(&handle.observed);
         let mut started = false;
 
         let mut vector = Default::default();
@@ -122,6 +124,11 @@ impl<G: Scope, C: Container> Probe<G, C> for StreamCore<G, C> {
                         RefOrMut::Mut(reference) => (&reference.time, RefOrMut::Mut(&mut reference.data)),
                     };
                     data.swap(&mut vector);
+                    if !vector.is_empty() {
+                        if let Some(shared_observed) = shared_observed.upgrade() {
+                            insert_maximal(&mut shared_observed.borrow_mut(), time);
+                        }
+                    }
                     output.session(time).give_container(&mut vector);
                 }
                 output.cease();
@@ -138,10 +145,23 @@ impl<G: Scope, C: Container> Probe<G, C> for StreamCore<G, C> {
     }
 }
 
+/// Records `time` among `maximal`, the times not less or equal to any other observed time.
+fn insert_maximal<T: PartialOrder+Clone>(maximal: &mut Vec<T>, time: &T) {
+    if !maximal.iter().any(|t| time.less_equal(t)) {
+        maximal.retain(|t| !t.less_equal(time));
+        maximal.push(time.clone());
+    }
+}
+
 /// Reports information about progress at the probe.
+///
+/// A handle is local to the worker that created it, and reports only the frontier and the
+/// records observed by that worker's probe operators.
 #[derive(Debug)]
 pub struct Handle<T:Timestamp> {
-    frontier: Rc<RefCell<MutableAntichain<T>>>
+    frontier: Rc<RefCell<MutableAntichain<T>>>,
+    /// The maximal timestamps of records that have passed through the probe.
+    observed: Rc<RefCell<Vec<T>>>,
 }
 
 impl<T: Timestamp> Handle<T> {
@@ -152,7 +172,13 @@ impl<T: Timestamp> Handle<T> {
     /// returns true iff the frontier is empty.
     #[inline] pub fn done(&self) -> bool { self.frontier.borrow().is_empty() }
     /// Allocates a new handle.
-    #[inline] pub fn new() -> Self { Handle { frontier: Rc::new(RefCell::new(MutableAntichain::new())) } }
+    #[inline] pub fn new() -> Self { Self::from_frontier(Rc::new(RefCell::new(MutableAntichain::new()))) }
+    /// Allocates a handle reading a frontier maintained elsewhere.
+    ///
+    /// Such a handle is not attached to a probe operator, and observes no records.
+    #[inline] pub(crate) fn from_frontier(frontier: Rc<RefCell<MutableAntichain<T>>>) -> Self {
+        Handle { frontier, observed: Rc::new(RefCell::new(Vec::new())) }
+    }
 
     /// Invokes a method on the frontier, returning its result.
     ///
@@ -171,12 +197,66 @@ impl<T: Timestamp> Handle<T> {
     pub fn with_frontier<R, F: FnMut(AntichainRef<T>)->R>(&self, mut function: F) -> R {
         function(self.frontier.borrow().frontier())
     }
+
+    /// Invokes a method on the maximal timestamps of records observed by the probe.
+    ///
+    /// These are the timestamps of non-empty messages that have passed through a probe operator
+    /// attached to this handle on this worker, retaining only those not less or equal to another.
+    /// Records at other workers are not reflected; to compare across workers, the results must
+    /// be exchanged by other means.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use timely::dataflow::operators::probe::Handle;
+    ///
+    /// let handle = Handle::<usize>::new();
+    /// let observed = handle.with_observed(|observed| observed.to_vec());
+    /// assert!(observed.is_empty());
+    /// ```
+    #[inline]
+    pub fn with_observed<R, F: FnMut(&[T])->R>(&self, mut function: F) -> R {
+        function(&self.observed.borrow()[..])
+    }
+
+    /// The greatest timestamp of any record observed by the probe on this worker, if any.
+    ///
+    /// Together with the frontier this allows lag to be measured, as the distance between the
+    /// latest data seen and the oldest time that may still change. As for `with_observed`, only
+    /// records passing through this worker's probe operators are reflected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use timely::dataflow::operators::{Input, Probe};
+    ///
+    /// timely::execute(timely::Config::thread(), |worker| {
+    ///     let (mut input, probe) = worker.dataflow(|scope| {
+    ///         let (input, stream) = scope.new_input::<u64>();
+    ///         (input, stream.probe())
+    ///     });
+    ///
+    ///     input.advance_to(3);
+    ///     input.send(0);
+    ///     input.advance_to(4);
+    ///     worker.step_while(|| probe.less_than(input.time()));
+    ///
+    ///     assert_eq!(probe.max_observed(), Some(3));
+    ///     assert!(probe.with_frontier(|frontier| frontier.less_equal(&4)));
+    /// }).unwrap();
+    /// ```
+    #[inline]
+    pub fn max_observed(&self) -> Option<T> where T: TotalOrder {
+        // With a total order, at most one time is maximal.
+        self.observed.borrow().last().cloned()
+    }
 }
 
 impl<T: Timestamp> Clone for Handle<T> {
     fn clone(&self) -> Self {
         Handle {
-            frontier: self.frontier.clone()
+            frontier: self.frontier.clone(),
+            observed: self.observed.clone(),
         }
     }
 }
@@ -190,6 +270,106 @@ where
     }
 }
 
+/// A group of probe handles, reporting on all of them together.
+///
+/// Dataflows with several outputs often probe each of them, and must wait until all have
+/// advanced. The group answers questions about all of its handles at once, each in time linear
+/// in the number of handles, and reflects their current frontiers without being updated.
+///
+/// # Examples
+///
+/// ```
+/// use timely::dataflow::operators::{Input, Map, Probe};
+/// use timely::dataflow::operators::probe::ProbeGroup;
+///
+/// timely::execute(timely::Config::thread(), |worker| {
+///     let mut group = ProbeGroup::new();
+///     let mut input = worker.dataflow(|scope| {
+///         let (input, stream) = scope.new_input::<u64>();
+///         group.push(stream.map(|x| x + 1).probe());
+///         group.push(stream.map(|x| x * 2).probe());
+///         input
+///     });
+///
+///     input.send(0);
+///     input.advance_to(1);
+///     group.step_until(worker, input.time());
+///     assert!(group.all_done(&0));
+/// }).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ProbeGroup<T: Timestamp> {
+    handles: Vec<Handle<T>>,
+}
+
+impl<T: Timestamp> ProbeGroup<T> {
+    /// Allocates an empty group.
+    pub fn new() -> Self {
+        ProbeGroup { handles: Vec::new() }
+    }
+    /// Adds `handle` to the group.
+    pub fn push(&mut self, handle: Handle<T>) {
+        self.handles.push(handle);
+    }
+    /// The handles in the group.
+    pub fn handles(&self) -> &[Handle<T>] {
+        &self.handles[..]
+    }
+    /// Returns true iff the frontier of some handle is strictly less than `time`.
+    ///
+    /// This is the condition to step the worker on to complete all times before `time`.
+    #[inline]
+    pub fn any_less_than(&self, time: &T) -> bool {
+        self.handles.iter().any(|handle| handle.less_than(time))
+    }
+    /// Returns true iff the frontier of some handle is less than or equal to `time`.
+    #[inline]
+    pub fn any_less_equal(&self, time: &T) -> bool {
+        self.handles.iter().any(|handle| handle.less_equal(time))
+    }
+    /// Returns true iff every handle is complete through `time`, meaning no frontier is less than or equal to it.
+    #[inline]
+    pub fn all_done(&self, time: &T) -> bool {
+        !self.any_less_equal(time)
+    }
+    /// Returns true iff the frontier of every handle is empty.
+    #[inline]
+    pub fn done(&self) -> bool {
+        self.handles.iter().all(|handle| handle.done())
+    }
+    /// Steps `worker` until every handle is complete for all times strictly less than `time`.
+    ///
+    /// As with `Worker::step_while`, this method does not return if an input that could send
+    /// data at such a time remains open and is not advanced.
+    pub fn step_until<A: crate::communication::Allocate>(&self, worker: &mut crate::worker::Worker<A>, time: &T) {
+        worker.step_while(|| self.any_less_than(time));
+    }
+}
+
+impl<T: Timestamp> Clone for ProbeGroup<T> {
+    fn clone(&self) -> Self {
+        ProbeGroup { handles: self.handles.clone() }
+    }
+}
+
+impl<T: Timestamp> Default for ProbeGroup<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Timestamp> Extend<Handle<T>> for ProbeGroup<T> {
+    fn extend<I: IntoIterator<Item=Handle<T>>>(&mut self, iter: I) {
+        self.handles.extend(iter);
+    }
+}
+
+impl<T: Timestamp> ::std::iter::FromIterator<Handle<T>> for ProbeGroup<T> {
+    fn from_iter<I: IntoIterator<Item=Handle<T>>>(iter: I) -> Self {
+        ProbeGroup { handles: iter.into_iter().collect() }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -229,4 +409,37 @@ mod tests {
         }).unwrap();
     }
 
+    #[test]
+    fn probe_group() {
+
+        crate::execute(Config::thread(), |worker| {
+
+            let mut group = super::ProbeGroup::new();
+            let (mut input1, mut input2) = worker.dataflow(|scope| {
+                let (input1, stream1) = scope.new_input::<String>();
+                let (input2, stream2) = scope.new_input::<String>();
+                group.push(stream1.probe());
+                group.push(stream2.probe());
+                (input1, input2)
+            });
+
+            // the group waits on the slower of its probes.
+            input1.advance_to(5);
+            input2.advance_to(2);
+            group.step_until(worker, &2);
+            assert!(group.all_done(&1));
+            assert!(!group.all_done(&2));
+            assert!(group.any_less_than(&3));
+            assert!(!group.any_less_than(&2));
+
+            input2.advance_to(5);
+            group.step_until(worker, &5);
+            assert!(group.all_done(&4));
+
+            input1.close();
+            input2.close();
+            worker.step_while(|| !group.done());
+        }).unwrap();
+    }
+
 }
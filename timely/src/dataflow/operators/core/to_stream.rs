@@ -1,4 +1,4 @@
-//! Conversion to the `StreamCore` type from iterators.
+//! Conversion to the `StreamCore` type from iterators, and from asynchronous streams.
 
 use crate::container::{SizableContainer, PushInto};
 use crate::Container;
@@ -55,3 +55,95 @@ impl<C: SizableContainer, I: IntoIterator+'static> ToStream<C> for I where C: Pu
         })
     }
 }
+
+/// Converts an asynchronous stream to a timely [StreamCore].
+///
+/// This trait is only available if the `async` feature is enabled.
+#[cfg(feature = "async")]
+pub trait ToStreamAsync<C: Container>: futures_util::stream::Stream + Sized {
+    /// Converts an asynchronous stream to a timely [StreamCore], with timestamps from `time`.
+    ///
+    /// Each item is introduced at the time `time` assigns to it, and times must not decrease:
+    /// the operator's capability is downgraded to each new time, and produced times must not
+    /// go backwards. Once the stream ends, the capability is dropped and the output completes.
+    ///
+    /// No runtime is spawned. The stream is polled on the worker thread whenever the operator
+    /// is scheduled, with a waker that activates the operator, so a wake-up from any thread
+    /// causes the worker to poll the stream again. Polling must therefore not block, and a
+    /// stream that depends on a particular runtime's reactor (for example, tokio sockets) must
+    /// run on that runtime and forward its items through a channel whose receiving end is
+    /// converted here. The operator yields after a bounded number of items, and reschedules
+    /// itself, so that a stream which is always ready does not monopolize the worker.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use timely::dataflow::operators::core::{ToStreamAsync, Capture};
+    /// use timely::dataflow::operators::core::capture::Extract;
+    ///
+    /// let data = timely::example(|scope| {
+    ///     futures_util::stream::iter(0..3u64)
+    ///         .to_stream_async(scope, |x| *x)
+    ///         .container::<Vec<_>>()
+    ///         .capture()
+    /// });
+    ///
+    /// assert_eq!(data.extract(), vec![(0, vec![0]), (1, vec![1]), (2, vec![2])]);
+    /// ```
+    fn to_stream_async<S, F>(self, scope: &mut S, time: F) -> StreamCore<S, C>
+    where
+        S: Scope,
+        F: FnMut(&Self::Item) -> S::Timestamp + 'static;
+}
+
+#[cfg(feature = "async")]
+impl<C, I> ToStreamAsync<C> for I
+where
+    C: SizableContainer + PushInto<I::Item>,
+    I: futures_util::stream::Stream + Unpin + 'static,
+{
+    fn to_stream_async<S, F>(mut self, scope: &mut S, mut time: F) -> StreamCore<S, C>
+    where
+        S: Scope,
+        F: FnMut(&Self::Item) -> S::Timestamp + 'static,
+    {
+        use std::pin::Pin;
+        use std::sync::Arc;
+        use std::task::{Context, Poll};
+
+        source(scope, "ToStreamAsync", |capability, info| {
+
+            // Wakers activate the operator from any thread, so that it polls the stream again.
+            let activator = Arc::new(scope.sync_activator_for(&info.address[..]));
+            // Reschedules the operator when it yields before the stream is pending.
+            let reactivator = scope.activator_for(&info.address[..]);
+            let mut capability = Some(capability);
+
+            move |output| {
+                let waker = futures_util::task::waker(activator.clone());
+                let mut context = Context::from_waker(&waker);
+
+                // Poll a bounded number of items, so that a stream that is always ready does not
+                // keep the worker from its other operators.
+                let mut budget = 256 * crate::container::buffer::default_capacity::<I::Item>();
+                while let Some(cap) = capability.as_mut() {
+                    if budget == 0 {
+                        reactivator.activate();
+                        break;
+                    }
+                    budget -= 1;
+                    match futures_util::stream::Stream::poll_next(Pin::new(&mut self), &mut context) {
+                        Poll::Ready(Some(item)) => {
+                            cap.downgrade(&time(&item));
+                            output.session(&*cap).give(item);
+                        }
+                        Poll::Ready(None) => {
+                            capability = None;
+                        }
+                        Poll::Pending => break,
+                    }
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,100 @@
+//! Emits the latest record for each key once its updates pause.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use crate::Data;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::operators::Capability;
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Extension trait for debouncing records by key.
+pub trait Debounce<G: Scope, D: Data> {
+    /// Emits the latest record for each key, once no record with that key has arrived for `quiet`.
+    ///
+    /// A record replaces any earlier record with the same key that is still being held, and the
+    /// earlier record is discarded. A record is emitted at its own timestamp, and the operator
+    /// holds a capability for it until then, so that downstream frontiers do not pass it. The
+    /// quiet period is measured in wall-clock time from the schedule at which the operator first
+    /// saw the record, and the operator requests to be woken once the earliest period ends, rather
+    /// than being rescheduled continually.
+    ///
+    /// Memory is proportional to the number of keys with held records. The dataflow does not
+    /// complete until the records of all keys have been emitted, which is at least `quiet` after
+    /// the last record arrives.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::Duration;
+    /// use timely::dataflow::operators::{ToStream, Debounce, Inspect};
+    ///
+    /// let emitted = Arc::new(Mutex::new(Vec::new()));
+    /// let sink = Arc::clone(&emitted);
+    ///
+    /// timely::example(move |scope| {
+    ///     vec![("a", 1), ("b", 1), ("a", 2), ("a", 3)]
+    ///         .into_iter()
+    ///         .to_stream(scope)
+    ///         .debounce(|x| x.0, Duration::from_millis(10))
+    ///         .inspect(move |x| sink.lock().unwrap().push(*x));
+    /// });
+    ///
+    /// let mut emitted = emitted.lock().unwrap().clone();
+    /// emitted.sort();
+    /// assert_eq!(emitted, vec![("a", 3), ("b", 1)]);
+    /// ```
+    fn debounce<K, F>(&self, key: F, quiet: Duration) -> Stream<G, D>
+    where
+        K: Hash + Eq + 'static,
+        F: FnMut(&D) -> K + 'static;
+}
+
+impl<G: Scope, D: Data> Debounce<G, D> for Stream<G, D> {
+    fn debounce<K, F>(&self, mut key: F, quiet: Duration) -> Stream<G, D>
+    where
+        K: Hash + Eq + 'static,
+        F: FnMut(&D) -> K + 'static,
+    {
+        // The latest record for each key, its capability, and when it was first seen.
+        let mut pending: HashMap<K, (Capability<G::Timestamp>, D, Instant)> = HashMap::new();
+        // The records still held after a schedule, swapped with `pending` to reuse allocations.
+        let mut retained = HashMap::new();
+        let mut vector = Vec::new();
+
+        let scope = self.scope();
+        self.unary(Pipeline, "Debounce", move |_, info| {
+            let activator = scope.activator_for(&info.address[..]);
+            move |input, output| {
+                let now = Instant::now();
+                input.for_each(|time, data| {
+                    data.swap(&mut vector);
+                    for datum in vector.drain(..) {
+                        pending.insert(key(&datum), (time.retain(), datum, now));
+                    }
+                });
+
+                // Emit the records of quiet keys, and note when the next key becomes quiet.
+                let mut next_quiet: Option<Duration> = None;
+                for (held, (cap, datum, seen)) in pending.drain() {
+                    let elapsed = now.duration_since(seen);
+                    if elapsed >= quiet {
+                        output.session(&cap).give(datum);
+                    }
+                    else {
+                        let remaining = quiet - elapsed;
+                        next_quiet = Some(next_quiet.map_or(remaining, |next| next.min(remaining)));
+                        retained.insert(held, (cap, datum, seen));
+                    }
+                }
+                std::mem::swap(&mut pending, &mut retained);
+
+                if let Some(delay) = next_quiet {
+                    activator.activate_after(delay);
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,95 @@
+//! Reports the frontier of a stream as data.
+
+use crate::{Container, Data};
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::operators::CapabilitySet;
+use crate::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use crate::dataflow::{Scope, Stream, StreamCore};
+use crate::progress::frontier::AntichainRef;
+use crate::progress::{Antichain, Timestamp};
+
+/// Extension trait for reporting the frontier of a stream.
+pub trait FrontierStream<S: Scope> {
+    /// Emits the frontier of the stream as a record each time the frontier changes.
+    ///
+    /// The record lists the elements of the new frontier, and is sent at the first of them, which
+    /// for totally ordered timestamps is the frontier itself. So that the record can be sent, the
+    /// operator holds a capability for each element of the frontier of its input, and the frontier
+    /// of its output follows that of its input. The records of the stream are discarded. Once the
+    /// input is complete no record is sent, as there is no time to send it at; instead the output
+    /// completes as well. The initial frontier, which contains only the minimal timestamp, is
+    /// not reported.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::InputHandle;
+    /// use timely::dataflow::operators::{Input, FrontierStream, Inspect, Probe};
+    ///
+    /// timely::execute_from_args(std::env::args(), |worker| {
+    ///     let mut input = InputHandle::<u64, u64>::new();
+    ///     let probe = worker.dataflow(|scope| {
+    ///         scope.input_from(&mut input)
+    ///              .frontier_stream()
+    ///              .inspect(|frontier| println!("frontier: {:?}", frontier))
+    ///              .probe()
+    ///     });
+    ///
+    ///     for round in 1 .. 4 {
+    ///         input.advance_to(round);
+    ///         worker.step_while(|| probe.less_than(input.time()));
+    ///     }
+    /// }).unwrap();
+    /// ```
+    fn frontier_stream(&self) -> Stream<S, Vec<S::Timestamp>>;
+}
+
+impl<S: Scope, D: Data> FrontierStream<S> for Stream<S, D> {
+    fn frontier_stream(&self) -> Stream<S, Vec<S::Timestamp>> {
+        frontier_changes(self, "FrontierStream", |_previous, frontier| {
+            frontier.first().map(|time| (time.clone(), frontier.to_vec()))
+        })
+    }
+}
+
+/// Sends a record each time the frontier of `stream` changes, as `report` chooses from the
+/// previous and the new frontier.
+///
+/// The operator holds a capability for each element of the previous frontier, and `report`
+/// returns the record and the time to send it at, which must be greater or equal to one of
+/// those elements. The records of `stream` are discarded.
+pub(crate) fn frontier_changes<G, C, D, R>(stream: &StreamCore<G, C>, name: &str, mut report: R) -> Stream<G, D>
+where
+    G: Scope,
+    C: Container,
+    D: Data,
+    R: FnMut(AntichainRef<G::Timestamp>, AntichainRef<G::Timestamp>)->Option<(G::Timestamp, D)>+'static,
+{
+    let mut builder = OperatorBuilder::new(name.to_owned(), stream.scope());
+
+    let mut input = builder.new_input(stream, Pipeline);
+    let (mut output, changes) = builder.new_output();
+
+    builder.build_reschedule(move |mut capabilities| {
+        let mut capabilities = CapabilitySet::from_elem(capabilities.pop().unwrap());
+        let mut previous = Antichain::from_elem(G::Timestamp::minimum());
+        move |frontiers| {
+            input.for_each(|_time, _data| { });
+
+            let frontier = frontiers.frontier(0);
+            if frontier != previous.borrow() {
+                if let Some((time, record)) = report(previous.borrow(), frontier) {
+                    output.activate()
+                          .session(&capabilities.delayed(&time))
+                          .give(record);
+                }
+                capabilities.downgrade(frontier.iter());
+                previous = frontier.to_owned();
+            }
+
+            // The capabilities, rather than the return value, keep the operator alive.
+            false
+        }
+    });
+
+    changes
+}
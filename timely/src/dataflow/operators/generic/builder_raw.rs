@@ -8,7 +8,7 @@ use std::default::Default;
 use std::rc::Rc;
 use std::cell::RefCell;
 
-use crate::scheduling::{Schedule, Activations};
+use crate::scheduling::{Schedule, Activations, Activator};
 
 use crate::progress::{Source, Target};
 use crate::progress::{Timestamp, Operate, operate::SharedProgress, Antichain};
@@ -41,6 +41,11 @@ impl OperatorShape {
         }
     }
 
+    /// The name of this operator
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// The number of inputs of this operator
     pub fn inputs(&self) -> usize {
         self.inputs
@@ -50,6 +55,58 @@ impl OperatorShape {
     pub fn outputs(&self) -> usize {
         self.outputs
     }
+
+    /// Whether this operator requires progress notifications
+    pub fn notify(&self) -> bool {
+        self.notify
+    }
+
+    /// The total number of workers in the computation
+    pub fn peers(&self) -> usize {
+        self.peers
+    }
+}
+
+/// A read-only view of the summaries from each operator input to each operator output.
+///
+/// The `Debug` implementation lists, for each input, the outputs it is connected to and the
+/// antichain of summaries connecting them. Outputs with an empty antichain are not connected.
+pub struct Connectivity<'a, S> {
+    summary: &'a [Vec<Antichain<S>>],
+}
+
+impl<'a, S> Connectivity<'a, S> {
+    /// Wraps summaries indexed first by input and then by output.
+    pub fn new(summary: &'a [Vec<Antichain<S>>]) -> Self {
+        Connectivity { summary }
+    }
+
+    /// The summaries, indexed first by input and then by output.
+    pub fn summary(&self) -> &'a [Vec<Antichain<S>>] {
+        self.summary
+    }
+
+    /// The summaries from input `input` to output `output`.
+    pub fn connection(&self, input: usize, output: usize) -> &'a Antichain<S> {
+        &self.summary[input][output]
+    }
+}
+
+impl<'a, S: std::fmt::Debug> std::fmt::Debug for Connectivity<'a, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (input, outputs) in self.summary.iter().enumerate() {
+            write!(f, "input {}:", input)?;
+            let mut connected = outputs.iter().enumerate().filter(|(_, summary)| !summary.is_empty()).peekable();
+            if connected.peek().is_none() {
+                write!(f, " (no outputs)")?;
+            }
+            for (output, summary) in connected {
+                write!(f, " output {} via {:?};", output, summary.elements())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
 }
 
 /// Builds operators with generic shape.
@@ -99,6 +156,27 @@ impl<G: Scope> OperatorBuilder<G> {
         &self.shape
     }
 
+    /// A handle that schedules the operator, immediately or after a delay.
+    pub fn activator(&self) -> Activator {
+        self.scope.activator_for(&self.address[..])
+    }
+
+    /// The summaries from each input to each output, as declared so far.
+    ///
+    /// These are the promises made through the `connection` arguments of `new_input_connection`
+    /// and `new_output_connection`, and are what the operator reports to progress tracking.
+    pub fn connectivity(&self) -> Connectivity<'_, <G::Timestamp as Timestamp>::Summary> {
+        Connectivity::new(&self.summary)
+    }
+
+    /// Replaces the operator's name.
+    ///
+    /// The name is read only once the operator is built, where it is used in logging and by
+    /// `Operate::name`, and so this may be called at any point before `build`.
+    pub fn set_name(&mut self, name: String) {
+        self.shape.name = name;
+    }
+
     /// Indicates whether the operator requires frontier information.
     pub fn set_notify(&mut self, notify: bool) {
         self.shape.notify = notify;
@@ -175,7 +253,7 @@ impl<G: Scope> OperatorBuilder<G> {
 
     /// Information describing the operator.
     pub fn operator_info(&self) -> OperatorInfo {
-        OperatorInfo::new(self.index, self.global, &self.address[..])
+        OperatorInfo::with_worker(self.index, self.global, &self.address[..], self.scope.index(), self.scope.peers())
     }
 }
 
@@ -3,14 +3,17 @@
 use std::rc::Rc;
 use std::cell::RefCell;
 use std::default::Default;
+use std::ops::Deref;
 
+use crate::order::PartialOrder;
 use crate::progress::{ChangeBatch, Timestamp};
 use crate::progress::operate::SharedProgress;
-use crate::progress::frontier::{Antichain, MutableAntichain};
+use crate::progress::frontier::{Antichain, AntichainRef, MutableAntichain};
 
 use crate::Container;
 use crate::container::ContainerBuilder;
 use crate::dataflow::{Scope, StreamCore};
+use crate::scheduling::Activator;
 use crate::dataflow::channels::pushers::Tee;
 use crate::dataflow::channels::pushers::Counter as PushCounter;
 use crate::dataflow::channels::pushers::buffer::Buffer as PushBuffer;
@@ -19,9 +22,9 @@ use crate::dataflow::channels::pullers::Counter as PullCounter;
 use crate::dataflow::operators::capability::Capability;
 use crate::dataflow::operators::generic::handles::{InputHandleCore, new_input_handle, OutputWrapper};
 use crate::dataflow::operators::generic::operator_info::OperatorInfo;
-use crate::dataflow::operators::generic::builder_raw::OperatorShape;
+use crate::dataflow::operators::generic::builder_raw::{Connectivity, OperatorShape};
 
-use crate::logging::TimelyLogger as Logger;
+use crate::logging::{HeldCapabilitiesEvent, TimelyLogger as Logger};
 
 use super::builder_raw::OperatorBuilder as OperatorBuilderRaw;
 
@@ -35,7 +38,11 @@ pub struct OperatorBuilder<G: Scope> {
     /// For each input, a shared list of summaries to each output.
     summaries: Vec<Rc<RefCell<Vec<Antichain<<G::Timestamp as Timestamp>::Summary>>>>>,
     produced: Vec<Rc<RefCell<ChangeBatch<G::Timestamp>>>>,
+    /// For each output, whether the operator is handed an initial capability for it.
+    initial_capabilities: Vec<bool>,
     logging: Option<Logger>,
+    /// The idle schedulings after which held capabilities are logged, if configured.
+    held_capability_schedules: Option<usize>,
 }
 
 impl<G: Scope> OperatorBuilder<G> {
@@ -43,6 +50,7 @@ impl<G: Scope> OperatorBuilder<G> {
     /// Allocates a new generic operator builder from its containing scope.
     pub fn new(name: String, scope: G) -> Self {
         let logging = scope.logging();
+        let held_capability_schedules = scope.config().held_capability_schedules;
         OperatorBuilder {
             builder: OperatorBuilderRaw::new(name, scope),
             frontier: Vec::new(),
@@ -50,10 +58,20 @@ impl<G: Scope> OperatorBuilder<G> {
             internal: Rc::new(RefCell::new(Vec::new())),
             summaries: Vec::new(),
             produced: Vec::new(),
+            initial_capabilities: Vec::new(),
             logging,
+            held_capability_schedules,
         }
     }
 
+    /// Replaces the operator's name, as used in logging and reported by its shape.
+    ///
+    /// This may be called at any point before `build`. The `OperatorInfo` does not carry a
+    /// name, and is unaffected.
+    pub fn set_name(&mut self, name: String) {
+        self.builder.set_name(name);
+    }
+
     /// Indicates whether the operator requires frontier information.
     pub fn set_notify(&mut self, notify: bool) {
         self.builder.set_notify(notify);
@@ -113,14 +131,122 @@ impl<G: Scope> OperatorBuilder<G> {
         OutputWrapper<G::Timestamp, CB, Tee<G::Timestamp, CB::Container>>,
         StreamCore<G, CB::Container>
     ) {
+        self.new_output_inner(connection, true, 0)
+    }
+
+    /// Adds a new output to a generic operator builder, preallocating room for `capacity` distinct
+    /// times in the output's internal capability changes.
+    ///
+    /// The output is otherwise identical to one added by `new_output`. Operators holding and
+    /// downgrading capabilities for many distinct times at once can use this to avoid repeatedly
+    /// growing the changes as they accumulate between schedulings.
+    pub fn new_output_with_capacity<CB: ContainerBuilder>(&mut self, capacity: usize) -> (OutputWrapper<G::Timestamp, CB, Tee<G::Timestamp, CB::Container>>, StreamCore<G, CB::Container>) {
+        let connection = vec![Antichain::from_elem(Default::default()); self.builder.shape().inputs()];
+        self.new_output_inner(connection, true, capacity)
+    }
+
+    /// Adds a new output to a generic operator builder, without an initial capability for it.
+    ///
+    /// The output is omitted from the capabilities handed to the constructor in `build` and
+    /// `build_reschedule`, rather than handing out a capability the operator would need to drop.
+    /// The operator can still send data on the output using capabilities obtained from its inputs.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::container::CapacityContainerBuilder;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    /// use timely::dataflow::operators::{ToStream, Inspect};
+    /// use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+    ///
+    /// timely::example(|scope| {
+    ///     let stream = (0u64..10).to_stream(scope);
+    ///
+    ///     let mut builder = OperatorBuilder::new("Forward".to_owned(), scope.clone());
+    ///     let mut input = builder.new_input(&stream, Pipeline);
+    ///     let (mut output, forwarded) = builder.new_output_no_capability::<CapacityContainerBuilder<Vec<u64>>>();
+    ///
+    ///     builder.build(move |capabilities| {
+    ///         assert!(capabilities.is_empty());
+    ///         move |_frontiers| {
+    ///             let mut output = output.activate();
+    ///             input.for_each(|time, data| {
+    ///                 output.session(&time).give_container(&mut data.replace(Vec::new()));
+    ///             });
+    ///         }
+    ///     });
+    ///
+    ///     forwarded.inspect(|x| println!("forwarded: {:?}", x));
+    /// });
+    /// ```
+    pub fn new_output_no_capability<CB: ContainerBuilder>(&mut self) -> (OutputWrapper<G::Timestamp, CB, Tee<G::Timestamp, CB::Container>>, StreamCore<G, CB::Container>) {
+        let connection = vec![Antichain::from_elem(Default::default()); self.builder.shape().inputs()];
+        self.new_output_inner(connection, false, 0)
+    }
+
+    /// Adds a new output at which the operator only sends data at the times of its inputs.
+    ///
+    /// The output is connected to every input with the identity summary and has no initial
+    /// capability, as with `new_output_no_capability`. In addition the operator is marked as not
+    /// requiring frontier information, as with `set_notify(false)`, so that it is only scheduled
+    /// when it receives data and not merely because its input frontiers change. This suits
+    /// operators like `map` and `filter`, which send each record at the time it arrived and never
+    /// retain capabilities.
+    ///
+    /// As the frontier information applies to the whole operator, its logic should not rely on
+    /// the frontiers it is presented with, nor retain capabilities for any of its outputs.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::container::CapacityContainerBuilder;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    /// use timely::dataflow::operators::{ToStream, Inspect};
+    /// use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+    ///
+    /// timely::example(|scope| {
+    ///     let stream = (0u64..10).to_stream(scope);
+    ///
+    ///     let mut builder = OperatorBuilder::new("Double".to_owned(), scope.clone());
+    ///     let mut input = builder.new_input(&stream, Pipeline);
+    ///     let (mut output, doubled) = builder.new_output_synchronous::<CapacityContainerBuilder<Vec<u64>>>();
+    ///
+    ///     builder.build(move |_capabilities| {
+    ///         let mut vector = Vec::new();
+    ///         move |_frontiers| {
+    ///             let mut output = output.activate();
+    ///             input.for_each(|time, data| {
+    ///                 data.swap(&mut vector);
+    ///                 output.session(&time).give_iterator(vector.drain(..).map(|x| 2 * x));
+    ///             });
+    ///         }
+    ///     });
+    ///
+    ///     doubled.inspect(|x| println!("doubled: {:?}", x));
+    /// });
+    /// ```
+    pub fn new_output_synchronous<CB: ContainerBuilder>(&mut self) -> (OutputWrapper<G::Timestamp, CB, Tee<G::Timestamp, CB::Container>>, StreamCore<G, CB::Container>) {
+        self.set_notify(false);
+        let connection = vec![Antichain::from_elem(Default::default()); self.builder.shape().inputs()];
+        self.new_output_inner(connection, false, 0)
+    }
+
+    fn new_output_inner<CB: ContainerBuilder>(
+        &mut self,
+        connection: Vec<Antichain<<G::Timestamp as Timestamp>::Summary>>,
+        initial_capability: bool,
+        capacity: usize,
+    ) -> (
+        OutputWrapper<G::Timestamp, CB, Tee<G::Timestamp, CB::Container>>,
+        StreamCore<G, CB::Container>
+    ) {
 
         let (tee, stream) = self.builder.new_output_connection(connection.clone());
 
-        let internal = Rc::new(RefCell::new(ChangeBatch::new()));
+        let internal = Rc::new(RefCell::new(ChangeBatch::with_capacity(capacity)));
         self.internal.borrow_mut().push(internal.clone());
 
         let mut buffer = PushBuffer::new(PushCounter::new(tee));
         self.produced.push(buffer.inner().produced().clone());
+        self.initial_capabilities.push(initial_capability);
 
         for (summary, connection) in self.summaries.iter().zip(connection.into_iter()) {
             summary.borrow_mut().push(connection.clone());
@@ -130,10 +256,38 @@ impl<G: Scope> OperatorBuilder<G> {
     }
 
     /// Creates an operator implementation from supplied logic constructor.
+    ///
+    /// The logic is presented with the [`Frontiers`] of the operator's inputs each time it is scheduled.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::channels::pact::Pipeline;
+    /// use timely::dataflow::operators::ToStream;
+    /// use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+    ///
+    /// timely::example(|scope| {
+    ///     let stream = (0u64..10).to_stream(scope);
+    ///
+    ///     let mut builder = OperatorBuilder::new("Frontiers".to_owned(), scope.clone());
+    ///     let mut input = builder.new_input(&stream, Pipeline);
+    ///
+    ///     builder.build(move |_capabilities| {
+    ///         move |frontiers| {
+    ///             input.for_each(|_time, _data| { });
+    ///             if frontiers.is_empty(0) {
+    ///                 println!("input complete");
+    ///             }
+    ///             else if !frontiers.less_equal(0, &5) {
+    ///                 println!("input complete through time 5");
+    ///             }
+    ///         }
+    ///     });
+    /// });
+    /// ```
     pub fn build<B, L>(self, constructor: B)
     where
         B: FnOnce(Vec<Capability<G::Timestamp>>) -> L,
-        L: FnMut(&[MutableAntichain<G::Timestamp>])+'static
+        L: FnMut(Frontiers<G::Timestamp>)+'static
     {
         self.build_reschedule(|caps| {
             let mut logic = constructor(caps);
@@ -147,20 +301,98 @@ impl<G: Scope> OperatorBuilder<G> {
     /// should be considered incomplete. The `build` method indicates that
     /// the operator is never incomplete and can be shut down at the system's
     /// discretion.
+    ///
+    /// The constructor receives one capability for each output, in the order the outputs
+    /// were created, except for outputs created with `new_output_no_capability` or
+    /// `new_output_synchronous`.
+    ///
+    /// The worker configuration `log_held_capabilities` enables logging a `HeldCapabilitiesEvent`
+    /// for operators that still hold capabilities once their inputs are complete. The check costs
+    /// nothing when it is not configured.
     pub fn build_reschedule<B, L>(self, constructor: B)
     where
         B: FnOnce(Vec<Capability<G::Timestamp>>) -> L,
-        L: FnMut(&[MutableAntichain<G::Timestamp>])->bool+'static
+        L: FnMut(Frontiers<G::Timestamp>)->bool+'static
+    {
+        self.build_reschedule_with_info(|caps, _info| constructor(caps))
+    }
+
+    /// Creates an operator implementation from supplied logic constructor, which also receives
+    /// the operator's [`OperatorInfo`].
+    ///
+    /// The info includes the index of the worker and the number of peers, so that the logic can
+    /// vary by worker without capturing the scope.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::container::CapacityContainerBuilder;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    /// use timely::dataflow::operators::{ToStream, Inspect};
+    /// use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+    ///
+    /// timely::example(|scope| {
+    ///     let stream = (0u64..10).to_stream(scope);
+    ///
+    ///     let mut builder = OperatorBuilder::new("Sample".to_owned(), scope.clone());
+    ///     let mut input = builder.new_input(&stream, Pipeline);
+    ///     let (mut output, sampled) = builder.new_output::<CapacityContainerBuilder<Vec<u64>>>();
+    ///
+    ///     builder.build_with_info(move |_capabilities, info| {
+    ///         // Each worker keeps a different residue of records.
+    ///         let residue = info.worker_index() as u64;
+    ///         let peers = info.peers() as u64;
+    ///         let mut vector = Vec::new();
+    ///         move |_frontiers| {
+    ///             let mut output_handle = output.activate();
+    ///             input.for_each(|time, data| {
+    ///                 data.swap(&mut vector);
+    ///                 output_handle.session(&time)
+    ///                              .give_iterator(vector.drain(..).filter(|x| x % peers == residue));
+    ///             });
+    ///         }
+    ///     });
+    ///
+    ///     sampled.inspect(|x| println!("sampled: {:?}", x));
+    /// });
+    /// ```
+    pub fn build_with_info<B, L>(self, constructor: B)
+    where
+        B: FnOnce(Vec<Capability<G::Timestamp>>, OperatorInfo) -> L,
+        L: FnMut(Frontiers<G::Timestamp>)+'static
+    {
+        self.build_reschedule_with_info(|caps, info| {
+            let mut logic = constructor(caps, info);
+            move |frontier| { logic(frontier); false }
+        })
+    }
+
+    /// Creates an operator implementation from supplied logic constructor, which also receives
+    /// the operator's [`OperatorInfo`].
+    ///
+    /// This is `build_reschedule`, with the info as for `build_with_info`.
+    pub fn build_reschedule_with_info<B, L>(self, constructor: B)
+    where
+        B: FnOnce(Vec<Capability<G::Timestamp>>, OperatorInfo) -> L,
+        L: FnMut(Frontiers<G::Timestamp>)->bool+'static
     {
+        let info = self.operator_info();
+
         // create capabilities, discard references to their creation.
         let mut capabilities = Vec::with_capacity(self.internal.borrow().len());
-        for batch in self.internal.borrow().iter() {
-            capabilities.push(Capability::new(G::Timestamp::minimum(), batch.clone()));
+        for (batch, &initial) in self.internal.borrow().iter().zip(self.initial_capabilities.iter()) {
+            let capability = Capability::new(G::Timestamp::minimum(), batch.clone());
             // Discard evidence of creation, as we are assumed to start with one.
             batch.borrow_mut().clear();
+            // Capabilities that were not requested are dropped here, and their release
+            // is reported the first time the operator is scheduled.
+            if initial {
+                capabilities.push(capability);
+            }
         }
 
-        let mut logic = constructor(capabilities);
+        let mut logic = constructor(capabilities, info);
+
+        let mut held_check = HeldCapabilities::new(&self.builder, self.held_capability_schedules, self.logging.clone());
 
         let mut self_frontier = self.frontier;
         let self_consumed = self.consumed;
@@ -176,7 +408,7 @@ impl<G: Scope> OperatorBuilder<G> {
             }
 
             // invoke supplied logic
-            let result = logic(&self_frontier[..]);
+            let result = logic(Frontiers::new(&self_frontier[..]));
 
             // move batches of consumed changes.
             for (progress, consumed) in progress.consumeds.iter_mut().zip(self_consumed.iter()) {
@@ -187,9 +419,16 @@ impl<G: Scope> OperatorBuilder<G> {
             let self_internal_borrow = self_internal.borrow_mut();
             for index in 0 .. self_internal_borrow.len() {
                 let mut borrow = self_internal_borrow[index].borrow_mut();
+                if let Some(check) = held_check.as_mut() {
+                    check.update(index, borrow.iter().cloned());
+                }
                 progress.internals[index].extend(borrow.drain());
             }
 
+            if let Some(check) = held_check.as_mut() {
+                check.observe(&self_frontier[..], result);
+            }
+
             // move batches of produced changes.
             for (progress, produced) in progress.produceds.iter_mut().zip(self_produced.iter()) {
                 produced.borrow_mut().drain_into(progress);
@@ -201,6 +440,85 @@ impl<G: Scope> OperatorBuilder<G> {
         self.builder.build(raw_logic);
     }
 
+    /// Creates an operator implementation from supplied logic constructor, whose logic may fail.
+    ///
+    /// The logic behaves as in `build_reschedule`, except that it returns a `Result`. When the logic
+    /// returns `Err`, it is dropped along with everything it owns, including its capabilities and
+    /// input and output handles, and the error is passed to `on_error`. The operator then reports
+    /// itself as complete, and is shut down once its inputs are complete.
+    ///
+    /// Only errors returned as `Err` are handled. A panic in the logic still unwinds the worker,
+    /// and is not caught here: a panic may leave the operator's shared `RefCell`s borrowed or
+    /// partially updated, and there is no way to recover a consistent operator from that state.
+    ///
+    /// Once the logic has been dropped, records that subsequently arrive at the operator's inputs
+    /// are not consumed, and hold back the frontiers of downstream operators. An error should
+    /// generally be followed by dropping the dataflow, for example with `Worker::drop_dataflow`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use timely::dataflow::channels::pact::Pipeline;
+    /// use timely::dataflow::operators::ToStream;
+    /// use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+    ///
+    /// let errors = timely::example(|scope| {
+    ///     let stream = (0u64..10).to_stream(scope);
+    ///     let errors = Arc::new(Mutex::new(Vec::new()));
+    ///
+    ///     let mut builder = OperatorBuilder::new("Validate".to_owned(), scope.clone());
+    ///     let mut input = builder.new_input(&stream, Pipeline);
+    ///
+    ///     let sink = Arc::clone(&errors);
+    ///     builder.build_fallible(
+    ///         move |_capabilities| {
+    ///             move |_frontiers| {
+    ///                 let mut error = None;
+    ///                 input.for_each(|_time, data| {
+    ///                     if let Some(x) = data.iter().find(|x| **x > 5) {
+    ///                         error.get_or_insert_with(|| format!("value out of range: {}", x));
+    ///                     }
+    ///                 });
+    ///                 match error {
+    ///                     Some(error) => Err(error),
+    ///                     None => Ok(false),
+    ///                 }
+    ///             }
+    ///         },
+    ///         move |error| sink.lock().unwrap().push(error),
+    ///     );
+    ///
+    ///     errors
+    /// });
+    ///
+    /// assert_eq!(errors.lock().unwrap().len(), 1);
+    /// ```
+    pub fn build_fallible<B, L, E, H>(self, constructor: B, on_error: H)
+    where
+        B: FnOnce(Vec<Capability<G::Timestamp>>) -> L,
+        L: FnMut(Frontiers<G::Timestamp>)->Result<bool, E>+'static,
+        H: FnOnce(E)+'static,
+    {
+        self.build_reschedule(move |caps| {
+            let mut logic = Some(constructor(caps));
+            let mut on_error = Some(on_error);
+            move |frontiers| {
+                match logic.as_mut().map(|logic| logic(frontiers)) {
+                    Some(Ok(incomplete)) => incomplete,
+                    Some(Err(error)) => {
+                        // Release the logic's capabilities before reporting the error.
+                        logic = None;
+                        if let Some(on_error) = on_error.take() {
+                            on_error(error);
+                        }
+                        false
+                    },
+                    None => false,
+                }
+            }
+        })
+    }
+
     /// Get the identifier assigned to the operator being constructed
     pub fn index(&self) -> usize {
         self.builder.index()
@@ -211,17 +529,204 @@ impl<G: Scope> OperatorBuilder<G> {
         self.builder.global()
     }
 
+    /// The summaries from each input to each output, as declared so far.
+    ///
+    /// This is intended for debugging, for example by printing the result with `{:?}` to see
+    /// which outputs each input can reach. Inspecting the summaries does not affect the operator.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::container::CapacityContainerBuilder;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    /// use timely::dataflow::operators::ToStream;
+    /// use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+    /// use timely::progress::Antichain;
+    ///
+    /// timely::example(|scope| {
+    ///     let stream = (0u64..10).to_stream(scope);
+    ///
+    ///     let mut builder = OperatorBuilder::new("Connected".to_owned(), scope.clone());
+    ///     let (_output0, _stream0) = builder.new_output::<CapacityContainerBuilder<Vec<u64>>>();
+    ///     let (_output1, _stream1) = builder.new_output::<CapacityContainerBuilder<Vec<u64>>>();
+    ///     let _input = builder.new_input_connection(&stream, Pipeline, vec![Antichain::from_elem(0), Antichain::new()]);
+    ///
+    ///     let connectivity = builder.connectivity();
+    ///     assert_eq!(connectivity.connection(0, 0), &Antichain::from_elem(0));
+    ///     assert!(connectivity.connection(0, 1).is_empty());
+    ///     assert_eq!(format!("{:?}", connectivity), "input 0: output 0 via [0];\n");
+    /// });
+    /// ```
+    pub fn connectivity(&self) -> Connectivity<'_, <G::Timestamp as Timestamp>::Summary> {
+        self.builder.connectivity()
+    }
+
     /// Return a reference to the operator's shape
     pub fn shape(&self) -> &OperatorShape {
         self.builder.shape()
     }
 
+    /// A handle that schedules the operator, immediately or after a delay.
+    ///
+    /// The handle can be moved into the operator's logic, for operators that act on wall-clock
+    /// time rather than only on their inputs. With `activate_after`, an operator that has nothing
+    /// to do until a deadline asks to be scheduled once it passes, instead of asking to be
+    /// rescheduled continually. The worker tracks delayed activations, and `step_or_park` parks
+    /// only until the earliest of them is due.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use timely::container::CapacityContainerBuilder;
+    /// use timely::dataflow::operators::Inspect;
+    /// use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+    ///
+    /// timely::example(|scope| {
+    ///     let mut builder = OperatorBuilder::new("Alarm".to_owned(), scope.clone());
+    ///     let (mut output, stream) = builder.new_output::<CapacityContainerBuilder<Vec<&str>>>();
+    ///     let activator = builder.activator();
+    ///
+    ///     builder.build(move |mut capabilities| {
+    ///         let deadline = Instant::now() + Duration::from_millis(10);
+    ///         activator.activate_after(Duration::from_millis(10));
+    ///         move |_frontiers| {
+    ///             if Instant::now() >= deadline {
+    ///                 if let Some(capability) = capabilities.pop() {
+    ///                     output.activate().session(&capability).give("ring");
+    ///                 }
+    ///             }
+    ///             else if !capabilities.is_empty() {
+    ///                 activator.activate_after(deadline.saturating_duration_since(Instant::now()));
+    ///             }
+    ///         }
+    ///     });
+    ///
+    ///     stream.inspect(|x| println!("{}", x));
+    /// });
+    /// ```
+    pub fn activator(&self) -> Activator {
+        self.builder.activator()
+    }
+
     /// Creates operator info for the operator.
     pub fn operator_info(&self) -> OperatorInfo {
         self.builder.operator_info()
     }
 }
 
+/// Opt-in diagnostics for capabilities held after an operator's inputs are complete.
+///
+/// Enabled by `worker::Config::log_held_capabilities`. Once the operator has been scheduled the
+/// configured number of times with all input frontiers empty, without asking to be rescheduled,
+/// yet still holding capabilities, a `HeldCapabilitiesEvent` naming the held times is logged.
+struct HeldCapabilities<T: Timestamp> {
+    global: usize,
+    threshold: usize,
+    idle: usize,
+    logged: bool,
+    logging: Logger,
+    /// Capabilities held for each output, as accumulated from internal changes.
+    held: Vec<MutableAntichain<T>>,
+}
+
+impl<T: Timestamp> HeldCapabilities<T> {
+    /// Returns a check for the operator under construction, if enabled and meaningful.
+    ///
+    /// Operators without inputs have vacuously complete inputs, and are not checked.
+    fn new<G: Scope<Timestamp=T>>(builder: &OperatorBuilderRaw<G>, threshold: Option<usize>, logging: Option<Logger>) -> Option<Self> {
+        if builder.shape().inputs() == 0 {
+            return None;
+        }
+        Some(HeldCapabilities {
+            global: builder.global(),
+            threshold: threshold?,
+            idle: 0,
+            logged: false,
+            logging: logging?,
+            // Each output starts with one capability, whose release is reported as a change.
+            held: (0 .. builder.shape().outputs()).map(|_| MutableAntichain::new_bottom(T::minimum())).collect(),
+        })
+    }
+
+    /// Accumulates capability changes for `output`.
+    fn update<I: IntoIterator<Item=(T, i64)>>(&mut self, output: usize, changes: I) {
+        self.held[output].update_iter(changes);
+    }
+
+    /// Records a scheduling of the operator, and logs once the threshold is reached.
+    fn observe(&mut self, frontiers: &[MutableAntichain<T>], reschedule: bool) {
+        let complete = frontiers.iter().all(|frontier| frontier.is_empty());
+        let holding = self.held.iter().any(|held| !held.is_empty());
+        if complete && holding && !reschedule {
+            self.idle += 1;
+            if self.idle >= self.threshold && !self.logged {
+                self.logged = true;
+                let held = self.held.iter().map(|held| format!("{:?}", held.frontier().to_vec())).collect();
+                self.logging.log(HeldCapabilitiesEvent { id: self.global, held });
+            }
+        }
+        else {
+            self.idle = 0;
+        }
+    }
+}
+
+/// The input frontiers of an operator, as presented to its logic.
+///
+/// This is a thin wrapper around the operator's `[MutableAntichain<T>]` frontiers, and dereferences
+/// to that slice. Its methods answer common questions about individual inputs, identified by their
+/// index, without reaching into the `MutableAntichain` for each of them.
+#[derive(Debug)]
+pub struct Frontiers<'a, T> {
+    frontiers: &'a [MutableAntichain<T>],
+}
+
+impl<'a, T> Clone for Frontiers<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for Frontiers<'a, T> { }
+
+impl<'a, T> Frontiers<'a, T> {
+    /// Wraps a slice of input frontiers.
+    pub fn new(frontiers: &'a [MutableAntichain<T>]) -> Self {
+        Frontiers { frontiers }
+    }
+
+    /// The frontier of input `input`.
+    #[inline]
+    pub fn frontier(&self, input: usize) -> AntichainRef<'a, T> {
+        self.frontiers[input].frontier()
+    }
+
+    /// Returns true if input `input` is complete, meaning it will receive no further data at any time.
+    #[inline]
+    pub fn is_empty(&self, input: usize) -> bool {
+        self.frontiers[input].is_empty()
+    }
+
+    /// Returns true if input `input` may still receive data at some time strictly less than `time`.
+    #[inline]
+    pub fn less_than(&self, input: usize, time: &T) -> bool where T: PartialOrder {
+        self.frontiers[input].less_than(time)
+    }
+
+    /// Returns true if input `input` may still receive data at some time less than or equal to `time`.
+    ///
+    /// When this returns false, the input is complete for `time`.
+    #[inline]
+    pub fn less_equal(&self, input: usize, time: &T) -> bool where T: PartialOrder {
+        self.frontiers[input].less_equal(time)
+    }
+}
+
+impl<'a, T> Deref for Frontiers<'a, T> {
+    type Target = [MutableAntichain<T>];
+    fn deref(&self) -> &Self::Target {
+        self.frontiers
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -295,4 +800,36 @@ mod tests {
             "Hello".to_owned()
         });
     }
+
+    #[test]
+    fn no_capability_outputs() {
+
+        // This tests that outputs declared without an initial capability are omitted
+        // from the capabilities handed to the constructor, and do not hold back progress
+        // (the computation would otherwise not complete).
+
+        use crate::dataflow::operators::generic::builder_rc::OperatorBuilder;
+
+        crate::example(|scope| {
+
+            let mut builder = OperatorBuilder::new("NoCapability".to_owned(), scope.clone());
+
+            let (mut output1, _stream1) = builder.new_output_no_capability::<CapacityContainerBuilder<Vec<()>>>();
+            let (mut output2, _stream2) = builder.new_output::<CapacityContainerBuilder<Vec<()>>>();
+
+            builder.build(move |mut capabilities| {
+                assert_eq!(capabilities.len(), 1);
+                move |_frontiers| {
+
+                    let _output_handle1 = output1.activate();
+                    let mut output_handle2 = output2.activate();
+
+                    // The only capability belongs to the second output.
+                    if let Some(capability) = capabilities.pop() {
+                        output_handle2.session(&capability).give(());
+                    }
+                }
+            });
+        });
+    }
 }
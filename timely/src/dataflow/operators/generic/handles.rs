@@ -12,14 +12,14 @@ use crate::progress::ChangeBatch;
 use crate::progress::frontier::MutableAntichain;
 use crate::dataflow::channels::pullers::Counter as PullCounter;
 use crate::dataflow::channels::pushers::Counter as PushCounter;
-use crate::dataflow::channels::pushers::buffer::{Buffer, Session};
+use crate::dataflow::channels::pushers::buffer::{AutoflushSession, Buffer, Session};
 use crate::dataflow::channels::Bundle;
 use crate::communication::{Push, Pull, message::RefOrMut};
 use crate::Container;
 use crate::container::{ContainerBuilder, CapacityContainerBuilder};
 use crate::logging::TimelyLogger as Logger;
 
-use crate::dataflow::operators::InputCapability;
+use crate::dataflow::operators::{Capability, InputCapability};
 use crate::dataflow::operators::capability::CapabilityTrait;
 
 /// Handle to an operator's input stream.
@@ -236,6 +236,36 @@ impl<'a, T: Timestamp, CB: ContainerBuilder, P: Push<Bundle<T, CB::Container>>>
         self.push_buffer.session_with_builder(cap.time())
     }
 
+    /// Obtains a session that holds `cap` and sends data at its time until the session is dropped.
+    ///
+    /// The session borrows the output handle, and so no session for another time can be opened
+    /// while it is live. All data given to the session is flushed when it is dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::ToStream;
+    /// use timely::dataflow::operators::generic::Operator;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    /// use timely::container::CapacityContainerBuilder;
+    ///
+    /// timely::example(|scope| {
+    ///     (0u64..10).to_stream(scope)
+    ///            .unary::<CapacityContainerBuilder<Vec<_>>, _, _, _>(Pipeline, "example", |_cap, _info| |input, output| {
+    ///                while let Some((cap, data)) = input.next() {
+    ///                    let mut session = output.session_for(cap.retain());
+    ///                    for datum in data.iter() {
+    ///                        session.give(*datum);
+    ///                        session.give(*datum + 1);
+    ///                    }
+    ///                }
+    ///            });
+    /// });
+    /// ```
+    pub fn session_for(&mut self, cap: Capability<T>) -> AutoflushSession<'_, T, CB, PushCounter<T, CB::Container, P>> {
+        assert!(cap.valid_for_output(&self.internal_buffer), "Attempted to open output session with invalid capability");
+        self.push_buffer.autoflush_session_with_builder(cap)
+    }
+
     /// Flushes all pending data and indicate that no more data immediately follows.
     pub fn cease(&mut self) {
         self.push_buffer.cease();
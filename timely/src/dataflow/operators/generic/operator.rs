@@ -245,6 +245,61 @@ pub trait Operator<G: Scope, C1: Container> {
     ///    }
     /// }).unwrap();
     /// ```
+    ///
+    /// Stashing records until their time is complete yields a simple inner join:
+    /// ```
+    /// use std::collections::HashMap;
+    /// use timely::dataflow::operators::{Input, Inspect};
+    /// use timely::dataflow::operators::generic::operator::Operator;
+    /// use timely::dataflow::channels::pact::Exchange;
+    ///
+    /// timely::execute(timely::Config::thread(), |worker| {
+    ///    let (mut names, mut ages) = worker.dataflow::<usize,_,_>(|scope| {
+    ///        let (names_handle, names) = scope.new_input::<(u64, String)>();
+    ///        let (ages_handle, ages) = scope.new_input::<(u64, usize)>();
+    ///
+    ///        let mut stash = HashMap::new();
+    ///        let mut vector1 = Vec::new();
+    ///        let mut vector2 = Vec::new();
+    ///        let pact1 = Exchange::new(|x: &(u64, String)| x.0);
+    ///        let pact2 = Exchange::new(|x: &(u64, usize)| x.0);
+    ///        names.binary_notify(&ages, pact1, pact2, "Join", None, move |input1, input2, output, notificator| {
+    ///            input1.for_each(|time, data| {
+    ///                data.swap(&mut vector1);
+    ///                let entry = stash.entry(time.time().clone()).or_insert_with(|| (Vec::new(), Vec::new()));
+    ///                entry.0.extend(vector1.drain(..));
+    ///                notificator.notify_at(time.retain());
+    ///            });
+    ///            input2.for_each(|time, data| {
+    ///                data.swap(&mut vector2);
+    ///                let entry = stash.entry(time.time().clone()).or_insert_with(|| (Vec::new(), Vec::new()));
+    ///                entry.1.extend(vector2.drain(..));
+    ///                notificator.notify_at(time.retain());
+    ///            });
+    ///            notificator.for_each(|time, _cnt, _not| {
+    ///                if let Some((left, right)) = stash.remove(time.time()) {
+    ///                    let mut session = output.session(&time);
+    ///                    for (key1, name) in left.iter() {
+    ///                        for (key2, age) in right.iter() {
+    ///                            if key1 == key2 {
+    ///                                session.give((*key1, name.clone(), *age));
+    ///                            }
+    ///                        }
+    ///                    }
+    ///                }
+    ///            });
+    ///        })
+    ///        .inspect(|x| println!("joined: {:?}", x));
+    ///
+    ///        (names_handle, ages_handle)
+    ///    });
+    ///
+    ///    names.send((0, "alice".to_string()));
+    ///    ages.send((0, 32));
+    ///    names.advance_to(1);
+    ///    ages.advance_to(1);
+    /// }).unwrap();
+    /// ```
     fn binary_notify<C2: Container,
               CB: ContainerBuilder,
               L: FnMut(&mut InputHandleCore<G::Timestamp, C1, P1::Puller>,
@@ -8,15 +8,32 @@ pub struct OperatorInfo {
     pub global_id: usize,
     /// Operator address.
     pub address: Vec<usize>,
+    worker_index: usize,
+    peers: usize,
 }
 
 impl OperatorInfo {
     /// Construct a new `OperatorInfo`.
+    ///
+    /// The info describes worker 0 of a single worker; use `with_worker` to set these.
     pub fn new(local_id: usize, global_id: usize, address: &[usize]) -> OperatorInfo {
+        Self::with_worker(local_id, global_id, address, 0, 1)
+    }
+
+    /// Construct a new `OperatorInfo` for the worker `worker_index` of `peers` workers.
+    pub fn with_worker(local_id: usize, global_id: usize, address: &[usize], worker_index: usize, peers: usize) -> OperatorInfo {
         OperatorInfo {
             local_id,
             global_id,
             address: address.to_vec(),
+            worker_index,
+            peers,
         }
     }
+
+    /// Index of the worker constructing the operator.
+    pub fn worker_index(&self) -> usize { self.worker_index }
+
+    /// Number of workers in the computation.
+    pub fn peers(&self) -> usize { self.peers }
 }
@@ -94,3 +94,6 @@ impl<G: Scope> Input for G where <G as ScopeParent>::Timestamp: TotalOrder {
 
 /// A handle to an input `Stream`, used to introduce data to a timely dataflow computation.
 pub type Handle<T, D> = crate::dataflow::operators::core::input::Handle<T, Vec<D>>;
+
+/// An input handle that advances its own epoch to the times of the records it is sent.
+pub type AutoHandle<T, D, F> = crate::dataflow::operators::core::input::AutoHandle<T, Vec<D>, F>;
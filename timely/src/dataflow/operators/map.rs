@@ -1,8 +1,11 @@
 //! Extension methods for `Stream` based on record-by-record transformation.
 
+use std::collections::VecDeque;
+
 use crate::Data;
 use crate::dataflow::{Stream, Scope};
 use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::operators::Capability;
 use crate::dataflow::operators::generic::operator::Operator;
 use crate::dataflow::operators::core::{Map as MapCore};
 
@@ -36,6 +39,35 @@ pub trait Map<S: Scope, D: Data> {
     /// });
     /// ```
     fn map_in_place<L: FnMut(&mut D)+'static>(&self, logic: L) -> Stream<S, D>;
+    /// Consumes each element of the stream and applies each of `stages` to it in sequence.
+    ///
+    /// The stages are applied within a single operator, rather than one operator per stage, and
+    /// so avoid the channels between stages. All stages run when the operator is scheduled, and
+    /// each batch of input passes through every stage before the next is read. This suits
+    /// pipelines assembled at runtime; with no stages, elements are passed through unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Map, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     let stages: Vec<Box<dyn FnMut(u64)->u64>> = vec![
+    ///         Box::new(|x| x + 1),
+    ///         Box::new(|x| x * 2),
+    ///     ];
+    ///     (0..10).to_stream(scope)
+    ///            .pipeline(stages)
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn pipeline(&self, mut stages: Vec<Box<dyn FnMut(D)->D>>) -> Stream<S, D> {
+        self.map(move |mut datum| {
+            for stage in stages.iter_mut() {
+                datum = stage(datum);
+            }
+            datum
+        })
+    }
     /// Consumes each element of the stream and yields some number of new elements.
     ///
     /// # Examples
@@ -49,6 +81,30 @@ pub trait Map<S: Scope, D: Data> {
     /// });
     /// ```
     fn flat_map<I: IntoIterator, L: FnMut(D)->I+'static>(&self, logic: L) -> Stream<S, I::Item> where I::Item: Data;
+    /// Consumes each element of the stream and yields some number of new elements, producing at
+    /// most `max_per_schedule` elements each time the operator is scheduled.
+    ///
+    /// Unlike `flat_map`, the iterator of each element is retained and resumed at the next
+    /// schedule, rather than drained at once, so that an element producing many records neither
+    /// monopolizes the worker nor enqueues all of its records at once. The operator holds the
+    /// capabilities of its pending input, so that output is produced at the input timestamps.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Map, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     (0..10).to_stream(scope)
+    ///            .flat_map_bounded(4, |x| (0..x))
+    ///            .inspect(|x| println!("seen: {:?}", x));
+    /// });
+    /// ```
+    fn flat_map_bounded<I, L>(&self, max_per_schedule: usize, logic: L) -> Stream<S, I::Item>
+    where
+        I: IntoIterator,
+        I::IntoIter: 'static,
+        I::Item: Data,
+        L: FnMut(D)->I+'static;
 }
 
 impl<S: Scope, D: Data> Map<S, D> for Stream<S, D> {
@@ -64,8 +120,60 @@ impl<S: Scope, D: Data> Map<S, D> for Stream<S, D> {
     }
     // TODO : This would be more robust if it captured an iterator and then pulled an appropriate
     // TODO : number of elements from the iterator. This would allow iterators that produce many
-    // TODO : records without taking arbitrarily long and arbitrarily much memory.
+    // TODO : records without taking arbitrarily long. Output containers are sent as they fill,
+    // TODO : but all of them are enqueued for the downstream operator before it can run.
     fn flat_map<I: IntoIterator, L: FnMut(D)->I+'static>(&self, logic: L) -> Stream<S, I::Item> where I::Item: Data {
         MapCore::flat_map(self, logic)
     }
+    fn flat_map_bounded<I, L>(&self, max_per_schedule: usize, mut logic: L) -> Stream<S, I::Item>
+    where
+        I: IntoIterator,
+        I::IntoIter: 'static,
+        I::Item: Data,
+        L: FnMut(D)->I+'static,
+    {
+        assert!(max_per_schedule > 0, "flat_map_bounded requires a positive max_per_schedule");
+
+        // Input not yet consumed, and the iterator of the element being consumed.
+        let mut pending = VecDeque::new();
+        let mut current: Option<(Capability<S::Timestamp>, I::IntoIter)> = None;
+
+        let scope = self.scope();
+        self.unary(Pipeline, "FlatMapBounded", move |_,info| {
+            let activator = scope.activator_for(&info.address[..]);
+            move |input, output| {
+                input.for_each(|time, data| {
+                    pending.push_back((time.retain(), data.replace(Vec::new()).into_iter()));
+                });
+
+                let mut budget = max_per_schedule;
+                while budget > 0 {
+                    if current.is_none() {
+                        match pending.front_mut() {
+                            Some((cap, data)) => match data.next() {
+                                Some(datum) => { current = Some((cap.clone(), logic(datum).into_iter())); },
+                                None => { pending.pop_front(); continue; },
+                            },
+                            None => break,
+                        }
+                    }
+                    if let Some((cap, iter)) = current.as_mut() {
+                        let mut session = output.session(cap);
+                        for item in iter.by_ref().take(budget) {
+                            session.give(item);
+                            budget -= 1;
+                        }
+                    }
+                    // The iterator is exhausted if it ran out before the budget did.
+                    if budget > 0 {
+                        current = None;
+                    }
+                }
+
+                if current.is_some() || !pending.is_empty() {
+                    activator.activate();
+                }
+            }
+        })
+    }
 }
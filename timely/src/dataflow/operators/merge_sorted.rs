@@ -0,0 +1,97 @@
+//! Merges two streams into one, ordered within each timestamp.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::Data;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Merges two streams, ordering their records within each timestamp.
+pub trait MergeSorted<G: Scope, D: Data> {
+    /// Merges `self` and `other` into one stream, ordered by `cmp` at each timestamp.
+    ///
+    /// The records of each input are buffered until their timestamp is complete on both inputs,
+    /// after which the merged run for that timestamp is sent in order. Each input is stably sorted
+    /// by `cmp` before merging, which is cheap if it arrives already sorted. Among records that
+    /// compare equal, those of `self` come before those of `other`, and each input's own order
+    /// is preserved.
+    ///
+    /// The ordering is per worker, and holds for the records each worker receives; it is not a
+    /// global order across workers. All records of a timestamp are held in memory until the
+    /// timestamp completes, so an input that runs far ahead of the other, or a timestamp that
+    /// receives many records, is buffered in its entirety.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use timely::dataflow::operators::{ToStream, Inspect, MergeSorted};
+    ///
+    /// let merged = Arc::new(Mutex::new(Vec::new()));
+    /// let sink = Arc::clone(&merged);
+    ///
+    /// timely::example(move |scope| {
+    ///     let left = vec![(1, 'a'), (3, 'a'), (5, 'a')].into_iter().to_stream(scope);
+    ///     let right = vec![(2, 'b'), (3, 'b'), (4, 'b')].into_iter().to_stream(scope);
+    ///     left.merge_sorted(&right, |x, y| x.0.cmp(&y.0))
+    ///         .inspect(move |x| sink.lock().unwrap().push(*x));
+    /// });
+    ///
+    /// let expected = vec![(1, 'a'), (2, 'b'), (3, 'a'), (3, 'b'), (4, 'b'), (5, 'a')];
+    /// assert_eq!(*merged.lock().unwrap(), expected);
+    /// ```
+    fn merge_sorted<F>(&self, other: &Stream<G, D>, cmp: F) -> Stream<G, D>
+    where
+        F: FnMut(&D, &D)->Ordering+'static;
+}
+
+impl<G: Scope, D: Data> MergeSorted<G, D> for Stream<G, D> {
+    fn merge_sorted<F>(&self, other: &Stream<G, D>, mut cmp: F) -> Stream<G, D>
+    where
+        F: FnMut(&D, &D)->Ordering+'static,
+    {
+        // Records of each input, by the timestamp at which they await merging.
+        let mut stash: HashMap<G::Timestamp, (Vec<D>, Vec<D>)> = HashMap::new();
+        let mut vector = Vec::new();
+
+        self.binary_notify(other, Pipeline, Pipeline, "MergeSorted", vec![], move |input1, input2, output, notificator| {
+
+            input1.for_each(|time, data| {
+                data.swap(&mut vector);
+                stash.entry(time.time().clone())
+                     .or_insert_with(|| { notificator.notify_at(time.retain()); Default::default() })
+                     .0.append(&mut vector);
+            });
+
+            input2.for_each(|time, data| {
+                data.swap(&mut vector);
+                stash.entry(time.time().clone())
+                     .or_insert_with(|| { notificator.notify_at(time.retain()); Default::default() })
+                     .1.append(&mut vector);
+            });
+
+            notificator.for_each(|time, _, _| {
+                if let Some((mut left, mut right)) = stash.remove(time.time()) {
+                    left.sort_by(&mut cmp);
+                    right.sort_by(&mut cmp);
+
+                    let mut session = output.session(&time);
+                    let mut left = left.into_iter().peekable();
+                    let mut right = right.into_iter().peekable();
+                    loop {
+                        // Take from the left unless the right is strictly less, to keep ties left first.
+                        let take_left = match (left.peek(), right.peek()) {
+                            (Some(l), Some(r)) => cmp(r, l) != Ordering::Less,
+                            (Some(_), None) => true,
+                            (None, Some(_)) => false,
+                            (None, None) => break,
+                        };
+                        let next = if take_left { left.next() } else { right.next() };
+                        session.give(next.expect("peeked record missing"));
+                    }
+                }
+            });
+        })
+    }
+}
@@ -15,6 +15,10 @@ pub use self::map::Map;
 pub use self::inspect::{Inspect, InspectCore};
 pub use self::filter::Filter;
 pub use self::delay::Delay;
+pub use self::debounce::Debounce;
+pub use self::merge_sorted::MergeSorted;
+pub use self::zip::Zip;
+pub use self::unzip::Unzip;
 pub use self::exchange::Exchange;
 pub use self::broadcast::Broadcast;
 pub use self::branch::{Branch, BranchWhen};
@@ -27,6 +31,8 @@ pub use self::generic::{Notificator, FrontierNotificator};
 
 pub use self::reclock::Reclock;
 pub use self::count::Accumulate;
+pub use self::quantiles::Quantiles;
+pub use self::frontier_stream::FrontierStream;
 
 pub mod core;
 
@@ -36,16 +42,23 @@ pub mod flow_controlled;
 pub mod unordered_input;
 pub use self::core::feedback::{self, Feedback, LoopVariable, ConnectLoop};
 pub use self::core::concat::{self, Concat, Concatenate};
+pub use self::core::container_stats::{self, ContainerStats};
 pub mod partition;
 pub mod map;
 pub use self::core::inspect;
 pub mod filter;
 pub mod delay;
+pub mod debounce;
+pub mod merge_sorted;
+pub mod zip;
+pub mod unzip;
 pub use self::core::exchange;
 pub mod broadcast;
 pub use self::core::probe::{self, Probe};
 pub mod to_stream;
 pub use self::core::capture::{self, Capture};
+pub use self::core::coalesce::{self, Coalesce};
+pub use self::core::for_each::{self, ForEach};
 pub mod branch;
 pub use self::core::ok_err::{self, OkErr};
 pub use self::core::rc;
@@ -56,6 +69,9 @@ pub mod generic;
 
 pub use self::core::reclock;
 pub mod count;
+pub mod quantiles;
+pub mod frontier_stream;
+pub mod trace;
 
 // keep "mint" module-private
 mod capability;
@@ -0,0 +1,156 @@
+//! Estimates quantiles of the numbers at each time.
+
+use std::collections::HashMap;
+
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// The number of records sampled at each time.
+const RESERVOIR_SIZE: usize = 4096;
+
+/// Estimates quantiles of the numbers at each time.
+pub trait Quantiles<G: Scope> {
+    /// Reports, once each time is complete, the value of each quantile in `qs` among the records
+    /// at that time, as pairs of the quantile and its value.
+    ///
+    /// Quantiles are computed on a uniform random sample of up to 4096 of the records at each
+    /// time, retained with reservoir sampling, and the value of quantile `q` is the element of rank
+    /// `ceil(q * n)` in the sorted sample of size `n`; so `0.0` reports the least and `1.0` the
+    /// greatest sampled value. If a time has at most 4096 records, the quantiles are exact. Otherwise,
+    /// the rank of the reported value among all records at the time deviates from `q` by a standard
+    /// deviation of `sqrt(q * (1 - q) / 4096)` of their number, which is at most 0.8% at the median.
+    /// The sampling is pseudo-random with a fixed seed, and so is deterministic for a given input order.
+    ///
+    /// Each worker reports the quantiles of its own records, and per-time state is released once
+    /// they are reported. To compute quantiles across workers, first exchange the records to a single
+    /// worker. Values are ordered by `f64::total_cmp`, which places NaNs after all numbers.
+    ///
+    /// This method panics if any element of `qs` is not between `0.0` and `1.0`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use timely::dataflow::operators::{ToStream, Quantiles, Inspect};
+    ///
+    /// let reported = Arc::new(Mutex::new(Vec::new()));
+    /// let sink = Arc::clone(&reported);
+    ///
+    /// timely::example(move |scope| {
+    ///     (1..=100).map(|x| x as f64)
+    ///              .to_stream(scope)
+    ///              .quantiles(&[0.0, 0.5, 0.9, 1.0])
+    ///              .inspect(move |x| sink.lock().unwrap().push(x.clone()));
+    /// });
+    ///
+    /// assert_eq!(*reported.lock().unwrap(), vec![vec![(0.0, 1.0), (0.5, 50.0), (0.9, 90.0), (1.0, 100.0)]]);
+    /// ```
+    fn quantiles(&self, qs: &[f64]) -> Stream<G, Vec<(f64, f64)>>;
+}
+
+impl<G: Scope> Quantiles<G> for Stream<G, f64> {
+    fn quantiles(&self, qs: &[f64]) -> Stream<G, Vec<(f64, f64)>> {
+        for q in qs.iter() {
+            assert!((0.0 ..= 1.0).contains(q), "quantiles: {} is not between 0.0 and 1.0", q);
+        }
+        let qs = qs.to_vec();
+
+        let mut reservoirs = HashMap::new();
+        self.unary_notify(Pipeline, "Quantiles", vec![], move |input, output, notificator| {
+            input.for_each(|time, data| {
+                let reservoir = reservoirs
+                    .entry(time.time().clone())
+                    .or_insert_with(|| { notificator.notify_at(time.retain()); Reservoir::new() });
+                for value in data.iter() {
+                    reservoir.insert(*value);
+                }
+            });
+
+            notificator.for_each(|time, _, _| {
+                if let Some(reservoir) = reservoirs.remove(&time) {
+                    output.session(&time).give(reservoir.quantiles(&qs));
+                }
+            });
+        })
+    }
+}
+
+/// A uniform random sample of up to `RESERVOIR_SIZE` values.
+struct Reservoir {
+    sample: Vec<f64>,
+    /// The number of values inserted.
+    seen: u64,
+    /// The state of the pseudo-random number generator.
+    state: u64,
+}
+
+impl Reservoir {
+    fn new() -> Self {
+        Reservoir { sample: Vec::new(), seen: 0, state: 0x853c_49e6_748f_ea9b }
+    }
+
+    /// Inserts `value`, replacing a sampled value with the probability that keeps the sample uniform.
+    fn insert(&mut self, value: f64) {
+        if self.sample.len() < RESERVOIR_SIZE {
+            self.sample.push(value);
+        }
+        else {
+            let index = (((self.next_random() as u128) * ((self.seen + 1) as u128)) >> 64) as usize;
+            if index < RESERVOIR_SIZE {
+                self.sample[index] = value;
+            }
+        }
+        self.seen += 1;
+    }
+
+    /// The next value of a splitmix64 generator.
+    fn next_random(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// The value of each quantile in `qs` among the sample, which must be non-empty.
+    fn quantiles(mut self, qs: &[f64]) -> Vec<(f64, f64)> {
+        self.sample.sort_by(|x, y| x.total_cmp(y));
+        let len = self.sample.len();
+        qs.iter()
+            .map(|&q| {
+                let rank = ((q * len as f64).ceil() as usize).max(1).min(len);
+                (q, self.sample[rank - 1])
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Reservoir, RESERVOIR_SIZE};
+
+    #[test]
+    fn exact_below_reservoir_size() {
+        let mut reservoir = Reservoir::new();
+        for value in (0 .. RESERVOIR_SIZE).rev() {
+            reservoir.insert(value as f64);
+        }
+        let quantiles = reservoir.quantiles(&[0.0, 0.25, 1.0]);
+        assert_eq!(quantiles, vec![(0.0, 0.0), (0.25, 1023.0), (1.0, (RESERVOIR_SIZE - 1) as f64)]);
+    }
+
+    #[test]
+    fn approximate_above_reservoir_size() {
+        let count = 1_000_000;
+        let mut reservoir = Reservoir::new();
+        for value in 0 .. count {
+            reservoir.insert(value as f64);
+        }
+        assert_eq!(reservoir.sample.len(), RESERVOIR_SIZE);
+        for (q, value) in reservoir.quantiles(&[0.1, 0.5, 0.9]) {
+            // Four standard deviations of the sampled rank.
+            let tolerance = 4.0 * (q * (1.0 - q) / RESERVOIR_SIZE as f64).sqrt() * count as f64;
+            assert!((value - q * count as f64).abs() < tolerance, "quantile {} estimated as {}", q, value);
+        }
+    }
+}
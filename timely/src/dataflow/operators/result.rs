@@ -2,6 +2,7 @@
 
 use crate::Data;
 use crate::dataflow::operators::Map;
+use crate::dataflow::operators::core::OkErr;
 use crate::dataflow::{Scope, Stream};
 
 /// Extension trait for `Stream`.
@@ -34,6 +35,25 @@ pub trait ResultStream<S: Scope, T: Data, E: Data> {
     /// ```
     fn err(&self) -> Stream<S, E>;
 
+    /// Splits `self` into a stream of `Ok` values and a stream of `Err` values.
+    ///
+    /// Each value is sent at the timestamp of its record, by a single operator with one input and
+    /// two outputs. This is the usual way to set aside records that failed to process, for
+    /// example to report them separately from the results.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Inspect, ResultStream};
+    ///
+    /// timely::example(|scope| {
+    ///     let (oks, errs) = vec![Ok(0), Err("failed"), Ok(1)].to_stream(scope)
+    ///            .ok_and_err();
+    ///     oks.inspect(|x| println!("ok: {:?}", x));
+    ///     errs.inspect(|x| println!("err: {:?}", x));
+    /// });
+    /// ```
+    fn ok_and_err(&self) -> (Stream<S, T>, Stream<S, E>);
+
     /// Returns a new instance of `self` applying `logic` on all `Ok` records.
     ///
     /// # Examples
@@ -104,6 +124,10 @@ impl<S: Scope, T: Data, E: Data> ResultStream<S, T, E> for Stream<S, Result<T, E
         self.flat_map(Result::err)
     }
 
+    fn ok_and_err(&self) -> (Stream<S, T>, Stream<S, E>) {
+        self.ok_err(|result| result)
+    }
+
     fn map_ok<T2: Data, L: FnMut(T) -> T2 + 'static>(&self, mut logic: L) -> Stream<S, Result<T2, E>> {
         self.map(move |r| r.map(|x| logic(x)))
     }
@@ -145,6 +169,17 @@ mod tests {
         assert_eq!(output.extract()[0].1, vec![()]);
     }
 
+    #[test]
+    fn test_ok_and_err() {
+        let (oks, errs) = crate::example(|scope| {
+            let (oks, errs) = vec![Ok(0), Err(()), Ok(1)].to_stream(scope)
+                .ok_and_err();
+            (oks.capture(), errs.capture())
+        });
+        assert_eq!(oks.extract()[0].1, vec![0, 1]);
+        assert_eq!(errs.extract()[0].1, vec![()]);
+    }
+
     #[test]
     fn test_map_ok() {
         let output = crate::example(|scope| {
@@ -0,0 +1,158 @@
+//! Tracing of individual records through a dataflow.
+//!
+//! A record is traced by wrapping it in a [`Traced`], which carries an optional trace identifier
+//! alongside the data. The `traced` operator marks records with identifiers, and each of the
+//! operators of [`TracedStream`] logs a `RecordTraceEvent` to the worker's `"timely"` logger for
+//! each marked record it receives, recording the identifier, the operator and the time. The path
+//! of a record can then be reconstructed from the log, linking operator identifiers to names and
+//! addresses with the `OperatesEvent`s.
+//!
+//! Events are only logged with the `trace-records` feature. Without it, the operators still
+//! transform records, but `trace_point` returns its stream without an operator, and no operator
+//! formats times or consults the logger. Records pay for the identifier they carry either way.
+//!
+//! Timely does not know how the records of one operator relate to those of the next, and so
+//! only trace-aware operators preserve identifiers: `map_traced` gives its output the identifier
+//! of its input, and `flat_map_traced` gives every output of an input record its identifier, so
+//! that a traced record fans out into a traced tree. Other operators, such as `exchange`, move
+//! a `Traced` record like any other and so preserve its identifier, but do not log it; a
+//! `trace_point` after them records that the record passed. General operators that combine
+//! records must choose an identifier for their outputs themselves, for example the least of
+//! those of their inputs.
+//!
+//! # Examples
+//! ```
+//! use timely::dataflow::operators::{ToStream, Exchange, Inspect};
+//! use timely::dataflow::operators::trace::{TraceRecords, TracedStream};
+//!
+//! timely::example(|scope| {
+//!     (0..10u64).to_stream(scope)
+//!               .traced(|x| if *x == 3 { Some(17) } else { None })
+//!               .map_traced(|x| x * 2)
+//!               .exchange(|x| x.data)
+//!               .trace_point()
+//!               .flat_map_traced(|x| vec![x, x + 1])
+//!               .untraced()
+//!               .inspect(|x| println!("seen: {:?}", x));
+//! });
+//! ```
+
+use crate::Data;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Scope, Stream};
+use crate::dataflow::operators::Map;
+use crate::dataflow::operators::generic::operator::Operator;
+use crate::logging::{RecordTraceEvent, TimelyLogger};
+
+/// A record with an optional trace identifier.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd, Abomonation, Serialize, Deserialize)]
+pub struct Traced<D> {
+    /// The trace identifier, if the record is traced.
+    pub trace: Option<u64>,
+    /// The record.
+    pub data: D,
+}
+
+impl<D> Traced<D> {
+    /// Wraps `data` without a trace identifier.
+    pub fn untraced(data: D) -> Self {
+        Traced { trace: None, data }
+    }
+    /// Transforms the record, retaining its trace identifier.
+    pub fn map<D2, F: FnOnce(D)->D2>(self, logic: F) -> Traced<D2> {
+        Traced { trace: self.trace, data: logic(self.data) }
+    }
+}
+
+/// Extension trait for marking records to trace.
+pub trait TraceRecords<G: Scope, D: Data> {
+    /// Wraps each record in a [`Traced`], with the identifier `mark` assigns it, if any.
+    ///
+    /// Marked records are logged as they pass this operator, as for `trace_point`.
+    fn traced<F>(&self, mark: F) -> Stream<G, Traced<D>>
+    where
+        F: FnMut(&D)->Option<u64>+'static;
+}
+
+impl<G: Scope, D: Data> TraceRecords<G, D> for Stream<G, D> {
+    fn traced<F>(&self, mut mark: F) -> Stream<G, Traced<D>>
+    where
+        F: FnMut(&D)->Option<u64>+'static,
+    {
+        self.flat_map(move |data| { let trace = mark(&data); Some(Traced { trace, data }) })
+            .trace_point()
+    }
+}
+
+/// Operators on streams of traced records, which log the traced records they receive.
+pub trait TracedStream<G: Scope, D: Data> {
+    /// Logs the records that carry a trace identifier, and passes all records on unchanged.
+    fn trace_point(&self) -> Stream<G, Traced<D>>;
+    /// Transforms each record with `logic`, retaining its trace identifier.
+    fn map_traced<D2: Data, L: FnMut(D)->D2+'static>(&self, logic: L) -> Stream<G, Traced<D2>>;
+    /// Transforms each record into any number of records with `logic`, each with its trace identifier.
+    fn flat_map_traced<I, L>(&self, logic: L) -> Stream<G, Traced<I::Item>>
+    where
+        I: IntoIterator,
+        I::Item: Data,
+        L: FnMut(D)->I+'static;
+    /// Removes the trace identifiers, after logging the records that carry one.
+    fn untraced(&self) -> Stream<G, D>;
+}
+
+impl<G: Scope, D: Data> TracedStream<G, D> for Stream<G, Traced<D>> {
+    fn trace_point(&self) -> Stream<G, Traced<D>> {
+        if cfg!(feature = "trace-records") {
+            trace_flat_map(self, "TracePoint", Some)
+        }
+        else {
+            self.clone()
+        }
+    }
+    fn map_traced<D2: Data, L: FnMut(D)->D2+'static>(&self, mut logic: L) -> Stream<G, Traced<D2>> {
+        trace_flat_map(self, "MapTraced", move |record| Some(record.map(&mut logic)))
+    }
+    fn flat_map_traced<I, L>(&self, mut logic: L) -> Stream<G, Traced<I::Item>>
+    where
+        I: IntoIterator,
+        I::Item: Data,
+        L: FnMut(D)->I+'static,
+    {
+        trace_flat_map(self, "FlatMapTraced", move |record| {
+            let trace = record.trace;
+            logic(record.data).into_iter().map(move |data| Traced { trace, data })
+        })
+    }
+    fn untraced(&self) -> Stream<G, D> {
+        trace_flat_map(self, "Untraced", |record| Some(record.data))
+    }
+}
+
+/// Transforms each traced record with `logic`, logging those that carry a trace identifier.
+fn trace_flat_map<G, D, I, L>(stream: &Stream<G, Traced<D>>, name: &str, mut logic: L) -> Stream<G, I::Item>
+where
+    G: Scope,
+    D: Data,
+    I: IntoIterator,
+    I::Item: Data,
+    L: FnMut(Traced<D>)->I+'static,
+{
+    let logging: Option<TimelyLogger> = if cfg!(feature = "trace-records") { stream.scope().logging() } else { None };
+    let mut vector = Vec::new();
+    stream.unary(Pipeline, name, move |_, info| move |input, output| {
+        input.for_each(|time, data| {
+            data.swap(&mut vector);
+            let mut session = output.session(&time);
+            for record in vector.drain(..) {
+                if let (Some(trace), Some(logging)) = (record.trace, logging.as_ref()) {
+                    logging.log(RecordTraceEvent {
+                        trace,
+                        operator: info.global_id,
+                        time: format!("{:?}", time.time()),
+                    });
+                }
+                session.give_iterator(logic(record).into_iter());
+            }
+        });
+    })
+}
@@ -0,0 +1,79 @@
+//! Splits a stream of pairs into two streams.
+
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use crate::dataflow::{Scope, Stream};
+use crate::Data;
+
+/// Splits a stream of pairs into two streams.
+pub trait Unzip<S: Scope, D1: Data, D2: Data> {
+    /// Sends the first component of each pair to the first stream, and the second to the second.
+    ///
+    /// Both components are sent at the time of the pair. This is the counterpart of
+    /// [Zip](crate::dataflow::operators::Zip), and visits each record once, where two `map`
+    /// operators would each visit every record.
+    ///
+    /// The operator is only implemented for streams of vectors of pairs, `Stream<S, (D1, D2)>`,
+    /// and not for general containers.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::operators::{ToStream, Unzip, Inspect};
+    ///
+    /// timely::example(|scope| {
+    ///     let (names, values) = vec![("a", 1), ("b", 2)]
+    ///         .into_iter()
+    ///         .to_stream(scope)
+    ///         .unzip();
+    ///
+    ///     names.inspect(|x| println!("name: {:?}", x));
+    ///     values.inspect(|x| println!("value: {:?}", x));
+    /// });
+    /// ```
+    fn unzip(&self) -> (Stream<S, D1>, Stream<S, D2>);
+}
+
+impl<S: Scope, D1: Data, D2: Data> Unzip<S, D1, D2> for Stream<S, (D1, D2)> {
+    fn unzip(&self) -> (Stream<S, D1>, Stream<S, D2>) {
+        let mut builder = OperatorBuilder::new("Unzip".to_owned(), self.scope());
+
+        let mut input = builder.new_input(self, Pipeline);
+        let (mut output1, stream1) = builder.new_output();
+        let (mut output2, stream2) = builder.new_output();
+
+        builder.build(move |_| {
+            let mut vector = Vec::new();
+            move |_frontiers| {
+                let mut output1_handle = output1.activate();
+                let mut output2_handle = output2.activate();
+
+                input.for_each(|time, data| {
+                    data.swap(&mut vector);
+                    let mut out1 = output1_handle.session(&time);
+                    let mut out2 = output2_handle.session(&time);
+                    for (datum1, datum2) in vector.drain(..) {
+                        out1.give(datum1);
+                        out2.give(datum2);
+                    }
+                });
+            }
+        });
+
+        (stream1, stream2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dataflow::operators::{ToStream, Unzip, Capture, capture::Extract};
+
+    #[test]
+    fn test_unzip() {
+        let (names, values) = crate::example(|scope| {
+            let (names, values) = vec![("a", 1), ("b", 2)].into_iter().to_stream(scope).unzip();
+            (names.capture(), values.capture())
+        });
+        assert_eq!(names.extract(), vec![(0, vec!["a", "b"])]);
+        assert_eq!(values.extract(), vec![(0, vec![1, 2])]);
+    }
+}
@@ -0,0 +1,111 @@
+//! Pairs the records of two streams positionally within each timestamp.
+
+use std::collections::HashMap;
+
+use crate::Data;
+use crate::dataflow::channels::pact::Pipeline;
+use crate::dataflow::{Stream, Scope};
+use crate::dataflow::operators::generic::operator::Operator;
+
+/// Pairs the records of two streams positionally within each timestamp.
+pub trait Zip<G: Scope, D1: Data> {
+    /// Pairs the `i`-th record of `self` with the `i`-th record of `other`, at each timestamp.
+    ///
+    /// The records of each input are buffered until their timestamp is complete on both inputs,
+    /// after which the pairs for that timestamp are sent. Both inputs must present the same
+    /// number of records at each timestamp, and the operator panics if they do not.
+    ///
+    /// Pairing is by arrival order at each worker, and so is only meaningful if both inputs
+    /// arrive in a deterministic order. This holds for inputs that are produced on the same
+    /// worker and not exchanged, and generally does not hold for inputs that have been exchanged
+    /// between workers, as records from different workers may interleave arbitrarily.
+    ///
+    /// The operator is only implemented for streams of vectors, `Stream<G, D>`, and not for
+    /// general containers, whose records it would need to own and count to pair them.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use timely::dataflow::operators::{ToStream, Inspect, Zip};
+    ///
+    /// let zipped = Arc::new(Mutex::new(Vec::new()));
+    /// let sink = Arc::clone(&zipped);
+    ///
+    /// timely::example(move |scope| {
+    ///     let names = vec!["a", "b", "c"].into_iter().to_stream(scope);
+    ///     let values = vec![1, 2, 3].into_iter().to_stream(scope);
+    ///     names.zip(&values)
+    ///          .inspect(move |x| sink.lock().unwrap().push(*x));
+    /// });
+    ///
+    /// assert_eq!(*zipped.lock().unwrap(), vec![("a", 1), ("b", 2), ("c", 3)]);
+    /// ```
+    fn zip<D2: Data>(&self, other: &Stream<G, D2>) -> Stream<G, (D1, D2)>;
+}
+
+impl<G: Scope, D1: Data> Zip<G, D1> for Stream<G, D1> {
+    fn zip<D2: Data>(&self, other: &Stream<G, D2>) -> Stream<G, (D1, D2)> {
+
+        // Records of each input, by the timestamp at which they await pairing.
+        let mut stash: HashMap<G::Timestamp, (Vec<D1>, Vec<D2>)> = HashMap::new();
+        let mut vector1 = Vec::new();
+        let mut vector2 = Vec::new();
+
+        self.binary_notify(other, Pipeline, Pipeline, "Zip", vec![], move |input1, input2, output, notificator| {
+
+            input1.for_each(|time, data| {
+                data.swap(&mut vector1);
+                stash.entry(time.time().clone())
+                     .or_insert_with(|| { notificator.notify_at(time.retain()); Default::default() })
+                     .0.append(&mut vector1);
+            });
+
+            input2.for_each(|time, data| {
+                data.swap(&mut vector2);
+                stash.entry(time.time().clone())
+                     .or_insert_with(|| { notificator.notify_at(time.retain()); Default::default() })
+                     .1.append(&mut vector2);
+            });
+
+            notificator.for_each(|time, _, _| {
+                if let Some((left, right)) = stash.remove(time.time()) {
+                    assert_eq!(
+                        left.len(),
+                        right.len(),
+                        "Zip: inputs have different numbers of records at time {:?}",
+                        time.time(),
+                    );
+                    let mut session = output.session(&time);
+                    for pair in left.into_iter().zip(right) {
+                        session.give(pair);
+                    }
+                }
+            });
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dataflow::operators::{ToStream, Zip, Capture, capture::Extract};
+
+    #[test]
+    fn test_zip() {
+        let output = crate::example(|scope| {
+            let names = vec!["a", "b", "c"].into_iter().to_stream(scope);
+            let values = vec![1, 2, 3].into_iter().to_stream(scope);
+            names.zip(&values).capture()
+        });
+        assert_eq!(output.extract(), vec![(0, vec![("a", 1), ("b", 2), ("c", 3)])]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Zip: inputs have different numbers of records")]
+    fn test_zip_different_counts() {
+        crate::example(|scope| {
+            let names = vec!["a", "b", "c"].into_iter().to_stream(scope);
+            let values = vec![1, 2].into_iter().to_stream(scope);
+            names.zip(&values);
+        });
+    }
+}
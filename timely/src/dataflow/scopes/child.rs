@@ -2,6 +2,7 @@
 
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::time::Instant;
 
 use crate::communication::{Data, Push, Pull};
 use crate::communication::allocator::thread::{ThreadPusher, ThreadPuller};
@@ -14,6 +15,7 @@ use crate::order::Product;
 use crate::logging::TimelyLogger as Logger;
 use crate::logging::TimelyProgressLogger as ProgressLogger;
 use crate::worker::{AsWorker, Config};
+use crate::dataflow::operators::probe::Handle as ProbeHandle;
 
 use super::{ScopeParent, Scope};
 
@@ -48,6 +50,41 @@ where
     pub fn index(&self) -> usize { self.parent.index() }
     /// The total number of workers in the computation.
     pub fn peers(&self) -> usize { self.parent.peers() }
+
+    /// Returns a probe handle tracking the progress of the entire scope.
+    ///
+    /// The handle's frontier is the lower bound of all capabilities held and messages in flight
+    /// within the scope, on all workers, and so reflects every operator in the scope whether it
+    /// is added before or after this call. Once the frontier passes a time, no operator in the
+    /// scope will do further work at that time. For a dataflow this is equivalent to probing
+    /// each of its terminal streams at once.
+    ///
+    /// The frontier only reflects work inside the scope: for a nested scope it may advance past
+    /// times at which the enclosing scope can still send it data.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::InputHandle;
+    /// use timely::dataflow::operators::{Input, Inspect, Map};
+    ///
+    /// timely::execute_from_args(std::env::args(), |worker| {
+    ///     let mut input = InputHandle::new();
+    ///     let probe = worker.dataflow(|scope| {
+    ///         let stream = scope.input_from(&mut input);
+    ///         stream.inspect(|x: &u64| println!("seen: {:?}", x));
+    ///         stream.map(|x| x + 1).inspect(|x| println!("next: {:?}", x));
+    ///         scope.scope_probe()
+    ///     });
+    ///     for round in 0..10 {
+    ///         input.send(round);
+    ///         input.advance_to(round + 1);
+    ///         worker.step_while(|| probe.less_than(input.time()));
+    ///     }
+    /// }).unwrap();
+    /// ```
+    pub fn scope_probe(&self) -> ProbeHandle<T> {
+        ProbeHandle::from_frontier(self.subgraph.borrow_mut().scope_frontier())
+    }
 }
 
 impl<'a, G, T> AsWorker for Child<'a, G, T>
@@ -58,6 +95,7 @@ where
     fn config(&self) -> &Config { self.parent.config() }
     fn index(&self) -> usize { self.parent.index() }
     fn peers(&self) -> usize { self.parent.peers() }
+    fn timer(&self) -> Instant { self.parent.timer() }
     fn allocate<D: Data>(&mut self, identifier: usize, address: &[usize]) -> (Vec<Box<dyn Push<Message<D>>>>, Box<dyn Pull<Message<D>>>) {
         self.parent.allocate(identifier, address)
     }
@@ -70,6 +108,9 @@ where
     fn log_register(&self) -> ::std::cell::RefMut<crate::logging_core::Registry<crate::logging::WorkerIdentifier>> {
         self.parent.log_register()
     }
+    fn resources(&self) -> ::std::cell::RefMut<crate::worker::Resources> {
+        self.parent.resources()
+    }
 }
 
 impl<'a, G, T> Scheduler for Child<'a, G, T>
@@ -101,6 +142,10 @@ where
         self.subgraph.borrow_mut().connect(source, target);
     }
 
+    fn add_channel(&self, identifier: usize, target: Target) {
+        self.subgraph.borrow_mut().add_channel(identifier, target);
+    }
+
     fn add_operator_with_indices(&mut self, operator: Box<dyn Operate<Self::Timestamp>>, local: usize, global: usize) {
         self.subgraph.borrow_mut().add_child(operator, local, global);
     }
@@ -37,6 +37,11 @@ pub trait Scope: ScopeParent {
     /// the purposes of tracking progress, rather than effect any data movement itself.
     fn add_edge(&self, source: Source, target: Target);
 
+    /// Records that the edge arriving at `target` is carried by channel `identifier`.
+    ///
+    /// This is descriptive only, and is used to report the dataflow's topology.
+    fn add_channel(&self, _identifier: usize, _target: Target) { }
+
     /// Adds a child `Operate` to the builder's scope. Returns the new child's index.
     fn add_operator(&mut self, operator: Box<dyn Operate<Self::Timestamp>>) -> usize {
         let index = self.allocate_operator_index();
@@ -48,6 +48,7 @@ impl<S: Scope, C: Container> StreamCore<S, C> {
         }));
 
         self.scope.add_edge(self.name, target);
+        self.scope.add_channel(identifier, target);
         self.ports.add_pusher(pusher);
     }
     /// Allocates a `Stream` from a supplied `Source` name and rendezvous point.
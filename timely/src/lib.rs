@@ -108,6 +108,9 @@ pub mod logging;
 // pub mod log_events;
 
 pub mod scheduling;
+pub mod testing;
+pub mod checkpoint;
+pub mod memory;
 
 /// A composite trait for types usable as data in timely dataflow.
 ///
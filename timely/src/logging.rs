@@ -9,6 +9,15 @@ pub type TimelyLogger = Logger<TimelyEvent>;
 /// Logger for timely dataflow progress events (the "timely/progress" log stream).
 pub type TimelyProgressLogger = Logger<TimelyProgressEvent>;
 
+#[cfg(feature = "json")]
+pub mod json;
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use std::time::Duration;
 use crate::dataflow::operators::capture::{Event, EventPusher};
 
@@ -48,6 +57,264 @@ impl<T, E, P> Drop for BatchLogger<T, E, P> where P: EventPusher<Duration, Vec<(
     }
 }
 
+/// Aggregates timely events into counts and histograms, rather than retaining the events.
+///
+/// The logger is a cheaply cloneable handle to shared aggregates: one clone is moved into the
+/// registered logging closure, and another retained to read the aggregates with `snapshot`.
+///
+/// # Examples
+/// ```
+/// use timely::logging::{HistogramLogger, TimelyEvent};
+///
+/// let snapshot = timely::execute_directly(|worker| {
+///     let histograms = HistogramLogger::new();
+///     let mut logger = histograms.clone();
+///     worker.log_register().insert::<TimelyEvent,_>("timely", move |time, data| {
+///         logger.publish_batch(time, data);
+///     });
+///
+///     worker.dataflow::<u64,_,_>(|scope| {
+///         use timely::dataflow::operators::{ToStream, Inspect};
+///         (0 .. 10).to_stream(scope).inspect(|_| ());
+///     });
+///     while worker.step() { }
+///
+///     histograms.snapshot()
+/// });
+///
+/// assert!(snapshot.events["Schedule"] > 0);
+/// assert!(snapshot.schedule_nanos.values().map(|h| h.count()).sum::<u64>() > 0);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct HistogramLogger {
+    aggregates: Rc<RefCell<LogAggregates>>,
+}
+
+impl HistogramLogger {
+    /// Creates a logger with empty aggregates.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Folds a batch of logged events into the aggregates, and discards the events.
+    pub fn publish_batch(&mut self, _time: &Duration, data: &mut Vec<(Duration, WorkerIdentifier, TimelyEvent)>) {
+        let mut aggregates = self.aggregates.borrow_mut();
+        for (_time, _worker, event) in data.drain(..) {
+            *aggregates.events.entry(event.name()).or_insert(0) += 1;
+            match event {
+                TimelyEvent::ScheduleElapsed(event) => {
+                    aggregates.schedule_nanos
+                        .entry(event.id)
+                        .or_default()
+                        .record(event.elapsed.as_nanos() as u64);
+                },
+                TimelyEvent::Messages(event) if event.is_send => {
+                    aggregates.message_lengths.record(event.length as u64);
+                },
+                TimelyEvent::ContainerStats(event) => {
+                    aggregates.container_lengths
+                        .entry(event.id)
+                        .or_default()
+                        .record(event.length as u64);
+                    aggregates.container_bytes
+                        .entry(event.id)
+                        .or_default()
+                        .record(event.bytes as u64);
+                },
+                _ => { },
+            }
+        }
+    }
+
+    /// A copy of the aggregates accumulated so far.
+    pub fn snapshot(&self) -> LogAggregates {
+        self.aggregates.borrow().clone()
+    }
+}
+
+/// Aggregates of logged timely events, as accumulated by a `HistogramLogger`.
+#[derive(Clone, Debug, Default)]
+pub struct LogAggregates {
+    /// The number of events of each type, keyed by `TimelyEvent` variant name.
+    pub events: BTreeMap<&'static str, u64>,
+    /// Nanoseconds spent per operator schedule, keyed by operator identifier.
+    pub schedule_nanos: BTreeMap<usize, Histogram>,
+    /// Records per sent message.
+    pub message_lengths: Histogram,
+    /// Records per container observed by a `container_stats` operator, keyed by operator identifier.
+    pub container_lengths: BTreeMap<usize, Histogram>,
+    /// Estimated bytes per container observed by a `container_stats` operator, keyed by operator identifier.
+    pub container_bytes: BTreeMap<usize, Histogram>,
+}
+
+/// A histogram of non-negative integers, with a bucket for each power of two.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Histogram {
+    // Bucket `i` counts values `v` with `2^(i-1) <= v < 2^i`, and bucket zero counts zeros.
+    buckets: Vec<u64>,
+    count: u64,
+    sum: u128,
+}
+
+impl Histogram {
+    /// Records one occurrence of `value`.
+    pub fn record(&mut self, value: u64) {
+        let bucket = (64 - value.leading_zeros()) as usize;
+        if self.buckets.len() <= bucket {
+            self.buckets.resize(bucket + 1, 0);
+        }
+        self.buckets[bucket] += 1;
+        self.count += 1;
+        self.sum += value as u128;
+    }
+    /// The number of recorded values.
+    pub fn count(&self) -> u64 { self.count }
+    /// The sum of recorded values.
+    pub fn sum(&self) -> u128 { self.sum }
+    /// Non-empty buckets, as pairs of an exclusive upper bound and the number of values below it
+    /// and at least half of it.
+    ///
+    /// The bucket of zeros has the upper bound one.
+    pub fn buckets(&self) -> impl Iterator<Item=(u128, u64)> + '_ {
+        self.buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .map(|(index, count)| (1u128 << index, *count))
+    }
+}
+
+/// Logs events to a per-worker file, with optional size-based rotation.
+///
+/// Events are formatted into an in-memory buffer by a user-supplied closure, and the buffer is
+/// written out once it grows past a threshold, and when the logger is dropped. With rotation
+/// enabled, a file that would grow past `max_bytes` is renamed to `<path>.1`, with older files
+/// shifted to `<path>.2` and onwards, and only `keep` rotated files are retained. A single flush
+/// is never split across files, so a file may exceed `max_bytes` by up to one flush.
+///
+/// # Examples
+/// ```
+/// use std::io::Write;
+/// use timely::logging::{FileLogger, TimelyEvent};
+///
+/// timely::execute_directly(|worker| {
+///     let directory = std::env::temp_dir();
+///     let mut logger = FileLogger::new(&directory, "timely", worker.index(), |buffer, event: &(_, _, TimelyEvent)| {
+///         writeln!(buffer, "{:?}", event).unwrap();
+///     })
+///     .expect("failed to create log file")
+///     .with_rotation(1 << 20, 3);
+///
+///     worker.log_register().insert::<TimelyEvent,_>("timely", move |time, data| {
+///         logger.publish_batch(time, data).expect("failed to write log file");
+///     });
+/// });
+/// ```
+pub struct FileLogger<T, E, F> where F: FnMut(&mut Vec<u8>, &(Duration, E, T)) {
+    path: PathBuf,
+    // None only while the file is being rotated.
+    file: Option<File>,
+    format: F,
+    buffer: Vec<u8>,
+    // Bytes written to the current file.
+    written: usize,
+    // Maximum file size and number of rotated files to retain, if rotating.
+    rotation: Option<(usize, usize)>,
+    _phantom: ::std::marker::PhantomData<(E, T)>,
+}
+
+impl<T, E, F> FileLogger<T, E, F> where F: FnMut(&mut Vec<u8>, &(Duration, E, T)) {
+
+    /// The number of buffered bytes at which the buffer is written to the file.
+    const FLUSH_BYTES: usize = 1 << 16;
+
+    /// Creates a logger writing to `<directory>/<name>.<worker>.log`, replacing any existing file.
+    pub fn new<P: AsRef<Path>>(directory: P, name: &str, worker: usize, format: F) -> std::io::Result<Self> {
+        let path = directory.as_ref().join(format!("{}.{}.log", name, worker));
+        let file = File::create(&path)?;
+        Ok(FileLogger {
+            path,
+            file: Some(file),
+            format,
+            buffer: Vec::new(),
+            written: 0,
+            rotation: None,
+            _phantom: ::std::marker::PhantomData,
+        })
+    }
+
+    /// Rotates the file once it would grow past `max_bytes`, retaining `keep` rotated files.
+    pub fn with_rotation(mut self, max_bytes: usize, keep: usize) -> Self {
+        self.rotation = Some((max_bytes, keep));
+        self
+    }
+
+    /// The path of the file currently being written.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Formats a batch of logged events, and writes them out if enough have accumulated.
+    pub fn publish_batch(&mut self, _time: &Duration, data: &mut Vec<(Duration, E, T)>) -> std::io::Result<()> {
+        for event in data.drain(..) {
+            (self.format)(&mut self.buffer, &event);
+        }
+        if self.buffer.len() >= Self::FLUSH_BYTES {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Writes all buffered events to the file.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        if let Some((max_bytes, keep)) = self.rotation {
+            if self.written > 0 && self.written + self.buffer.len() > max_bytes {
+                self.rotate(keep)?;
+            }
+        }
+        let file = self.file.as_mut().expect("log file missing after failed rotation");
+        file.write_all(&self.buffer)?;
+        file.flush()?;
+        self.written += self.buffer.len();
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Shifts existing files to the next rotated path, and starts a new file.
+    fn rotate(&mut self, keep: usize) -> std::io::Result<()> {
+        // Close the current file before renaming it.
+        self.file = None;
+        if keep > 0 {
+            for index in (1 .. keep).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    std::fs::rename(from, self.rotated_path(index + 1))?;
+                }
+            }
+            std::fs::rename(&self.path, self.rotated_path(1))?;
+        }
+        self.file = Some(File::create(&self.path)?);
+        self.written = 0;
+        Ok(())
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}", index));
+        PathBuf::from(path)
+    }
+}
+
+impl<T, E, F> Drop for FileLogger<T, E, F> where F: FnMut(&mut Vec<u8>, &(Duration, E, T)) {
+    fn drop(&mut self) {
+        // Errors cannot be reported from here; the tail of the log is written on a best-effort basis.
+        let _ = self.flush();
+    }
+}
+
 #[derive(Serialize, Deserialize, Abomonation, Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 /// The creation of an `Operate` implementor.
 pub struct OperatesEvent {
@@ -203,6 +470,59 @@ pub struct ShutdownEvent {
     pub id: usize,
 }
 
+#[derive(Serialize, Deserialize, Abomonation, Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+/// Time spent in one scheduling of an operator.
+pub struct ScheduleElapsedEvent {
+    /// Worker-unique identifier for the operator, linkable to the identifiers in `OperatesEvent`.
+    pub id: usize,
+    /// Wall-clock time between the operator's `Start` and `Stop` schedule events.
+    pub elapsed: Duration,
+}
+
+#[derive(Serialize, Deserialize, Abomonation, Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+/// A container observed by a `container_stats` operator.
+pub struct ContainerStatsEvent {
+    /// Worker-unique identifier for the operator, linkable to the identifiers in `OperatesEvent`.
+    pub id: usize,
+    /// Number of records in the container.
+    pub length: usize,
+    /// Estimated bytes occupied by the records, as reported by `ContainerBytes::length_in_bytes`.
+    pub bytes: usize,
+}
+
+#[derive(Serialize, Deserialize, Abomonation, Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+/// A traced record passed a tracing operator.
+pub struct RecordTraceEvent {
+    /// The trace identifier carried by the record.
+    pub trace: u64,
+    /// Worker-unique identifier for the operator, linkable to the identifiers in `OperatesEvent`.
+    pub operator: usize,
+    /// The record's timestamp, as formatted by `Debug`.
+    pub time: String,
+}
+
+#[derive(Serialize, Deserialize, Abomonation, Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+/// An operator holds capabilities after its inputs have completed.
+pub struct HeldCapabilitiesEvent {
+    /// Worker-unique identifier for the operator, linkable to the identifiers in `OperatesEvent`.
+    pub id: usize,
+    /// The times at which capabilities are held for each output, as formatted by `Debug`.
+    pub held: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Abomonation, Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+/// An operator's buffered state crossed its configured memory limit.
+pub struct OperatorMemoryEvent {
+    /// Worker-unique identifier for the operator, linkable to the identifiers in `OperatesEvent`.
+    pub id: usize,
+    /// Estimated bytes buffered by the operator, as last reported.
+    pub bytes: usize,
+    /// The configured limit, in bytes.
+    pub limit: usize,
+    /// True if the operator exceeds the limit, false if it has returned within it.
+    pub exceeded: bool,
+}
+
 #[derive(Serialize, Deserialize, Abomonation, Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 /// Application-defined code start or stop
 pub struct ApplicationEvent {
@@ -276,6 +596,10 @@ impl ParkEvent {
 
 #[derive(Serialize, Deserialize, Debug, Clone, Abomonation, Hash, Eq, PartialEq, Ord, PartialOrd)]
 /// An event in a timely worker
+///
+/// The enum is non-exhaustive, as new kinds of events are added over time: consumers that match
+/// on it must include a wildcard arm.
+#[non_exhaustive]
 pub enum TimelyEvent {
     /// Operator creation.
     Operates(OperatesEvent),
@@ -303,6 +627,42 @@ pub enum TimelyEvent {
     Park(ParkEvent),
     /// Unstructured event.
     Text(String),
+    /// Operator scheduling duration.
+    ScheduleElapsed(ScheduleElapsedEvent),
+    /// Container observed by a `container_stats` operator.
+    ContainerStats(ContainerStatsEvent),
+    /// Operator memory crossing its configured limit.
+    OperatorMemory(OperatorMemoryEvent),
+    /// Traced record passing a tracing operator.
+    RecordTrace(RecordTraceEvent),
+    /// Capabilities held by an operator after its inputs completed.
+    HeldCapabilities(HeldCapabilitiesEvent),
+}
+
+impl TimelyEvent {
+    /// The name of the event's variant.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimelyEvent::Operates(_) => "Operates",
+            TimelyEvent::Channels(_) => "Channels",
+            TimelyEvent::PushProgress(_) => "PushProgress",
+            TimelyEvent::Messages(_) => "Messages",
+            TimelyEvent::Schedule(_) => "Schedule",
+            TimelyEvent::Shutdown(_) => "Shutdown",
+            TimelyEvent::Application(_) => "Application",
+            TimelyEvent::GuardedMessage(_) => "GuardedMessage",
+            TimelyEvent::GuardedProgress(_) => "GuardedProgress",
+            TimelyEvent::CommChannels(_) => "CommChannels",
+            TimelyEvent::Input(_) => "Input",
+            TimelyEvent::Park(_) => "Park",
+            TimelyEvent::Text(_) => "Text",
+            TimelyEvent::ScheduleElapsed(_) => "ScheduleElapsed",
+            TimelyEvent::ContainerStats(_) => "ContainerStats",
+            TimelyEvent::OperatorMemory(_) => "OperatorMemory",
+            TimelyEvent::RecordTrace(_) => "RecordTrace",
+            TimelyEvent::HeldCapabilities(_) => "HeldCapabilities",
+        }
+    }
 }
 
 impl From<OperatesEvent> for TimelyEvent {
@@ -329,6 +689,26 @@ impl From<ShutdownEvent> for TimelyEvent {
     fn from(v: ShutdownEvent) -> TimelyEvent { TimelyEvent::Shutdown(v) }
 }
 
+impl From<ScheduleElapsedEvent> for TimelyEvent {
+    fn from(v: ScheduleElapsedEvent) -> TimelyEvent { TimelyEvent::ScheduleElapsed(v) }
+}
+
+impl From<ContainerStatsEvent> for TimelyEvent {
+    fn from(v: ContainerStatsEvent) -> TimelyEvent { TimelyEvent::ContainerStats(v) }
+}
+
+impl From<OperatorMemoryEvent> for TimelyEvent {
+    fn from(v: OperatorMemoryEvent) -> TimelyEvent { TimelyEvent::OperatorMemory(v) }
+}
+
+impl From<RecordTraceEvent> for TimelyEvent {
+    fn from(v: RecordTraceEvent) -> TimelyEvent { TimelyEvent::RecordTrace(v) }
+}
+
+impl From<HeldCapabilitiesEvent> for TimelyEvent {
+    fn from(v: HeldCapabilitiesEvent) -> TimelyEvent { TimelyEvent::HeldCapabilities(v) }
+}
+
 impl From<ApplicationEvent> for TimelyEvent {
     fn from(v: ApplicationEvent) -> TimelyEvent { TimelyEvent::Application(v) }
 }
@@ -0,0 +1,83 @@
+//! Newline-delimited JSON formatting for timely events.
+//!
+//! Each event is written as a single JSON object on its own line. The object has a `type` field
+//! naming the `TimelyEvent` variant, `time_nanos` and `worker` fields from the log batch, and the
+//! fields of the event itself. Events without named fields, such as `Text`, put their payload in
+//! a `value` field instead.
+//!
+//! # Examples
+//! ```
+//! use timely::logging::TimelyEvent;
+//!
+//! timely::execute_directly(|worker| {
+//!     worker.log_register().insert::<TimelyEvent,_>("timely", |_time, data| {
+//!         let stdout = std::io::stdout();
+//!         timely::logging::json::write_batch(&mut stdout.lock(), data).expect("failed to write log");
+//!     });
+//! });
+//! ```
+
+use std::io::Write;
+use std::time::Duration;
+
+use serde_json::{Map, Value};
+
+use super::{TimelyEvent, WorkerIdentifier};
+
+/// Writes each event in `batch` to `writer` as a line of JSON.
+pub fn write_batch<W: Write>(writer: &mut W, batch: &[(Duration, WorkerIdentifier, TimelyEvent)]) -> std::io::Result<()> {
+    for (time, worker, event) in batch.iter() {
+        let value = to_value(time, *worker, event)?;
+        serde_json::to_writer(&mut *writer, &value)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Converts a logged event to the JSON object written by `write_batch`.
+pub fn to_value(time: &Duration, worker: WorkerIdentifier, event: &TimelyEvent) -> serde_json::Result<Value> {
+    let mut object = Map::new();
+    object.insert("time_nanos".to_owned(), Value::from(time.as_nanos() as u64));
+    object.insert("worker".to_owned(), Value::from(worker));
+
+    // Serde represents the variant as an object with a single entry, mapping its name to its payload.
+    if let Value::Object(variant) = serde_json::to_value(event)? {
+        for (name, payload) in variant {
+            object.insert("type".to_owned(), Value::String(name));
+            match payload {
+                Value::Object(fields) => object.extend(fields),
+                payload => { object.insert("value".to_owned(), payload); },
+            }
+        }
+    }
+
+    Ok(Value::Object(object))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::logging::{ScheduleEvent, TimelyEvent};
+
+    #[test]
+    fn schedule_event() {
+        let batch = vec![
+            (Duration::from_nanos(5), 1, TimelyEvent::from(ScheduleEvent::start(3))),
+            (Duration::from_nanos(7), 1, TimelyEvent::Text("hello".to_owned())),
+        ];
+        let mut bytes = Vec::new();
+        super::write_batch(&mut bytes, &batch).unwrap();
+        let lines = String::from_utf8(bytes).unwrap();
+        let values = lines.lines().map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()).collect::<Vec<_>>();
+
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0]["type"], "Schedule");
+        assert_eq!(values[0]["time_nanos"], 5);
+        assert_eq!(values[0]["worker"], 1);
+        assert_eq!(values[0]["id"], 3);
+        assert_eq!(values[0]["start_stop"], "Start");
+        assert_eq!(values[1]["type"], "Text");
+        assert_eq!(values[1]["value"], "hello");
+    }
+}
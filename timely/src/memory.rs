@@ -0,0 +1,157 @@
+//! Accounting of the state buffered by operators.
+//!
+//! An operator that buffers records or per-time state opens a `MemoryAccount` when it is
+//! built, and reports its estimated buffered bytes to the account each time it is scheduled.
+//! The worker keeps the latest report of each operator, available from `Worker::operator_memory`,
+//! and applies the limit and policy configured with `worker::Config::operator_memory_limit`.
+//! Without a configured limit no account is opened, and operators estimate nothing.
+//!
+//! Reports are estimates made by the operators, typically of the bytes directly occupied by
+//! their records, and do not include the heap allocations that records own.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use crate::dataflow::operators::generic::OperatorInfo;
+use crate::logging::{OperatorMemoryEvent, TimelyLogger};
+use crate::worker::{AsWorker, MemoryPolicy};
+
+/// The latest report of an operator's buffered state.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct OperatorMemory {
+    /// Worker-unique identifier for the operator.
+    pub id: usize,
+    /// The name of the operator.
+    pub name: String,
+    /// Estimated bytes buffered by the operator.
+    pub bytes: usize,
+}
+
+/// The latest reports of the operators of a worker, by operator identifier.
+#[derive(Debug, Default)]
+pub struct Usage {
+    operators: BTreeMap<usize, OperatorMemory>,
+}
+
+impl Usage {
+    /// The latest report of each operator with an account, in order of operator identifier.
+    pub fn operators(&self) -> Vec<OperatorMemory> {
+        self.operators.values().cloned().collect()
+    }
+}
+
+/// Returns the memory usage of the operators of the worker, creating it if necessary.
+pub fn usage<A: AsWorker>(worker: &A) -> Rc<RefCell<Usage>> {
+    let mut resources = worker.resources();
+    if let Some(usage) = resources.get::<Usage>() {
+        usage
+    }
+    else {
+        resources.register(Usage::default);
+        resources.get::<Usage>().expect("memory usage just registered")
+    }
+}
+
+/// An operator's account of its buffered state.
+///
+/// The account is removed from the worker's usage when it is dropped, which happens when
+/// the operator that holds it shuts down.
+pub struct MemoryAccount {
+    id: usize,
+    exceeded: bool,
+    limit: (usize, MemoryPolicy),
+    usage: Rc<RefCell<Usage>>,
+    logging: Option<TimelyLogger>,
+}
+
+impl MemoryAccount {
+    /// Opens an account for the operator described by `info`, named `name`, if the worker
+    /// is configured with a memory limit.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::dataflow::Scope;
+    /// use timely::dataflow::channels::pact::Pipeline;
+    /// use timely::dataflow::operators::ToStream;
+    /// use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+    /// use timely::memory::MemoryAccount;
+    ///
+    /// timely::example(|scope| {
+    ///     let stream = (0..10u64).to_stream(scope);
+    ///
+    ///     let mut builder = OperatorBuilder::new("Collect".to_owned(), stream.scope());
+    ///     let mut input = builder.new_input(&stream, Pipeline);
+    ///     let mut account = MemoryAccount::new(&stream.scope(), &builder.operator_info(), "Collect");
+    ///
+    ///     builder.build(move |_capabilities| {
+    ///         let mut stash = Vec::new();
+    ///         move |_frontiers| {
+    ///             input.for_each(|_time, data| stash.extend(data.iter().cloned()));
+    ///             if let Some(account) = account.as_mut() {
+    ///                 account.report(stash.len() * std::mem::size_of::<u64>());
+    ///             }
+    ///         }
+    ///     });
+    /// });
+    /// ```
+    pub fn new<A: AsWorker>(worker: &A, info: &OperatorInfo, name: &str) -> Option<Self> {
+        let limit = worker.config().memory_limit?;
+        let usage = usage(worker);
+        usage.borrow_mut().operators.insert(info.global_id, OperatorMemory {
+            id: info.global_id,
+            name: name.to_owned(),
+            bytes: 0,
+        });
+        Some(MemoryAccount {
+            id: info.global_id,
+            exceeded: false,
+            limit,
+            usage,
+            logging: worker.logging(),
+        })
+    }
+
+    /// Records that the operator buffers an estimated `bytes`, and applies the configured limit.
+    ///
+    /// Returns true if the operator should release buffered state, which is the case if it
+    /// exceeds the limit under `MemoryPolicy::Flush`. Crossings of the limit, in either
+    /// direction, are logged. Under `MemoryPolicy::Panic`, this method panics if the operator
+    /// exceeds the limit.
+    pub fn report(&mut self, bytes: usize) -> bool {
+        let mut usage = self.usage.borrow_mut();
+        let operator = usage.operators.get_mut(&self.id).expect("memory account without usage");
+        operator.bytes = bytes;
+
+        let (limit, policy) = self.limit;
+        let exceeded = bytes > limit;
+        if exceeded != self.exceeded {
+            self.exceeded = exceeded;
+            if let Some(logging) = self.logging.as_ref() {
+                logging.log(OperatorMemoryEvent { id: self.id, bytes, limit, exceeded });
+            }
+            if exceeded && policy == MemoryPolicy::Panic {
+                panic!("operator {} (id {}) buffers {} bytes, exceeding the limit of {} bytes", operator.name, self.id, bytes, limit);
+            }
+        }
+        exceeded && policy == MemoryPolicy::Flush
+    }
+}
+
+impl Drop for MemoryAccount {
+    fn drop(&mut self) {
+        if let Ok(mut usage) = self.usage.try_borrow_mut() {
+            usage.operators.remove(&self.id);
+        }
+    }
+}
+
+impl ::std::fmt::Debug for MemoryAccount {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        f.debug_struct("MemoryAccount")
+            .field("id", &self.id)
+            .field("exceeded", &self.exceeded)
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
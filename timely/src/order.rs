@@ -25,6 +25,17 @@ pub trait PartialOrder<Rhs: ?Sized = Self>: PartialEq<Rhs> {
 /// and other sanity-maintaining operations.
 pub trait TotalOrder : PartialOrder { }
 
+/// A partially ordered type in which every pair of elements has a least upper and a greatest lower bound.
+///
+/// The bounds must be consistent with `PartialOrder`: `x.join(&y)` is the least element greater or
+/// equal to both `x` and `y`, and `x.meet(&y)` the greatest element less or equal to both.
+pub trait Lattice : PartialOrder {
+    /// The least upper bound of `self` and `other`.
+    fn join(&self, other: &Self) -> Self;
+    /// The greatest lower bound of `self` and `other`.
+    fn meet(&self, other: &Self) -> Self;
+}
+
 /// A type that does not affect total orderedness.
 ///
 /// This trait is not useful, but must be made public and documented or else Rust
@@ -53,16 +64,29 @@ macro_rules! implement_total {
     )
 }
 
+macro_rules! implement_lattice {
+    ($($index_type:ty,)*) => (
+        $(
+            impl Lattice for $index_type {
+                #[inline] fn join(&self, other: &Self) -> Self { ::std::cmp::max(*self, *other) }
+                #[inline] fn meet(&self, other: &Self) -> Self { ::std::cmp::min(*self, *other) }
+            }
+        )*
+    )
+}
+
 implement_partial!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, (), ::std::time::Duration,);
 implement_total!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, (), ::std::time::Duration,);
+implement_lattice!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, (), ::std::time::Duration,);
 
 pub use product::Product;
 /// A pair of timestamps, partially ordered by the product order.
 mod product {
-    use std::fmt::{Formatter, Error, Debug};
+    use std::fmt::{Formatter, Error, Debug, Display};
+    use std::ops::Range;
 
     use crate::container::columnation::{Columnation, Region};
-    use crate::order::{Empty, TotalOrder};
+    use crate::order::{Empty, Lattice, TotalOrder};
     use crate::progress::Timestamp;
     use crate::progress::timestamp::PathSummary;
     use crate::progress::timestamp::Refines;
@@ -87,6 +111,40 @@ mod product {
                 inner,
             }
         }
+
+        /// Iterates over the products with the same outer coordinate, and successive inner
+        /// coordinates from that of `self` up to but excluding `bound`.
+        ///
+        /// # Examples
+        /// ```
+        /// use timely::order::Product;
+        ///
+        /// let times = Product::new(3u64, 0u32).inner_until(3).collect::<Vec<_>>();
+        /// assert_eq!(times, vec![Product::new(3, 0), Product::new(3, 1), Product::new(3, 2)]);
+        /// ```
+        pub fn inner_until(&self, bound: TInner) -> impl Iterator<Item=Product<TOuter, TInner>>
+        where
+            TOuter: Clone,
+            TInner: Clone,
+            Range<TInner>: Iterator<Item=TInner>,
+        {
+            let outer = self.outer.clone();
+            (self.inner.clone() .. bound).map(move |inner| Product::new(outer.clone(), inner))
+        }
+    }
+
+    /// Renders the product as `(outer, inner)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use timely::order::Product;
+    ///
+    /// assert_eq!(Product::new(3u64, 1u32).to_string(), "(3, 1)");
+    /// ```
+    impl<TOuter: Display, TInner: Display> Display for Product<TOuter, TInner> {
+        fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
+            write!(f, "({}, {})", self.outer, self.inner)
+        }
     }
 
     // Debug implementation to avoid seeing fully qualified path names.
@@ -109,6 +167,17 @@ mod product {
         }
     }
 
+    impl<TOuter: Lattice, TInner: Lattice> Lattice for Product<TOuter, TInner> {
+        #[inline]
+        fn join(&self, other: &Self) -> Self {
+            Product::new(self.outer.join(&other.outer), self.inner.join(&other.inner))
+        }
+        #[inline]
+        fn meet(&self, other: &Self) -> Self {
+            Product::new(self.outer.meet(&other.outer), self.inner.meet(&other.inner))
+        }
+    }
+
     impl<TOuter: Timestamp, TInner: Timestamp> Timestamp for Product<TOuter, TInner> {
         type Summary = Product<TOuter::Summary, TInner::Summary>;
         fn minimum() -> Self { Self { outer: TOuter::minimum(), inner: TInner::minimum() }}
@@ -1,7 +1,7 @@
 //! Tracks minimal sets of mutually incomparable elements of a partial order.
 
 use crate::progress::ChangeBatch;
-use crate::order::{PartialOrder, TotalOrder};
+use crate::order::{Lattice, PartialOrder, TotalOrder};
 
 /// A set of mutually incomparable elements.
 ///
@@ -168,6 +168,65 @@ impl<T: PartialOrder> Antichain<T> {
     }
 }
 
+impl<T: PartialOrder+Clone> Antichain<T> {
+    /// The greatest lower bound of two antichains.
+    ///
+    /// The result is the minimal antichain of the elements of both `self` and `other`, and is
+    /// less or equal to each of them.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::order::Product;
+    /// use timely::progress::frontier::Antichain;
+    ///
+    /// let frontier1 = Antichain::from(vec![Product::new(0, 2), Product::new(2, 0)]);
+    /// let frontier2 = Antichain::from_elem(Product::new(1, 1));
+    /// let expected = Antichain::from(vec![Product::new(0, 2), Product::new(1, 1), Product::new(2, 0)]);
+    /// assert_eq!(frontier1.meet(&frontier2), expected);
+    ///```
+    pub fn meet(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for element in other.elements() {
+            result.insert_ref(element);
+        }
+        result
+    }
+}
+
+impl<T: Lattice> Antichain<T> {
+    /// The least upper bound of two antichains.
+    ///
+    /// The result is the minimal antichain of the joins of pairs of elements from `self` and `other`,
+    /// and is greater or equal to each of them.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::order::Product;
+    /// use timely::progress::frontier::Antichain;
+    ///
+    /// let frontier1 = Antichain::from(vec![Product::new(0, 2), Product::new(2, 0)]);
+    /// let frontier2 = Antichain::from_elem(Product::new(1, 1));
+    /// let expected = Antichain::from(vec![Product::new(1, 2), Product::new(2, 1)]);
+    /// assert_eq!(frontier1.join(&frontier2), expected);
+    ///```
+    pub fn join(&self, other: &Self) -> Self {
+        join_elements(self.elements(), other.elements())
+    }
+}
+
+/// The minimal antichain of the joins of pairs of elements from `elements1` and `elements2`.
+fn join_elements<T: Lattice>(elements1: &[T], elements2: &[T]) -> Antichain<T> {
+    let mut result = Antichain::new();
+    for element1 in elements1 {
+        for element2 in elements2 {
+            result.insert(element1.join(element2));
+        }
+    }
+    result
+}
+
 impl<T: PartialOrder> std::iter::FromIterator<T> for Antichain<T> {
     fn from_iter<I>(iterator: I) -> Self
     where
@@ -426,6 +485,89 @@ impl<T> MutableAntichain<T> {
         AntichainRef::new(&self.frontier)
     }
 
+    /// The least upper bound of the current frontier and `other`.
+    ///
+    /// This is `Antichain::join` applied to the frontier, without first copying it out.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::progress::frontier::{Antichain, MutableAntichain};
+    ///
+    /// let frontier = MutableAntichain::new_bottom(3u64);
+    /// let other = Antichain::from_elem(5u64);
+    /// assert_eq!(frontier.frontier_join(other.borrow()), Antichain::from_elem(5u64));
+    ///```
+    pub fn frontier_join(&self, other: AntichainRef<'_, T>) -> Antichain<T> where T: Lattice {
+        join_elements(&self.frontier, &other)
+    }
+
+    /// The greatest lower bound of the current frontiers of `self` and `other`.
+    ///
+    /// This is the frontier of the union of the two antichains' updates, and is the frontier
+    /// of an operator whose inputs have these frontiers.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::order::Product;
+    /// use timely::progress::frontier::{Antichain, MutableAntichain};
+    ///
+    /// let frontier1 = MutableAntichain::new_bottom(Product::new(0, 2));
+    /// let frontier2 = MutableAntichain::new_bottom(Product::new(1, 1));
+    /// let expected = Antichain::from(vec![Product::new(0, 2), Product::new(1, 1)]);
+    /// assert_eq!(frontier1.combine(&frontier2), expected);
+    ///```
+    pub fn combine(&self, other: &Self) -> Antichain<T> where T: PartialOrder+Clone {
+        let mut result = self.frontier().to_owned();
+        for element in other.frontier.iter() {
+            result.insert_ref(element);
+        }
+        result
+    }
+
+    /// Updates `combined` to the combined frontier of `self` and `other`, and enumerates the
+    /// changes from its previous value.
+    ///
+    /// Elements leaving the combined frontier are reported with `-1` and elements joining it with
+    /// `+1`, as `update_iter` reports changes to a single frontier. Starting from an empty
+    /// `combined`, and calling this method after each update to either input, tracks the
+    /// combined frontier along with the changes to it.
+    ///
+    /// # Examples
+    ///
+    ///```
+    /// use timely::progress::frontier::{Antichain, MutableAntichain};
+    ///
+    /// let mut frontier1 = MutableAntichain::new_bottom(1u64);
+    /// let frontier2 = MutableAntichain::new_bottom(3u64);
+    /// let mut combined = Antichain::new();
+    /// assert_eq!(frontier1.combine_changes(&frontier2, &mut combined).collect::<Vec<_>>(), vec![(1, 1)]);
+    ///
+    /// frontier1.update_iter(vec![(1, -1), (5, 1)]);
+    /// assert_eq!(frontier1.combine_changes(&frontier2, &mut combined).collect::<Vec<_>>(), vec![(1, -1), (3, 1)]);
+    /// assert_eq!(combined, Antichain::from_elem(3));
+    ///```
+    pub fn combine_changes(&self, other: &Self, combined: &mut Antichain<T>) -> ::std::vec::IntoIter<(T, i64)>
+    where
+        T: PartialOrder+Clone,
+    {
+        let current = self.combine(other);
+        let mut changes = Vec::new();
+        for element in combined.elements() {
+            if !current.elements().contains(element) {
+                changes.push((element.clone(), -1));
+            }
+        }
+        for element in current.elements() {
+            if !combined.elements().contains(element) {
+                changes.push((element.clone(), 1));
+            }
+        }
+        *combined = current;
+        changes.into_iter()
+    }
+
     /// Creates a new singleton `MutableAntichain`.
     ///
     /// # Examples
@@ -790,6 +932,8 @@ impl<'a, T: 'a> ::std::iter::IntoIterator for &'a AntichainRef<'a, T> {
 mod tests {
     use std::collections::HashSet;
 
+    use crate::order::Product;
+
     use super::*;
 
     #[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -817,6 +961,93 @@ mod tests {
         assert!(!hashed.contains(&Antichain::from(vec![])));
     }
 
+    fn product_antichain(elements: &[(u64, u64)]) -> Antichain<Product<u64, u64>> {
+        elements.iter().map(|&(outer, inner)| Product::new(outer, inner)).collect()
+    }
+
+    #[test]
+    fn antichain_lattice() {
+        let samples = vec![
+            product_antichain(&[]),
+            product_antichain(&[(0, 0)]),
+            product_antichain(&[(1, 3)]),
+            product_antichain(&[(3, 1)]),
+            product_antichain(&[(0, 4), (2, 2), (4, 0)]),
+            product_antichain(&[(1, 2), (3, 0)]),
+        ];
+
+        for a in samples.iter() {
+            // Idempotence.
+            assert_eq!(&a.join(a), a);
+            assert_eq!(&a.meet(a), a);
+            for b in samples.iter() {
+                // Commutativity.
+                assert_eq!(a.join(b), b.join(a));
+                assert_eq!(a.meet(b), b.meet(a));
+                // Absorption.
+                assert_eq!(&a.join(&a.meet(b)), a);
+                assert_eq!(&a.meet(&a.join(b)), a);
+                // Bounds, and consistency with the partial order.
+                assert!(PartialOrder::less_equal(a, &a.join(b)));
+                assert!(PartialOrder::less_equal(&a.meet(b), a));
+                assert_eq!(PartialOrder::less_equal(a, b), &a.join(b) == b);
+                assert_eq!(PartialOrder::less_equal(a, b), &a.meet(b) == a);
+                for c in samples.iter() {
+                    // Associativity.
+                    assert_eq!(a.join(&b.join(c)), a.join(b).join(c));
+                    assert_eq!(a.meet(&b.meet(c)), a.meet(b).meet(c));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mutable_frontier_join() {
+        let mut mutable = MutableAntichain::new();
+        mutable.update_iter(vec![(Product::new(0, 2), 1), (Product::new(2, 0), 1)]);
+        let other = product_antichain(&[(1, 1)]);
+        let frontier = mutable.frontier().to_owned();
+        assert_eq!(mutable.frontier_join(other.borrow()), frontier.join(&other));
+        assert_eq!(mutable.frontier_join(other.borrow()), product_antichain(&[(1, 2), (2, 1)]));
+    }
+
+    #[test]
+    fn mutable_combine() {
+        let mut mutable1 = MutableAntichain::new();
+        let mut mutable2 = MutableAntichain::new();
+        let mut combined = Antichain::new();
+
+        mutable1.update_iter(vec![(Product::new(0, 2), 1), (Product::new(2, 0), 1)]);
+        mutable2.update_iter(vec![(Product::new(1, 1), 1), (Product::new(3, 0), 1)]);
+        let expected = product_antichain(&[(0, 2), (1, 1), (2, 0)]);
+        assert_eq!(mutable1.combine(&mutable2), expected);
+        assert_eq!(mutable1.combine(&mutable2), mutable2.combine(&mutable1));
+        assert_eq!(mutable1.combine(&mutable2), mutable1.frontier().to_owned().meet(&mutable2.frontier().to_owned()));
+
+        let mut changes = mutable1.combine_changes(&mutable2, &mut combined).collect::<Vec<_>>();
+        changes.sort();
+        assert_eq!(changes, vec![(Product::new(0, 2), 1), (Product::new(1, 1), 1), (Product::new(2, 0), 1)]);
+        assert_eq!(combined, expected);
+
+        // Advancing one input past the other leaves the other's frontier.
+        mutable1.update_iter(vec![(Product::new(0, 2), -1), (Product::new(2, 0), -1), (Product::new(2, 2), 1)]);
+        let mut changes = mutable1.combine_changes(&mutable2, &mut combined).collect::<Vec<_>>();
+        changes.sort();
+        assert_eq!(changes, vec![(Product::new(0, 2), -1), (Product::new(2, 0), -1), (Product::new(3, 0), 1)]);
+        assert_eq!(combined, product_antichain(&[(1, 1), (3, 0)]));
+
+        // Unchanged inputs produce no changes.
+        assert_eq!(mutable1.combine_changes(&mutable2, &mut combined).count(), 0);
+
+        // Closing both inputs empties the combined frontier.
+        mutable1.update_iter(vec![(Product::new(2, 2), -1)]);
+        mutable2.update_iter(vec![(Product::new(1, 1), -1), (Product::new(3, 0), -1)]);
+        let mut changes = mutable1.combine_changes(&mutable2, &mut combined).collect::<Vec<_>>();
+        changes.sort();
+        assert_eq!(changes, vec![(Product::new(1, 1), -1), (Product::new(3, 0), -1)]);
+        assert!(combined.is_empty());
+    }
+
     #[test]
     fn mutable_compaction() {
         let mut mutable = MutableAntichain::new();
@@ -9,6 +9,7 @@ use std::rc::Rc;
 use std::cell::RefCell;
 use std::collections::BinaryHeap;
 use std::cmp::Reverse;
+use std::time::Instant;
 
 use crate::logging::TimelyLogger as Logger;
 use crate::logging::TimelyProgressLogger as ProgressLogger;
@@ -56,12 +57,18 @@ where
 
     edge_stash: Vec<(Source, Target)>,
 
+    // channel identifiers carrying the edges arriving at each target, for reporting topology.
+    channels: Vec<(Target, usize)>,
+
     // shared state written to by the datapath, counting records entering this subgraph instance.
     input_messages: Vec<Rc<RefCell<ChangeBatch<TInner>>>>,
 
     // expressed capabilities, used to filter changes against.
     output_capabilities: Vec<MutableAntichain<TOuter>>,
 
+    // pointstamps held anywhere within the scope, if requested by `scope_frontier`.
+    scope_frontier: Option<Rc<RefCell<MutableAntichain<TInner>>>>,
+
     /// Logging handle
     logging: Option<Logger>,
 
@@ -94,6 +101,23 @@ where
         self.edge_stash.push((source, target));
     }
 
+    /// Records that the edge arriving at `target` is carried by channel `identifier`.
+    ///
+    /// This does not affect progress tracking, and is only used to report the topology.
+    pub fn add_channel(&mut self, identifier: usize, target: Target) {
+        self.channels.push((target, identifier));
+    }
+
+    /// A shared frontier of the pointstamps held anywhere within the subgraph.
+    ///
+    /// The frontier accumulates the capabilities held by and the messages in flight to every
+    /// child of the subgraph, across all workers. The same frontier is returned by each call.
+    pub fn scope_frontier(&mut self) -> Rc<RefCell<MutableAntichain<TInner>>> {
+        self.scope_frontier
+            .get_or_insert_with(|| Rc::new(RefCell::new(MutableAntichain::new())))
+            .clone()
+    }
+
     /// Creates a new Subgraph from a channel allocator and "descriptive" indices.
     pub fn new_from(
         index: usize,
@@ -116,8 +140,10 @@ where
             children,
             child_count: 1,
             edge_stash: Vec::new(),
+            channels: Vec::new(),
             input_messages: Vec::new(),
             output_capabilities: Vec::new(),
+            scope_frontier: None,
             logging,
             progress_logging,
         }
@@ -202,8 +228,10 @@ where
             temp_active: BinaryHeap::new(),
             maybe_shutdown: Vec::new(),
             children: self.children,
+            channels: self.channels,
             input_messages: self.input_messages,
             output_capabilities: self.output_capabilities,
+            scope_frontier: self.scope_frontier,
 
             local_pointstamp: ChangeBatch::new(),
             final_pointstamp: ChangeBatch::new(),
@@ -237,6 +265,9 @@ where
     // handles to the children of the scope. index i corresponds to entry i-1, unless things change.
     children: Vec<PerOperatorState<TInner>>,
 
+    // channel identifiers carrying the edges arriving at each target, for reporting topology.
+    channels: Vec<(Target, usize)>,
+
     incomplete: Vec<bool>,   // the incompletion status of each child.
     incomplete_count: usize, // the number of incomplete children.
 
@@ -251,6 +282,9 @@ where
     // expressed capabilities, used to filter changes against.
     output_capabilities: Vec<MutableAntichain<TOuter>>,
 
+    // pointstamps held anywhere within the scope, if requested by `scope_frontier`.
+    scope_frontier: Option<Rc<RefCell<MutableAntichain<TInner>>>>,
+
     // pointstamp messages to exchange. ultimately destined for `messages` or `internal`.
     local_pointstamp: ChangeBatch<(Location, TInner)>,
     final_pointstamp: ChangeBatch<(Location, TInner)>,
@@ -328,6 +362,83 @@ where
 
         incomplete || tracking
     }
+
+    fn report_stalled(&self, stalled: &mut Vec<crate::worker::StalledOperator>) {
+        // Index zero is the scope itself, and is reported by the parent.
+        for (index, child) in self.children.iter().enumerate().skip(1) {
+            if let Some(operator) = child.operator.as_ref() {
+                let state = self.pointstamp_tracker.node_state(index);
+                let input_frontiers = state.targets.iter().map(|port| format!("{:?}", port.implications.frontier())).collect::<Vec<_>>();
+                let capabilities = state.sources.iter().map(|port| format!("{:?}", port.pointstamps.frontier())).collect::<Vec<_>>();
+                let waiting = state.targets.iter().any(|port| !port.implications.is_empty());
+                let holding = state.sources.iter().any(|port| !port.pointstamps.is_empty());
+                if waiting || holding {
+                    let mut path = self.path.clone();
+                    path.push(index);
+                    stalled.push(crate::worker::StalledOperator {
+                        id: child.id,
+                        name: child.name.clone(),
+                        path,
+                        input_frontiers,
+                        capabilities,
+                    });
+                }
+                operator.report_stalled(stalled);
+            }
+        }
+    }
+
+    fn report_metrics(&self, metrics: &mut Vec<crate::worker::OperatorMetrics>) {
+        // Index zero is the scope itself, and is reported by the parent.
+        for (index, child) in self.children.iter().enumerate().skip(1) {
+            let state = self.pointstamp_tracker.node_state(index);
+            metrics.push(crate::worker::OperatorMetrics {
+                id: child.id,
+                complete: child.operator.is_none(),
+                frontier_sizes: state.targets.iter().map(|port| port.implications.frontier().len()).collect(),
+                capability_sizes: state.sources.iter().map(|port| port.pointstamps.frontier().len()).collect(),
+            });
+            if let Some(operator) = child.operator.as_ref() {
+                operator.report_metrics(metrics);
+            }
+        }
+    }
+
+    fn report_topology(&self, topology: &mut crate::worker::Topology) {
+        // Index zero is the scope itself, and is reported by the parent, but its edges are ours.
+        for (index, child) in self.children.iter().enumerate() {
+            for (port, targets) in child.edges.iter().enumerate() {
+                for target in targets.iter() {
+                    let identifier = self.channels.iter().find(|(t, _)| t == target).map(|(_, id)| *id);
+                    topology.channels.push(crate::worker::TopologyChannel {
+                        id: identifier,
+                        // Known only to the worker, which allocated the channel.
+                        exchange: false,
+                        scope: self.path.clone(),
+                        source: (index, port),
+                        target: (target.node, target.port),
+                    });
+                }
+            }
+            if index > 0 {
+                let mut path = self.path.clone();
+                path.push(index);
+                topology.operators.push(crate::worker::TopologyOperator {
+                    id: child.id,
+                    name: child.name.clone(),
+                    path,
+                    inputs: child.inputs,
+                    outputs: child.outputs,
+                    connectivity: child.internal_summary.iter().map(|input| {
+                        input.iter().map(|summary| format!("{:?}", summary.elements())).collect()
+                    }).collect(),
+                });
+                if let Some(operator) = child.operator.as_ref() {
+                    operator.report_topology(topology);
+                }
+            }
+        }
+    }
 }
 
 
@@ -454,6 +565,9 @@ where
                 }
             }
             else {
+                if let Some(frontier) = &self.scope_frontier {
+                    frontier.borrow_mut().update_iter(Some((timestamp.clone(), delta)));
+                }
                 self.pointstamp_tracker.update(location, timestamp, delta);
             }
         }
@@ -696,11 +810,18 @@ impl<T: Timestamp> PerOperatorState<T> {
                 l.log(crate::logging::ScheduleEvent::start(self.id));
             }
 
+            // Only read the clock if there is a logger to report the elapsed time to.
+            let start = self.logging.as_ref().map(|_| Instant::now());
+
+            let _context = crate::scheduling::context::enter(self.id, &self.name);
             let incomplete = operator.schedule();
 
             // Perhaps log information about the stop of the schedule call.
             if let Some(l) = self.logging.as_mut() {
                 l.log(crate::logging::ScheduleEvent::stop(self.id));
+                if let Some(start) = start {
+                    l.log(crate::logging::ScheduleElapsedEvent { id: self.id, elapsed: start.elapsed() });
+                }
             }
 
             incomplete
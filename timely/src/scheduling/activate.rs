@@ -121,6 +121,14 @@ impl Activations {
         self.clean = self.bounds.len();
     }
 
+    /// The number of activations recorded since the last call to `advance`.
+    ///
+    /// Activations are not deduplicated until `advance`, and inter-thread and delayed
+    /// activations are only counted once `advance` has received them.
+    pub fn pending(&self) -> usize {
+        self.bounds.len() - self.clean
+    }
+
     /// Maps a function across activated paths.
     pub fn map_active(&self, logic: impl Fn(&[usize])) {
         for (offset, length) in self.bounds.iter() {
@@ -272,6 +280,15 @@ impl SyncActivator {
     }
 }
 
+/// Wakes futures by activating the operator that polls them.
+#[cfg(feature = "async")]
+impl futures_util::task::ArcWake for SyncActivator {
+    fn wake_by_ref(arc_self: &std::sync::Arc<Self>) {
+        // An error means the worker has shut down, and there is no one left to wake.
+        let _ = arc_self.activate();
+    }
+}
+
 /// The error returned when activation fails across thread boundaries because
 /// the receiving end has hung up.
 #[derive(Clone, Copy, Debug)]
@@ -0,0 +1,127 @@
+//! Reporting the operator a worker is executing when it panics.
+//!
+//! A worker configured with a [`PanicHandler`] tracks the operators it schedules, and invokes
+//! the handler with a [`PanicReport`] if its thread panics. Panic hooks are global to the process,
+//! and so a single hook is installed the first time any worker requests one. The hook invokes the
+//! handler of the panicking thread, if it has one, and then the hook that was installed before it,
+//! which by default prints the panic message. Once the worker is dropped, its thread no longer
+//! has a handler, and panics on it are only reported by the previous hook.
+//!
+//! The hook is never uninstalled, and remains for the life of the process. Reinstalling the
+//! previous hook when the last worker exits would discard any hook installed after this one, and
+//! is not possible for a worker dropped while its thread panics, as hooks cannot be changed then.
+
+use std::cell::{Cell, RefCell};
+use std::fmt::{self, Debug};
+use std::sync::{Arc, Once};
+
+/// An operator being executed by a worker.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct OperatorContext {
+    /// The worker-unique identifier of the operator.
+    pub id: usize,
+    /// The name of the operator.
+    pub name: String,
+}
+
+/// Information about a panic on a worker thread.
+#[derive(Debug, Clone)]
+pub struct PanicReport {
+    /// The index of the worker that panicked.
+    pub worker: usize,
+    /// The panic message, if the panic payload was a string.
+    pub message: Option<String>,
+    /// The source location of the panic, as `file:line:column`, if known.
+    pub location: Option<String>,
+    /// The innermost operator being executed, if the panic occurred while scheduling one.
+    pub operator: Option<OperatorContext>,
+}
+
+/// A function invoked on a worker thread when it panics.
+#[derive(Clone)]
+pub struct PanicHandler(Arc<dyn Fn(&PanicReport)+Send+Sync>);
+
+impl PanicHandler {
+    /// Wraps `handler` as a panic handler.
+    pub fn new<F: Fn(&PanicReport)+Send+Sync+'static>(handler: F) -> Self {
+        PanicHandler(Arc::new(handler))
+    }
+}
+
+impl Debug for PanicHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PanicHandler").finish()
+    }
+}
+
+thread_local! {
+    /// The handler of this thread, and the index of its worker.
+    static HANDLER: RefCell<Option<(usize, PanicHandler)>> = RefCell::new(None);
+    /// Whether this thread has a handler, checked before tracking operators.
+    static ENABLED: Cell<bool> = Cell::new(false);
+    /// The operators being scheduled by this thread, innermost last.
+    static OPERATORS: RefCell<Vec<OperatorContext>> = RefCell::new(Vec::new());
+}
+
+/// Installs the process-wide panic hook, once.
+fn install_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            // `try_with`, as the thread-locals may already be destroyed if the thread is exiting.
+            let handler = HANDLER.try_with(|handler| handler.borrow().clone()).ok().flatten();
+            if let Some((worker, handler)) = handler {
+                let payload = info.payload();
+                let message = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned());
+                let location = info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+                let operator = OPERATORS.try_with(|operators| operators.borrow().last().cloned()).ok().flatten();
+                (handler.0)(&PanicReport { worker, message, location, operator });
+            }
+            previous(info);
+        }));
+    });
+}
+
+/// Restores the previous handler of the thread when dropped.
+pub(crate) struct HandlerGuard {
+    previous: Option<(usize, PanicHandler)>,
+}
+
+impl Drop for HandlerGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        ENABLED.with(|enabled| enabled.set(previous.is_some()));
+        HANDLER.with(|handler| *handler.borrow_mut() = previous);
+    }
+}
+
+/// Sets `handler` as the handler for panics on this thread, a worker with index `worker`.
+pub(crate) fn install(worker: usize, handler: PanicHandler) -> HandlerGuard {
+    install_hook();
+    ENABLED.with(|enabled| enabled.set(true));
+    let previous = HANDLER.with(|current| current.borrow_mut().replace((worker, handler)));
+    HandlerGuard { previous }
+}
+
+/// Removes the operator from the operators being scheduled when dropped.
+pub(crate) struct OperatorGuard;
+
+impl Drop for OperatorGuard {
+    fn drop(&mut self) {
+        OPERATORS.with(|operators| operators.borrow_mut().pop());
+    }
+}
+
+/// Records that the operator `id` named `name` is being scheduled, if this thread has a handler.
+#[inline]
+pub(crate) fn enter(id: usize, name: &str) -> Option<OperatorGuard> {
+    if ENABLED.with(|enabled| enabled.get()) {
+        OPERATORS.with(|operators| operators.borrow_mut().push(OperatorContext { id, name: name.to_owned() }));
+        Some(OperatorGuard)
+    }
+    else {
+        None
+    }
+}
@@ -4,6 +4,7 @@ use std::rc::Rc;
 use std::cell::RefCell;
 
 pub mod activate;
+pub mod context;
 
 pub use self::activate::{Activations, Activator, ActivateOnDrop, SyncActivator};
 
@@ -18,6 +19,22 @@ pub trait Schedule {
     /// The return value indicates whether `self` has outstanding
     /// work and would be upset if the computation terminated.
     fn schedule(&mut self) -> bool;
+
+    /// Reports operators that may be responsible for a stalled computation.
+    ///
+    /// An operator is reported if it has a non-empty input frontier or holds capabilities.
+    /// The default implementation reports nothing; scopes report their children, recursively.
+    fn report_stalled(&self, _stalled: &mut Vec<crate::worker::StalledOperator>) { }
+
+    /// Reports the operators and channels within `self`.
+    ///
+    /// The default implementation reports nothing; scopes report their children, recursively.
+    fn report_topology(&self, _topology: &mut crate::worker::Topology) { }
+
+    /// Reports progress metrics for the operators within `self`.
+    ///
+    /// The default implementation reports nothing; scopes report their children, recursively.
+    fn report_metrics(&self, _metrics: &mut Vec<crate::worker::OperatorMetrics>) { }
 }
 
 /// Methods for types which schedule fibers.
@@ -0,0 +1,79 @@
+//! Helpers for testing timely dataflow computations with multiple workers.
+//!
+//! These functions run a closure as each of several workers within the current process, wait
+//! for all workers to complete, and return what each worker produced, indexed by worker. A worker
+//! that panics is reported with its panic message, rather than panicking the caller, so that tests
+//! can assert on failures as well as results.
+
+use crate::communication::Allocator;
+use crate::worker::Worker;
+use crate::{CommunicationConfig, Config, WorkerConfig};
+
+/// Runs `logic` as each of `workers` worker threads, and returns their results.
+///
+/// The workers communicate through shared memory, as with `Config::process`.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::{ToStream, Exchange, Inspect};
+///
+/// let results = timely::testing::run(3, |worker| {
+///     worker.dataflow::<u64,_,_>(|scope| {
+///         (0..10).to_stream(scope)
+///                .exchange(|x| *x)
+///                .inspect(|x| println!("seen: {:?}", x));
+///     });
+///     worker.index()
+/// });
+///
+/// assert_eq!(results, vec![Ok(0), Ok(1), Ok(2)]);
+/// ```
+pub fn run<T, F>(workers: usize, logic: F) -> Vec<Result<T, String>>
+where
+    T: Send+'static,
+    F: Fn(&mut Worker<Allocator>)->T+Send+Sync+'static,
+{
+    run_with(CommunicationConfig::Process(workers), logic)
+}
+
+/// Runs `logic` as each of `threads` workers in each of `processes` simulated processes, and
+/// returns their results.
+///
+/// Workers in different simulated processes communicate through in-memory streams, exercising
+/// the serialization and networking paths of a cluster, as with `CommunicationConfig::ClusterInMemory`.
+/// Results are ordered by worker index, so that those of the first process come first.
+///
+/// # Examples
+/// ```
+/// use timely::dataflow::operators::{ToStream, Exchange, Inspect};
+///
+/// let results = timely::testing::run_in_memory(2, 2, |worker| {
+///     worker.dataflow::<u64,_,_>(|scope| {
+///         (0..10).to_stream(scope)
+///                .exchange(|x| *x)
+///                .inspect(|x| println!("seen: {:?}", x));
+///     });
+///     worker.peers()
+/// });
+///
+/// assert_eq!(results, vec![Ok(4); 4]);
+/// ```
+pub fn run_in_memory<T, F>(processes: usize, threads: usize, logic: F) -> Vec<Result<T, String>>
+where
+    T: Send+'static,
+    F: Fn(&mut Worker<Allocator>)->T+Send+Sync+'static,
+{
+    run_with(CommunicationConfig::ClusterInMemory { processes, threads }, logic)
+}
+
+fn run_with<T, F>(communication: CommunicationConfig, logic: F) -> Vec<Result<T, String>>
+where
+    T: Send+'static,
+    F: Fn(&mut Worker<Allocator>)->T+Send+Sync+'static,
+{
+    let config = Config { communication, worker: WorkerConfig::default() };
+    match crate::execute::execute(config, logic) {
+        Ok(guards) => guards.join(),
+        Err(error) => panic!("timely::testing: failed to start workers: {}", error),
+    }
+}
@@ -2,16 +2,18 @@
 
 use std::rc::Rc;
 use std::cell::{RefCell, RefMut};
-use std::any::Any;
+use std::any::{Any, TypeId};
 use std::str::FromStr;
 use std::time::{Instant, Duration};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::collections::hash_map::Entry;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::communication::{Allocate, Data, Push, Pull};
 use crate::communication::allocator::thread::{ThreadPusher, ThreadPuller};
 use crate::scheduling::{Schedule, Scheduler, Activations};
+use crate::scheduling::context::{self, HandlerGuard, PanicHandler, PanicReport};
 use crate::progress::timestamp::{Refines};
 use crate::progress::SubgraphBuilder;
 use crate::progress::operate::Operate;
@@ -82,11 +84,72 @@ impl FromStr for ProgressMode {
     }
 }
 
+/// How a worker orders the dataflows it schedules in each step.
+///
+/// Each step of a worker schedules each dataflow with pending activations once, and in turn
+/// each dataflow schedules its operators with pending activations. The policy determines the
+/// order of the dataflows within a step, and so which dataflows' operators run first when
+/// several compete for the worker; no dataflow is skipped.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SchedulingPolicy {
+    /// Schedule active dataflows in the order of their indices.
+    RoundRobin,
+    /// Schedule first the active dataflows that have waited longest since they were last scheduled.
+    ///
+    /// Dataflows that have not yet been scheduled are scheduled first, and ties are broken by
+    /// dataflow index. This favors dataflows that are activated rarely, such as latency-sensitive
+    /// dataflows sharing a worker with dataflows that are activated at every step.
+    Staleness,
+}
+
+impl Default for SchedulingPolicy {
+    fn default() -> SchedulingPolicy {
+        SchedulingPolicy::RoundRobin
+    }
+}
+
+impl FromStr for SchedulingPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<SchedulingPolicy, String> {
+        match s {
+            "round-robin" => Ok(SchedulingPolicy::RoundRobin),
+            "staleness" => Ok(SchedulingPolicy::Staleness),
+            _ => Err(format!("unknown scheduling policy: {}", s)),
+        }
+    }
+}
+
+/// How a worker reacts to an operator whose buffered state exceeds the configured limit.
+///
+/// Operators report their buffered state through a [crate::memory::MemoryAccount]. Under each
+/// policy, the operator's crossing of the limit is logged as an `OperatorMemoryEvent`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MemoryPolicy {
+    /// Only log that the operator exceeds the limit.
+    Log,
+    /// Ask the operator to release buffered state where it can, for example by sending records
+    /// early. Operators that cannot release their state continue as with `Log`.
+    Flush,
+    /// Panic the worker, identifying the operator.
+    Panic,
+}
+
 /// Worker configuration.
 #[derive(Debug, Default, Clone)]
 pub struct Config {
     /// The progress mode to use.
     pub(crate) progress_mode: ProgressMode,
+    /// The order in which dataflows are scheduled in each step.
+    pub(crate) scheduling_policy: SchedulingPolicy,
+    /// A handler for panics on the worker thread.
+    pub(crate) panic_handler: Option<PanicHandler>,
+    /// The number of consecutive idle steps after which `Worker::detect_stall` reports.
+    pub(crate) stall_steps: Option<usize>,
+    /// The bytes each operator may buffer, and the reaction to operators that exceed them.
+    pub(crate) memory_limit: Option<(usize, MemoryPolicy)>,
+    /// The idle schedulings after which operators log capabilities held past their inputs.
+    pub(crate) held_capability_schedules: Option<usize>,
     /// A map from parameter name to typed parameter values.
     registry: HashMap<String, Arc<dyn Any + Send + Sync>>,
 }
@@ -104,6 +167,7 @@ impl Config {
     #[cfg(feature = "getopts")]
     pub fn install_options(opts: &mut getopts_dep::Options) {
         opts.optopt("", "progress-mode", "progress tracking mode (eager or demand)", "MODE");
+        opts.optopt("", "scheduling", "dataflow scheduling policy (round-robin or staleness)", "POLICY");
     }
 
     /// Instantiates a configuration based upon the parsed options in `matches`.
@@ -118,7 +182,9 @@ impl Config {
     pub fn from_matches(matches: &getopts_dep::Matches) -> Result<Config, String> {
         let progress_mode = matches
             .opt_get_default("progress-mode", ProgressMode::Eager)?;
-        Ok(Config::default().progress_mode(progress_mode))
+        let scheduling_policy = matches
+            .opt_get_default("scheduling", SchedulingPolicy::RoundRobin)?;
+        Ok(Config::default().progress_mode(progress_mode).scheduling_policy(scheduling_policy))
     }
 
     /// Sets the progress mode to `progress_mode`.
@@ -127,6 +193,82 @@ impl Config {
         self
     }
 
+    /// Sets the order in which dataflows are scheduled in each step to `scheduling_policy`.
+    ///
+    /// The default is `SchedulingPolicy::RoundRobin`.
+    pub fn scheduling_policy(mut self, scheduling_policy: SchedulingPolicy) -> Self {
+        self.scheduling_policy = scheduling_policy;
+        self
+    }
+
+    /// Sets `handler` to be invoked when the worker thread panics, with the operator it was executing.
+    ///
+    /// The handler is invoked on the panicking thread before the panic unwinds, followed by the
+    /// panic hook that was installed before it, which by default prints the panic message. The
+    /// handler is removed when the worker is dropped. This does not change the errors returned by
+    /// `WorkerGuards::join`, which remain the panic messages without the operator.
+    ///
+    /// The handler is invoked by a process-wide panic hook, which is installed when the first
+    /// worker with a handler starts and stays installed for the life of the process, even after
+    /// all such workers have exited. On threads without a handler it only calls the previous hook.
+    /// See [crate::scheduling::context] for details.
+    ///
+    /// # Examples
+    /// ```rust
+    /// let mut config = timely::Config::thread();
+    /// config.worker = config.worker.panic_handler(|report| {
+    ///     eprintln!("worker {} panicked in {:?}: {:?}", report.worker, report.operator, report.message);
+    /// });
+    /// timely::execute(config, |worker| {
+    ///     assert_eq!(worker.index(), 0);
+    /// }).unwrap();
+    /// ```
+    pub fn panic_handler<F: Fn(&PanicReport)+Send+Sync+'static>(mut self, handler: F) -> Self {
+        self.panic_handler = Some(PanicHandler::new(handler));
+        self
+    }
+
+    /// Sets the number of consecutive idle steps after which `Worker::detect_stall` reports.
+    ///
+    /// The default is 1000 steps.
+    pub fn stall_steps(mut self, steps: usize) -> Self {
+        self.stall_steps = Some(steps);
+        self
+    }
+
+    /// Limits the bytes each operator may buffer to `bytes`, reacting to operators that exceed it with `policy`.
+    ///
+    /// Only operators that report their buffered state are limited, such as `coalesce_accounted` and
+    /// `aggregate`, and their reports are estimates. By default, there is no limit.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use timely::worker::MemoryPolicy;
+    ///
+    /// let mut config = timely::Config::thread();
+    /// config.worker = config.worker.operator_memory_limit(1 << 30, MemoryPolicy::Flush);
+    /// timely::execute(config, |worker| {
+    ///     assert!(worker.operator_memory().is_empty());
+    /// }).unwrap();
+    /// ```
+    pub fn operator_memory_limit(mut self, bytes: usize, policy: MemoryPolicy) -> Self {
+        self.memory_limit = Some((bytes, policy));
+        self
+    }
+
+    /// Logs operators that still hold capabilities once their inputs have completed.
+    ///
+    /// An operator built with `builder_rc::OperatorBuilder` that has been scheduled `schedules`
+    /// times with all of its inputs complete, without asking to be rescheduled, yet still holds
+    /// capabilities, logs a `HeldCapabilitiesEvent` to the "timely" log naming the held times.
+    /// Each operator logs at most once. Such capabilities are almost always leaked, and stall
+    /// everything downstream of the operator. By default, and without a "timely" logger, the
+    /// capabilities are not tracked.
+    pub fn log_held_capabilities(mut self, schedules: usize) -> Self {
+        self.held_capability_schedules = Some(schedules.max(1));
+        self
+    }
+
     /// Sets a typed configuration parameter for the given `key`.
     ///
     /// It is recommended to install a single configuration struct using a key
@@ -181,6 +323,13 @@ pub trait AsWorker : Scheduler {
     fn index(&self) -> usize;
     /// Number of peer workers.
     fn peers(&self) -> usize;
+    /// The instant at which the worker was started, as reported by `Worker::timer`.
+    ///
+    /// Operators that need wall-clock time should measure it from this instant, so that every
+    /// operator of the worker, and the worker's logging, share a common origin.
+    fn timer(&self) -> Instant;
+    /// The time elapsed since the worker was started.
+    fn elapsed(&self) -> Duration { self.timer().elapsed() }
     /// Allocates a new channel from a supplied identifier and address.
     ///
     /// The identifier is used to identify the underlying channel and route
@@ -204,6 +353,70 @@ pub trait AsWorker : Scheduler {
     fn log_register(&self) -> ::std::cell::RefMut<crate::logging_core::Registry<crate::logging::WorkerIdentifier>>;
     /// Provides access to the timely logging stream.
     fn logging(&self) -> Option<crate::logging::TimelyLogger> { self.log_register().get("timely") }
+    /// Provides access to the resources shared by the operators of the worker.
+    fn resources(&self) -> RefMut<Resources>;
+    /// Returns the worker's instance of the resource of type `T`, if a factory is registered.
+    ///
+    /// The resource is created by its factory the first time it is requested, and the same
+    /// instance is returned to every subsequent request on this worker. Resources are local to
+    /// the worker's thread, and each worker creates its own instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashMap;
+    /// use timely::worker::AsWorker;
+    /// use timely::dataflow::operators::{ToStream, Inspect};
+    ///
+    /// timely::execute_from_args(std::env::args(), |worker| {
+    ///     worker.resources().register(HashMap::<u64, usize>::new);
+    ///     worker.dataflow::<u64,_,_>(|scope| {
+    ///         let counts = scope.local_resource::<HashMap<u64, usize>>().unwrap();
+    ///         (0 .. 10)
+    ///             .to_stream(scope)
+    ///             .inspect(move |x| *counts.borrow_mut().entry(x % 3).or_insert(0) += 1);
+    ///     });
+    /// }).unwrap();
+    /// ```
+    fn local_resource<T: 'static>(&self) -> Option<Rc<RefCell<T>>> { self.resources().get::<T>() }
+}
+
+/// Resources shared by the operators of a worker, identified by their type.
+///
+/// Each resource is created by a registered factory when it is first requested.
+#[derive(Default)]
+pub struct Resources {
+    factories: HashMap<TypeId, Box<dyn Fn() -> Rc<dyn Any>>>,
+    resources: HashMap<TypeId, Rc<dyn Any>>,
+}
+
+impl Resources {
+    /// Registers `factory` to create the worker's resource of type `T`.
+    ///
+    /// A previously registered factory for `T` is replaced, but a resource it already created
+    /// is retained. The factory is called while the resources are borrowed, and so must not
+    /// itself request resources.
+    pub fn register<T: 'static, F: Fn() -> T + 'static>(&mut self, factory: F) {
+        self.factories.insert(TypeId::of::<T>(), Box::new(move || Rc::new(RefCell::new(factory())) as Rc<dyn Any>));
+    }
+
+    /// Returns the resource of type `T`, creating it if necessary, or `None` if no factory is registered.
+    pub fn get<T: 'static>(&mut self) -> Option<Rc<RefCell<T>>> {
+        let type_id = TypeId::of::<T>();
+        let resource = match self.resources.entry(type_id) {
+            Entry::Occupied(entry) => entry.get().clone(),
+            Entry::Vacant(entry) => entry.insert((self.factories.get(&type_id)?)()).clone(),
+        };
+        Some(resource.downcast::<RefCell<T>>().expect("resource registered with a mismatched type"))
+    }
+}
+
+impl ::std::fmt::Debug for Resources {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        f.debug_struct("Resources")
+            .field("factories", &self.factories.len())
+            .field("resources", &self.resources.len())
+            .finish()
+    }
 }
 
 /// A `Worker` is the entry point to a timely dataflow computation. It wraps a `Allocate`,
@@ -225,17 +438,34 @@ pub struct Worker<A: Allocate> {
     // Temporary storage for channel identifiers during dataflow construction.
     // These are then associated with a dataflow once constructed.
     temp_channel_ids: Rc<RefCell<Vec<usize>>>,
+
+    // Dataflows whose `DataflowToken` has been released, to be dropped at the next step.
+    released_dataflows: Arc<ReleasedDataflows>,
+
+    // Number of consecutive steps in which no dataflow was scheduled.
+    idle_steps: Rc<RefCell<usize>>,
+
+    // Channels allocated to exchange data between workers, rather than within this worker.
+    exchange_channels: Rc<RefCell<HashSet<usize>>>,
+
+    // Resources shared by the operators of this worker.
+    resources: Rc<RefCell<Resources>>,
+
+    // Restores the previous panic handler of the thread, once the last clone drops.
+    panic_guard: Rc<Option<HandlerGuard>>,
 }
 
 impl<A: Allocate> AsWorker for Worker<A> {
     fn config(&self) -> &Config { &self.config }
     fn index(&self) -> usize { self.allocator.borrow().index() }
     fn peers(&self) -> usize { self.allocator.borrow().peers() }
+    fn timer(&self) -> Instant { self.timer() }
     fn allocate<D: Data>(&mut self, identifier: usize, address: &[usize]) -> (Vec<Box<dyn Push<Message<D>>>>, Box<dyn Pull<Message<D>>>) {
         if address.is_empty() { panic!("Unacceptable address: Length zero"); }
         let mut paths = self.paths.borrow_mut();
         paths.insert(identifier, address.to_vec());
         self.temp_channel_ids.borrow_mut().push(identifier);
+        self.exchange_channels.borrow_mut().insert(identifier);
         self.allocator.borrow_mut().allocate(identifier)
     }
     fn pipeline<T: 'static>(&mut self, identifier: usize, address: &[usize]) -> (ThreadPusher<Message<T>>, ThreadPuller<Message<T>>) {
@@ -250,6 +480,7 @@ impl<A: Allocate> AsWorker for Worker<A> {
     fn log_register(&self) -> RefMut<crate::logging_core::Registry<crate::logging::WorkerIdentifier>> {
         self.log_register()
     }
+    fn resources(&self) -> RefMut<Resources> { self.resources.borrow_mut() }
 }
 
 impl<A: Allocate> Scheduler for Worker<A> {
@@ -263,6 +494,7 @@ impl<A: Allocate> Worker<A> {
     pub fn new(config: Config, c: A) -> Worker<A> {
         let now = Instant::now();
         let index = c.index();
+        let panic_guard = config.panic_handler.clone().map(|handler| context::install(index, handler));
         Worker {
             config,
             timer: now,
@@ -275,6 +507,11 @@ impl<A: Allocate> Worker<A> {
             activations: Rc::new(RefCell::new(Activations::new(now))),
             active_dataflows: Default::default(),
             temp_channel_ids:  Default::default(),
+            released_dataflows: Default::default(),
+            idle_steps: Default::default(),
+            exchange_channels: Default::default(),
+            resources: Default::default(),
+            panic_guard: Rc::new(panic_guard),
         }
     }
 
@@ -308,6 +545,13 @@ impl<A: Allocate> Worker<A> {
     /// A step gives each dataflow operator a chance to run, and is the
     /// main way to ensure that a computation proceeds.
     ///
+    /// Only operators that have been activated run, for example by arriving
+    /// messages, progress changes, or an `Activator`. Each step first collects
+    /// the activations recorded since the previous step, and then schedules
+    /// each dataflow with activated operators once, in the order set by the
+    /// worker's `SchedulingPolicy`. Activations recorded while the step runs
+    /// may be deferred to the next step.
+    ///
     /// This method takes an optional timeout and may park the thread until
     /// there is work to perform or until this timeout expires. A value of
     /// `None` allows the worker to park indefinitely, whereas a value of
@@ -332,6 +576,11 @@ impl<A: Allocate> Worker<A> {
     /// ```
     pub fn step_or_park(&mut self, duration: Option<Duration>) -> bool {
 
+        // Drop dataflows whose tokens have been released.
+        for dataflow_index in self.released_dataflows.take() {
+            self.drop_dataflow(dataflow_index);
+        }
+
         {   // Process channel events. Activate responders.
             let mut allocator = self.allocator.borrow_mut();
             allocator.receive();
@@ -381,6 +630,8 @@ impl<A: Allocate> Worker<A> {
 
             // Log return from unpark.
             self.logging().as_mut().map(|l| l.log(crate::logging::ParkEvent::unpark()));
+
+            *self.idle_steps.borrow_mut() += 1;
         }
         else {   // Schedule active dataflows.
 
@@ -389,16 +640,34 @@ impl<A: Allocate> Worker<A> {
                 .borrow_mut()
                 .for_extensions(&[], |index| active_dataflows.push(index));
 
+            if active_dataflows.is_empty() {
+                *self.idle_steps.borrow_mut() += 1;
+            }
+            else {
+                *self.idle_steps.borrow_mut() = 0;
+            }
+
             let mut dataflows = self.dataflows.borrow_mut();
+            let staleness = self.config.scheduling_policy == SchedulingPolicy::Staleness;
+            if staleness {
+                // Stable, so that ties remain in index order.
+                active_dataflows.sort_by_key(|index| dataflows.get(index).and_then(|wrapper| wrapper.last_scheduled));
+            }
             for index in active_dataflows.drain(..) {
                 // Step dataflow if it exists, remove if not incomplete.
                 if let Entry::Occupied(mut entry) = dataflows.entry(index) {
+                    // Only the staleness policy reads the time, so only it pays for reading the clock.
+                    if staleness {
+                        entry.get_mut().last_scheduled = Some(Instant::now());
+                    }
                     // TODO: This is a moment at which a scheduling decision is being made.
                     let incomplete = entry.get_mut().step();
                     if !incomplete {
                         let mut paths = self.paths.borrow_mut();
+                        let mut exchange_channels = self.exchange_channels.borrow_mut();
                         for channel in entry.get_mut().channel_ids.drain(..) {
                             paths.remove(&channel);
+                            exchange_channels.remove(&channel);
                         }
                         entry.remove_entry();
                     }
@@ -412,6 +681,40 @@ impl<A: Allocate> Worker<A> {
         !self.dataflows.borrow().is_empty()
     }
 
+    /// Steps the worker until all of its dataflows have completed.
+    ///
+    /// A dataflow completes once all of its frontiers are empty and none of its operators
+    /// report outstanding work, at which point the worker retires it. No probe is required.
+    /// The worker parks between steps when it has nothing to do, as the `execute` functions
+    /// do once the worker closure returns.
+    ///
+    /// This method blocks until completion, and so is only appropriate once all inputs have
+    /// been closed, for example by dropping their handles. Calling it with an open input blocks
+    /// indefinitely.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// timely::execute_from_args(::std::env::args(), |worker| {
+    ///
+    ///     use timely::dataflow::operators::{ToStream, Inspect};
+    ///
+    ///     worker.dataflow::<usize,_,_>(|scope| {
+    ///         (0 .. 10)
+    ///             .to_stream(scope)
+    ///             .inspect(|x| println!("{:?}", x));
+    ///     });
+    ///
+    ///     worker.run_to_completion();
+    ///     assert!(!worker.has_dataflows());
+    /// });
+    /// ```
+    pub fn run_to_completion(&mut self) {
+        while self.has_dataflows() {
+            self.step_or_park(None);
+        }
+    }
+
     /// Calls `self.step()` as long as `func` evaluates to true.
     ///
     /// This method will continually execute even if there is not work
@@ -501,6 +804,28 @@ impl<A: Allocate> Worker<A> {
     /// ```
     pub fn peers(&self) -> usize { self.allocator.borrow().peers() }
 
+    /// Reports for each process whether it is still connected, indexed by process.
+    ///
+    /// A process is reported disconnected once a communication thread exchanging data with it
+    /// has failed, because the connection was closed without a clean shutdown, or reading from
+    /// or writing to it returned an error. The next call to `step` then panics with a message
+    /// starting with "timely communication error:" that names the process, rather than waiting
+    /// for data that will not arrive.
+    ///
+    /// Only connections that are closed or fail are detected. A process that stops responding
+    /// while its connection stays open is still reported connected, and workers waiting on it
+    /// wait indefinitely: connections carry no heartbeats, so a silent peer cannot be told apart
+    /// from an idle one.
+    ///
+    /// # Examples
+    /// ```
+    /// timely::execute_from_args(::std::env::args(), |worker| {
+    ///     let health = worker.peer_health();
+    ///     assert!(health.iter().all(|connected| *connected));
+    /// });
+    /// ```
+    pub fn peer_health(&self) -> Vec<bool> { self.allocator.borrow().peer_health() }
+
     /// A timer started at the initiation of the timely computation.
     ///
     /// # Examples
@@ -591,6 +916,48 @@ impl<A: Allocate> Worker<A> {
         self.dataflow_core(name, logging, Box::new(()), |_, child| func(child))
     }
 
+    /// Construct a new named dataflow, and a token that drops the dataflow when released.
+    ///
+    /// Dropping the token, or calling `DataflowToken::shutdown`, drops the dataflow as with
+    /// `drop_dataflow`. The token can be sent to another thread, in which case the dataflow is
+    /// dropped the next time this worker steps. Only this worker's part of the dataflow is dropped;
+    /// other workers must release their own tokens. Use `DataflowToken::detach` to let the dataflow
+    /// run to completion instead.
+    ///
+    /// # Examples
+    /// ```
+    /// timely::execute_from_args(::std::env::args(), |worker| {
+    ///
+    ///     use timely::dataflow::operators::{Input, Inspect};
+    ///
+    ///     let (mut input, token) = worker.dataflow_with_token::<usize,_,_>("Dropped Dataflow", |scope| {
+    ///         let (input, stream) = scope.new_input::<usize>();
+    ///         stream.inspect(|x| println!("{:?}", x));
+    ///         input
+    ///     });
+    ///
+    ///     input.send(0);
+    ///     worker.step();
+    ///
+    ///     token.shutdown();
+    ///     worker.step();
+    ///     assert!(!worker.has_dataflows());
+    /// });
+    /// ```
+    pub fn dataflow_with_token<T, R, F>(&mut self, name: &str, func: F) -> (R, DataflowToken)
+    where
+        T: Refines<()>,
+        F: FnOnce(&mut Child<Self, T>)->R,
+    {
+        let dataflow_index = self.next_dataflow_index();
+        let result = self.dataflow_named(name, func);
+        let token = DataflowToken {
+            dataflow_index,
+            released: Some(self.released_dataflows.clone()),
+        };
+        (result, token)
+    }
+
     /// Construct a new dataflow with specific configurations.
     ///
     /// This method constructs a new dataflow, using a name, logger, and additional
@@ -663,6 +1030,7 @@ impl<A: Allocate> Worker<A> {
             operate: Some(Box::new(operator)),
             resources: Some(Box::new(resources)),
             channel_ids,
+            last_scheduled: None,
         };
         self.dataflows.borrow_mut().insert(dataflow_index, wrapper);
 
@@ -676,13 +1044,29 @@ impl<A: Allocate> Worker<A> {
     /// Various other resources will be cleaned up, though the method is currently in
     /// public beta rather than expected to work. Please report all crashes and unmet
     /// expectations!
+    ///
+    /// The dataflow may be dropped while messages for its channels are still in flight. The
+    /// channels' receiving ends are dropped with the dataflow, before its other resources, and
+    /// messages that arrive for them later are then discarded by the allocators as for any
+    /// dropped channel; the zero-copy allocators recognize them because channel identifiers
+    /// are allocated in increasing order. Other workers should drop the dataflow too, rather
+    /// than wait on progress from this worker.
     pub fn drop_dataflow(&mut self, dataflow_identifier: usize) {
-        if let Some(mut entry) = self.dataflows.borrow_mut().remove(&dataflow_identifier) {
+        // Release the borrow before dropping the dataflow, whose operators may run arbitrary code on drop.
+        let entry = self.dataflows.borrow_mut().remove(&dataflow_identifier);
+        if let Some(mut entry) = entry {
             // Garbage collect channel_id to path information.
-            let mut paths = self.paths.borrow_mut();
-            for channel in entry.channel_ids.drain(..) {
-                paths.remove(&channel);
+            {
+                let mut paths = self.paths.borrow_mut();
+                let mut exchange_channels = self.exchange_channels.borrow_mut();
+                for channel in entry.channel_ids.drain(..) {
+                    paths.remove(&channel);
+                    exchange_channels.remove(&channel);
+                }
             }
+            // Drop the dataflow before its resources, as when it completes.
+            entry.operate = None;
+            entry.resources = None;
         }
     }
 
@@ -699,6 +1083,250 @@ impl<A: Allocate> Worker<A> {
         self.dataflows.borrow().keys().cloned().collect()
     }
 
+    /// Quiesces the worker, and snapshots the states registered with `checkpoint::register`.
+    ///
+    /// The worker is stepped until a step schedules no operators, so that no records remain in
+    /// flight between the operators of this worker, and the registered states are then snapshotted
+    /// without scheduling any operator in between. With multiple workers, the snapshots of the
+    /// workers form a consistent cut only if every worker checkpoints once the same input times
+    /// are complete; see the `checkpoint` module for the consistency model.
+    ///
+    /// This method does not return if an operator activates itself on every step.
+    pub fn checkpoint(&mut self) -> crate::checkpoint::Snapshot {
+        while self.step() {
+            if *self.idle_steps.borrow() > 0 {
+                break;
+            }
+        }
+        crate::checkpoint::registry(self).borrow_mut().snapshot()
+    }
+
+    /// Retains the states of `snapshot`, to restore each when a state is next registered under its name.
+    ///
+    /// This method should be called before the dataflows are rebuilt, as states registered before
+    /// it is called are not restored.
+    pub fn restore(&mut self, snapshot: crate::checkpoint::Snapshot) {
+        crate::checkpoint::registry(self).borrow_mut().restore(snapshot);
+    }
+
+    /// The latest report of the buffered state of each operator with a [crate::memory::MemoryAccount].
+    ///
+    /// Operators are listed in order of their identifiers, and only while they are running.
+    /// Operators only open accounts if the worker is configured with `operator_memory_limit`.
+    pub fn operator_memory(&self) -> Vec<crate::memory::OperatorMemory> {
+        crate::memory::usage(self).borrow().operators()
+    }
+
+    /// The number of operator activations awaiting the next step.
+    ///
+    /// These are the activations recorded since the worker last organized its activations at
+    /// the start of a step, possibly including duplicates. Activations from other threads that
+    /// have not yet been received, and delayed activations that are not yet due, are not
+    /// counted. A value that remains large across steps indicates that the worker is saturated.
+    pub fn activations_pending(&self) -> usize {
+        self.activations.borrow().pending()
+    }
+
+    /// Reports operators that may be responsible for a stalled computation.
+    ///
+    /// Returns `None` unless the worker has stepped without scheduling any dataflow for at least
+    /// the number of consecutive steps set by `Config::stall_steps`, so that calling this method
+    /// is cheap while the computation makes progress. Once a stall is suspected, the dataflow
+    /// graphs are walked and every operator with a non-empty input frontier or held capabilities
+    /// is reported. None of these operators can have produced output while the worker was idle.
+    ///
+    /// The assessment is local to this worker: a worker waiting on a slow peer also appears idle,
+    /// and only the report of each worker shows which of its operators hold capabilities.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// timely::execute_from_args(::std::env::args(), |worker| {
+    ///
+    ///     use timely::dataflow::InputHandle;
+    ///     use timely::dataflow::operators::{Input, Probe};
+    ///
+    ///     let mut input = InputHandle::<usize, usize>::new();
+    ///     worker.dataflow(|scope| {
+    ///         scope.input_from(&mut input)
+    ///              .probe();
+    ///     });
+    ///
+    ///     // The input is never advanced, so the worker eventually idles.
+    ///     for _ in 0 .. 2000 {
+    ///         worker.step();
+    ///     }
+    ///     if let Some(report) = worker.detect_stall() {
+    ///         for operator in report.operators.iter() {
+    ///             println!("{:?}", operator);
+    ///         }
+    ///     }
+    /// });
+    /// ```
+    pub fn detect_stall(&self) -> Option<StallReport> {
+        let idle_steps = *self.idle_steps.borrow();
+        if idle_steps < self.config.stall_steps.unwrap_or(1000) {
+            return None;
+        }
+
+        let mut operators = Vec::new();
+        let dataflows = self.dataflows.borrow();
+        let mut indices = dataflows.keys().cloned().collect::<Vec<_>>();
+        indices.sort();
+        for index in indices {
+            if let Some(operate) = dataflows[&index].operate.as_ref() {
+                operate.report_stalled(&mut operators);
+            }
+        }
+
+        if operators.is_empty() {
+            None
+        }
+        else {
+            Some(StallReport { idle_steps, operators })
+        }
+    }
+
+    /// Returns a snapshot of progress metrics across all dataflows of the worker, along with the
+    /// memory of its receive buffers and the bytes its channels exchange with other processes.
+    ///
+    /// The snapshot reads state the worker maintains anyway, and only allocates for the lists
+    /// of operators and channels, so that it is cheap enough to take periodically, for example
+    /// once a second.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// timely::execute_from_args(::std::env::args(), |worker| {
+    ///
+    ///     use timely::dataflow::InputHandle;
+    ///     use timely::dataflow::operators::{Input, Probe};
+    ///
+    ///     let mut input = InputHandle::<usize, usize>::new();
+    ///     worker.dataflow(|scope| {
+    ///         scope.input_from(&mut input)
+    ///              .probe();
+    ///     });
+    ///
+    ///     worker.step();
+    ///     let metrics = worker.metrics_snapshot();
+    ///     assert_eq!(metrics.dataflows, 1);
+    ///     println!("{:?}", metrics);
+    /// });
+    /// ```
+    pub fn metrics_snapshot(&self) -> WorkerMetrics {
+        let mut operators = Vec::new();
+        let dataflows = self.dataflows.borrow();
+        let mut indices = dataflows.keys().cloned().collect::<Vec<_>>();
+        indices.sort();
+        for index in indices.iter() {
+            if let Some(operate) = dataflows[index].operate.as_ref() {
+                operate.report_metrics(&mut operators);
+            }
+        }
+
+        let allocator = self.allocator.borrow();
+        let buffers = allocator.buffer_stats();
+        let channels = allocator.channel_bytes().into_iter().map(|channel| ChannelMetrics {
+            id: channel.channel,
+            sent_bytes: channel.sent,
+            received_bytes: channel.received,
+        }).collect();
+
+        WorkerMetrics {
+            dataflows: indices.len(),
+            idle_steps: *self.idle_steps.borrow(),
+            operators,
+            buffers: BufferMetrics {
+                allocated: buffers.allocated,
+                recycled: buffers.recycled,
+                retained_bytes: buffers.retained_bytes,
+            },
+            channels,
+        }
+    }
+
+    /// Returns a snapshot of the operators and channels of a dataflow.
+    ///
+    /// The dataflow is identified by its index, as returned by `next_dataflow_index` before it
+    /// was constructed, and `None` is returned if it is not installed. The snapshot is not
+    /// updated as the dataflow runs; operators that have shut down are still reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// timely::execute_from_args(::std::env::args(), |worker| {
+    ///
+    ///     use timely::dataflow::operators::{ToStream, Inspect};
+    ///
+    ///     let index = worker.next_dataflow_index();
+    ///     worker.dataflow::<usize,_,_>(|scope| {
+    ///         (0 .. 10)
+    ///             .to_stream(scope)
+    ///             .inspect(|x| println!("{:?}", x));
+    ///     });
+    ///
+    ///     let topology = worker.dataflow_topology(index).unwrap();
+    ///     for operator in topology.operators.iter() {
+    ///         println!("{:?}\t{}", operator.path, operator.name);
+    ///     }
+    /// });
+    /// ```
+    pub fn dataflow_topology(&self, dataflow_index: usize) -> Option<Topology> {
+        let dataflows = self.dataflows.borrow();
+        let wrapper = dataflows.get(&dataflow_index)?;
+        let mut topology = Topology::default();
+        if let Some(operate) = wrapper.operate.as_ref() {
+            // The dataflow itself appears as an operator without inputs or outputs.
+            topology.operators.push(TopologyOperator {
+                id: wrapper.identifier,
+                name: operate.name().to_owned(),
+                path: operate.path().to_vec(),
+                inputs: 0,
+                outputs: 0,
+                connectivity: Vec::new(),
+            });
+            operate.report_topology(&mut topology);
+        }
+        let exchange_channels = self.exchange_channels.borrow();
+        for channel in topology.channels.iter_mut() {
+            channel.exchange = channel.id.map_or(false, |id| exchange_channels.contains(&id));
+        }
+        Some(topology)
+    }
+
+    /// Renders a dataflow as a Graphviz DOT graph.
+    ///
+    /// Nodes are operators, labeled with their names and identifiers, and edges are channels,
+    /// labeled with their identifiers. Channels that exchange data between workers are dashed.
+    /// Nested scopes appear as nodes of their own, connected to the operators they contain by
+    /// the channels that enter and leave them. Returns `None` if the dataflow is not installed.
+    ///
+    /// This is a rendering of `Self::dataflow_topology`, and does not require logging.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// timely::execute_from_args(::std::env::args(), |worker| {
+    ///
+    ///     use timely::dataflow::operators::{ToStream, Exchange, Inspect};
+    ///
+    ///     let index = worker.next_dataflow_index();
+    ///     worker.dataflow::<usize,_,_>(|scope| {
+    ///         (0 .. 10)
+    ///             .to_stream(scope)
+    ///             .exchange(|x| *x)
+    ///             .inspect(|x| println!("{:?}", x));
+    ///     });
+    ///
+    ///     let dot = worker.render_dot(index).unwrap();
+    ///     assert!(dot.starts_with("digraph"));
+    /// });
+    /// ```
+    pub fn render_dot(&self, dataflow_index: usize) -> Option<String> {
+        self.dataflow_topology(dataflow_index).map(|topology| topology.to_dot())
+    }
+
     /// True if there is at least one dataflow under management.
     pub fn has_dataflows(&self) -> bool {
         !self.dataflows.borrow().is_empty()
@@ -727,6 +1355,238 @@ impl<A: Allocate> Clone for Worker<A> {
             activations: self.activations.clone(),
             active_dataflows: Vec::new(),
             temp_channel_ids: self.temp_channel_ids.clone(),
+            released_dataflows: self.released_dataflows.clone(),
+            idle_steps: self.idle_steps.clone(),
+            exchange_channels: self.exchange_channels.clone(),
+            resources: self.resources.clone(),
+            panic_guard: self.panic_guard.clone(),
+        }
+    }
+}
+
+/// Operators that may be responsible for a stall, as reported by `Worker::detect_stall`.
+#[derive(Debug, Clone)]
+pub struct StallReport {
+    /// The number of consecutive steps in which the worker scheduled no dataflow.
+    pub idle_steps: usize,
+    /// Operators with non-empty input frontiers or held capabilities.
+    pub operators: Vec<StalledOperator>,
+}
+
+/// An operator reported by `Worker::detect_stall`.
+///
+/// Timestamps differ between scopes, and so frontiers and capabilities are reported in their
+/// `Debug` form, one entry per port.
+#[derive(Debug, Clone)]
+pub struct StalledOperator {
+    /// The worker-unique identifier of the operator, as used in logging.
+    pub id: usize,
+    /// The name of the operator.
+    pub name: String,
+    /// The address of the operator.
+    pub path: Vec<usize>,
+    /// The frontier at each input of the operator.
+    pub input_frontiers: Vec<String>,
+    /// The capabilities held at each output of the operator.
+    pub capabilities: Vec<String>,
+}
+
+/// Progress, memory and channel metrics of a worker, as returned by `Worker::metrics_snapshot`.
+///
+/// With the `serde-metrics` feature, metrics implement `Serialize` and `Deserialize`.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde-metrics", derive(Serialize, Deserialize))]
+pub struct WorkerMetrics {
+    /// The number of dataflows installed in the worker.
+    pub dataflows: usize,
+    /// The number of consecutive steps in which the worker scheduled no dataflow.
+    pub idle_steps: usize,
+    /// The operators of all dataflows, including nested scopes and their contents.
+    pub operators: Vec<OperatorMetrics>,
+    /// The buffers that receive data from other processes, shared by the workers of a process.
+    pub buffers: BufferMetrics,
+    /// The bytes each channel has exchanged with other processes, in order of identifier.
+    pub channels: Vec<ChannelMetrics>,
+}
+
+/// Progress metrics of an operator, as reported in [WorkerMetrics].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde-metrics", derive(Serialize, Deserialize))]
+pub struct OperatorMetrics {
+    /// The worker-unique identifier of the operator, as used in logging.
+    pub id: usize,
+    /// Whether the operator has shut down.
+    pub complete: bool,
+    /// The number of elements in the frontier at each input of the operator.
+    pub frontier_sizes: Vec<usize>,
+    /// The number of elements in the frontier of capabilities held at each output.
+    pub capability_sizes: Vec<usize>,
+}
+
+/// Counts of the buffers that receive data from other processes, as reported in [WorkerMetrics].
+///
+/// Only workers of a cluster receive data from other processes, and other workers report zeros.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde-metrics", derive(Serialize, Deserialize))]
+pub struct BufferMetrics {
+    /// The number of buffers allocated afresh.
+    pub allocated: usize,
+    /// The number of buffers reused once all references to their previous contents were dropped.
+    pub recycled: usize,
+    /// The bytes of the buffers currently retained for receiving.
+    pub retained_bytes: usize,
+}
+
+/// The bytes a channel has exchanged with other processes, as reported in [WorkerMetrics].
+///
+/// Only channels that exchange data between processes are reported, until their receiving
+/// operator shuts down. Bytes are those of serialized messages, without framing.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde-metrics", derive(Serialize, Deserialize))]
+pub struct ChannelMetrics {
+    /// The identifier of the channel, as used in logging.
+    pub id: usize,
+    /// The bytes sent by this worker to workers of other processes.
+    pub sent_bytes: usize,
+    /// The bytes received by this worker from workers of other processes.
+    pub received_bytes: usize,
+}
+
+/// A snapshot of the structure of a dataflow, as returned by `Worker::dataflow_topology`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Topology {
+    /// The operators of the dataflow, including nested scopes and their contents.
+    pub operators: Vec<TopologyOperator>,
+    /// The channels connecting operators within each scope.
+    pub channels: Vec<TopologyChannel>,
+}
+
+/// An operator reported in a [Topology].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopologyOperator {
+    /// The worker-unique identifier of the operator, as used in logging.
+    pub id: usize,
+    /// The name of the operator.
+    pub name: String,
+    /// The address of the operator.
+    pub path: Vec<usize>,
+    /// The number of inputs of the operator.
+    pub inputs: usize,
+    /// The number of outputs of the operator.
+    pub outputs: usize,
+    /// For each input and output, the `Debug` form of the summaries connecting them.
+    ///
+    /// An empty list of summaries means the input is not connected to the output.
+    pub connectivity: Vec<Vec<String>>,
+}
+
+impl Topology {
+    /// Renders the topology as a Graphviz DOT graph, as described at `Worker::render_dot`.
+    pub fn to_dot(&self) -> String {
+        use std::fmt::Write;
+
+        fn node(path: &[usize]) -> String {
+            let path = path.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+            format!("op_{}", path.join("_"))
+        }
+
+        // Writing to a `String` cannot fail.
+        let mut dot = String::new();
+        writeln!(dot, "digraph dataflow {{").unwrap();
+        for operator in self.operators.iter() {
+            let label = format!("{} ({})", operator.name, operator.id);
+            writeln!(dot, "    {} [label={:?}];", node(&operator.path), label).unwrap();
+        }
+        for channel in self.channels.iter() {
+            let mut source = channel.scope.clone();
+            if channel.source.0 > 0 { source.push(channel.source.0); }
+            let mut target = channel.scope.clone();
+            if channel.target.0 > 0 { target.push(channel.target.0); }
+            let label = channel.id.map(|id| id.to_string()).unwrap_or_default();
+            let style = if channel.exchange { "dashed" } else { "solid" };
+            writeln!(dot, "    {} -> {} [label={:?}, style={}];", node(&source), node(&target), label, style).unwrap();
+        }
+        writeln!(dot, "}}").unwrap();
+        dot
+    }
+}
+
+/// A channel reported in a [Topology].
+///
+/// Endpoints are `(operator, port)` pairs local to `scope`, where operator index zero is the
+/// scope itself: its inputs act as sources within the scope, and its outputs as targets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopologyChannel {
+    /// The identifier of the channel, if it was connected through a stream.
+    pub id: Option<usize>,
+    /// True if the channel was allocated to exchange data between workers.
+    pub exchange: bool,
+    /// The address of the scope containing the channel.
+    pub scope: Vec<usize>,
+    /// The operator index and output port the channel leaves from.
+    pub source: (usize, usize),
+    /// The operator index and input port the channel arrives at.
+    pub target: (usize, usize),
+}
+
+/// A token that drops a dataflow when it is released.
+///
+/// Tokens are returned by `Worker::dataflow_with_token`. Releasing the token, by dropping it or
+/// calling `shutdown`, drops the dataflow the next time the worker steps.
+#[derive(Debug)]
+pub struct DataflowToken {
+    dataflow_index: usize,
+    released: Option<Arc<ReleasedDataflows>>,
+}
+
+/// Indices of dataflows whose tokens have been released, shared between a worker and its tokens.
+#[derive(Debug, Default)]
+struct ReleasedDataflows {
+    /// Set whenever `indices` may be non-empty, so that the worker need not lock at every step.
+    dirty: AtomicBool,
+    indices: Mutex<Vec<usize>>,
+}
+
+impl ReleasedDataflows {
+    /// Records that the token of dataflow `index` has been released.
+    fn release(&self, index: usize) {
+        // A poisoned lock means the worker has panicked, and the dataflow is gone regardless.
+        if let Ok(mut indices) = self.indices.lock() {
+            indices.push(index);
+            self.dirty.store(true, Ordering::Release);
+        }
+    }
+
+    /// Takes the indices of the dataflows released since the last call.
+    fn take(&self) -> Vec<usize> {
+        if self.dirty.swap(false, Ordering::Acquire) {
+            std::mem::take(&mut *self.indices.lock().expect("released dataflows lock poisoned"))
+        }
+        else {
+            Vec::new()
+        }
+    }
+}
+
+impl DataflowToken {
+    /// The index of the dataflow, as used by `Worker::drop_dataflow`.
+    pub fn dataflow_index(&self) -> usize {
+        self.dataflow_index
+    }
+
+    /// Releases the token, dropping the dataflow.
+    pub fn shutdown(self) { }
+
+    /// Discards the token without dropping the dataflow, which then runs until it completes.
+    pub fn detach(mut self) {
+        self.released = None;
+    }
+}
+
+impl Drop for DataflowToken {
+    fn drop(&mut self) {
+        if let Some(released) = self.released.take() {
+            released.release(self.dataflow_index);
         }
     }
 }
@@ -737,6 +1597,8 @@ struct Wrapper {
     operate: Option<Box<dyn Schedule>>,
     resources: Option<Box<dyn Any>>,
     channel_ids: Vec<usize>,
+    /// The moment the dataflow was last scheduled, if it has been under the staleness policy.
+    last_scheduled: Option<Instant>,
 }
 
 impl Wrapper {
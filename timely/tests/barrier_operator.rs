@@ -0,0 +1,20 @@
+mod common;
+
+use timely::dataflow::operators::core::Barrier;
+use timely::dataflow::operators::Exchange;
+
+#[test]
+fn barrier_releases_each_round() {
+    // Only the first worker supplies records, but every worker must signal each round.
+    let received = common::route_rounds(1, 3, 5, |index, round| {
+        if index == 0 { (round * 6 .. (round + 1) * 6).collect() } else { Vec::new() }
+    }, |stream| {
+        stream.exchange(|x: &u64| *x).barrier()
+    });
+    common::assert_routed(&received, 30, |x| (x % 3) as usize);
+
+    let mut released = received.concat();
+    released.sort();
+    let expected = (0..30u64).map(|x| (x / 6, x)).collect::<Vec<_>>();
+    assert_eq!(released, expected);
+}
@@ -0,0 +1,40 @@
+use std::sync::{Arc, Mutex};
+
+use timely::dataflow::InputHandleCore;
+use timely::dataflow::channels::bytes::BytesBatch;
+use timely::dataflow::operators::{Exchange, Inspect, Probe};
+use timely::dataflow::operators::core::Input;
+
+#[test]
+fn bytes_batch_exchange_in_memory() {
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&received);
+
+    let results = timely::testing::run_in_memory(2, 2, move |worker| {
+        let index = worker.index();
+        let sink = Arc::clone(&sink);
+        let mut input = InputHandleCore::<u64, BytesBatch>::new();
+        let probe = worker.dataflow(|scope| {
+            scope
+                .input_from(&mut input)
+                .exchange(|payload| payload[0] as u64)
+                .inspect(move |payload| sink.lock().unwrap().push((index, payload.to_vec())))
+                .probe()
+        });
+
+        for value in 0..100u8 {
+            let payload: Vec<u8> = std::iter::once(value).chain(std::iter::repeat(index as u8).take(value as usize)).collect();
+            input.send(payload);
+        }
+        input.advance_to(1);
+        worker.step_while(|| probe.less_than(input.time()));
+    });
+    assert!(results.iter().all(|result| result.is_ok()));
+
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 400);
+    for (index, payload) in received.iter() {
+        assert_eq!((payload[0] % 4) as usize, *index);
+        assert_eq!(payload.len(), payload[0] as usize + 1);
+    }
+}
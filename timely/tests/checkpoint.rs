@@ -0,0 +1,50 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use timely::bytes::arc::Bytes;
+use timely::checkpoint::{self, Checkpoint};
+use timely::dataflow::operators::{Inspect, ToStream};
+
+struct Values(Vec<u8>);
+
+impl Checkpoint for Values {
+    fn snapshot(&self) -> Bytes { Bytes::from(self.0.clone()) }
+    fn restore(&mut self, state: Bytes) { self.0 = state.to_vec(); }
+}
+
+#[test]
+fn restore_on_rebuild() {
+    timely::execute_directly(|worker| {
+        let values = Rc::new(RefCell::new(Values(Vec::new())));
+        let index = worker.next_dataflow_index();
+        worker.dataflow::<u64, _, _>(|scope| {
+            checkpoint::register(scope, "values", values.clone());
+            let values = values.clone();
+            (0..5u8).to_stream(scope).inspect(move |x| values.borrow_mut().0.push(*x));
+        });
+
+        let snapshot = worker.checkpoint();
+        assert_eq!(&snapshot.states["values"][..], &[0, 1, 2, 3, 4]);
+
+        worker.drop_dataflow(index);
+        drop(values);
+        worker.restore(snapshot);
+
+        let restored = Rc::new(RefCell::new(Values(Vec::new())));
+        worker.dataflow::<u64, _, _>(|scope| checkpoint::register(scope, "values", restored.clone()));
+        assert_eq!(restored.borrow().0, vec![0, 1, 2, 3, 4]);
+    });
+}
+
+#[test]
+#[should_panic(expected = "state \"values\" is already registered")]
+fn duplicate_names() {
+    timely::execute_directly(|worker| {
+        let first = Rc::new(RefCell::new(Values(Vec::new())));
+        let second = Rc::new(RefCell::new(Values(Vec::new())));
+        worker.dataflow::<u64, _, _>(|scope| {
+            checkpoint::register(scope, "values", first.clone());
+            checkpoint::register(scope, "values", second.clone());
+        });
+    });
+}
@@ -0,0 +1,16 @@
+mod common;
+
+use timely::dataflow::operators::Exchange;
+
+#[test]
+fn exchange_across_in_memory_processes() {
+    let received = common::route_rounds(2, 2, 1, |index, _| (0..100).map(|value| value * 4 + index as u64).collect(), |stream| {
+        stream.exchange(|x: &u64| *x)
+    });
+    assert_eq!(received.len(), 4);
+    common::assert_routed(&received, 400, |x| (x % 4) as usize);
+
+    let mut records = received.concat().into_iter().map(|(_, x)| x).collect::<Vec<_>>();
+    records.sort();
+    assert!(records.into_iter().eq(0..400));
+}
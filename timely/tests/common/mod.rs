@@ -0,0 +1,73 @@
+//! Helpers shared by the integration tests of record routing.
+
+// Not every test uses every helper.
+#![allow(dead_code)]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use timely::communication::Allocator;
+use timely::dataflow::operators::{Input, Inspect, Probe};
+use timely::dataflow::scopes::Child;
+use timely::dataflow::{InputHandle, Stream};
+use timely::worker::Worker;
+
+/// A stream of the dataflows built by `route_rounds`.
+pub type TestStream<'a> = Stream<Child<'a, Worker<Allocator>, u64>, u64>;
+
+/// Sends the records `send(index, round)` of each worker in each of `rounds` rounds through
+/// `route`, and returns the `(time, record)` pairs each worker received, indexed by worker.
+///
+/// The records of round `r` are sent at time `r`, and each worker steps until its dataflow has
+/// completed a round before sending the next. The workers run in a single process if `processes`
+/// is one, and otherwise in simulated processes that communicate through in-memory streams.
+pub fn route_rounds<S, R>(processes: usize, threads: usize, rounds: u64, send: S, route: R) -> Vec<Vec<(u64, u64)>>
+where
+    S: Fn(usize, u64)->Vec<u64>+Send+Sync+'static,
+    R: for<'a> Fn(&TestStream<'a>)->TestStream<'a>+Send+Sync+'static,
+{
+    let logic = move |worker: &mut Worker<Allocator>| {
+        let index = worker.index();
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&received);
+        let mut input = InputHandle::new();
+        let probe = worker.dataflow::<u64, _, _>(|scope| {
+            route(&scope.input_from(&mut input))
+                .inspect_time(move |time, x| sink.borrow_mut().push((*time, *x)))
+                .probe()
+        });
+
+        for round in 0..rounds {
+            for value in send(index, round) {
+                input.send(value);
+            }
+            input.advance_to(round + 1);
+            worker.step_while(|| probe.less_than(input.time()));
+        }
+        drop(input);
+        worker.step_while(|| !probe.done());
+
+        received.take()
+    };
+    let results = if processes == 1 {
+        timely::testing::run(threads, logic)
+    }
+    else {
+        timely::testing::run_in_memory(processes, threads, logic)
+    };
+    results.into_iter()
+           .enumerate()
+           .map(|(index, result)| result.unwrap_or_else(|error| panic!("worker {} failed: {}", index, error)))
+           .collect()
+}
+
+/// Asserts that each worker received `count` records in total, and only records for which
+/// `worker` names it.
+pub fn assert_routed<W: Fn(u64)->usize>(received: &[Vec<(u64, u64)>], count: usize, worker: W) {
+    assert_eq!(received.iter().map(|records| records.len()).sum::<usize>(), count);
+    for (index, records) in received.iter().enumerate() {
+        for (_time, x) in records.iter() {
+            assert_eq!(worker(*x), index, "record {} at worker {}", x, index);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use timely::dataflow::operators::{Exchange, Input, Probe};
+use timely::dataflow::InputHandle;
+use timely::{CommunicationConfig, Config, WorkerConfig};
+
+fn drop_dataflow_helper(communication: CommunicationConfig) {
+    let config = Config { communication, worker: WorkerConfig::default() };
+    timely::execute(config, |worker| {
+        for round in 0..100u64 {
+            let mut input = InputHandle::new();
+            let index = worker.next_dataflow_index();
+            worker.dataflow::<u64, _, _>(|scope| {
+                scope
+                    .input_from(&mut input)
+                    .exchange(|x: &u64| *x)
+                    .probe();
+            });
+
+            // Send data to both workers, and drop the dataflow before it can be received.
+            for value in 0..10 {
+                input.send(value + round);
+            }
+            input.advance_to(1);
+            worker.step();
+            drop(input);
+            worker.drop_dataflow(index);
+            worker.step();
+        }
+
+        assert!(worker.installed_dataflows().is_empty());
+    })
+    .unwrap();
+}
+
+#[test]
+fn drop_dataflow_with_messages_in_flight() {
+    drop_dataflow_helper(CommunicationConfig::Process(2));
+}
+
+#[test]
+fn drop_dataflow_with_messages_in_flight_process_binary() {
+    drop_dataflow_helper(CommunicationConfig::ProcessBinary(2));
+}
+
+#[test]
+fn drop_dataflow_with_messages_in_flight_cluster_in_memory() {
+    drop_dataflow_helper(CommunicationConfig::ClusterInMemory { processes: 2, threads: 1 });
+}
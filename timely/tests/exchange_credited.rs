@@ -0,0 +1,30 @@
+mod common;
+
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::generic::operator::Operator;
+
+fn credited_exchange_helper(processes: usize, threads: usize) {
+    let received = common::route_rounds(processes, threads, 10, |_, _| (0..1000).collect(), |stream| {
+        stream.unary(Exchange::new(|x: &u64| *x).with_credits(10), "Credited", |_, _| {
+            let mut vector = Vec::new();
+            move |input, output| {
+                input.for_each(|time, data| {
+                    data.swap(&mut vector);
+                    output.session(&time).give_container(&mut vector);
+                });
+            }
+        })
+    });
+    let peers = processes * threads;
+    common::assert_routed(&received, 10_000 * peers, |x| (x as usize) % peers);
+}
+
+#[test]
+fn credited_exchange_process() {
+    credited_exchange_helper(1, 3);
+}
+
+#[test]
+fn credited_exchange_cluster_in_memory() {
+    credited_exchange_helper(2, 2);
+}
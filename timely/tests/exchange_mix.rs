@@ -0,0 +1,58 @@
+use std::sync::{Arc, Mutex};
+
+use timely::dataflow::channels::pact::{fibonacci_mix, Exchange};
+use timely::dataflow::operators::generic::operator::Operator;
+use timely::dataflow::operators::Input;
+use timely::dataflow::InputHandle;
+use timely::Config;
+
+/// Routes the keys `0, 3, 6, ...` among six workers, and returns the number each worker receives.
+fn received_per_worker(mix: Option<fn(u64) -> u64>) -> Vec<usize> {
+    let peers = 6;
+    let counts = Arc::new(Mutex::new(vec![0; peers]));
+    let sink = Arc::clone(&counts);
+
+    timely::execute(Config::process(peers), move |worker| {
+        let index = worker.index();
+        let sink = Arc::clone(&sink);
+        let mut input = InputHandle::new();
+        worker.dataflow::<u64, _, _>(|scope| {
+            let pact = Exchange::new(|x: &u64| *x);
+            let pact = match mix { Some(mix) => pact.with_mixer(mix), None => pact };
+            scope
+                .input_from(&mut input)
+                .sink(pact, "Count", move |input| {
+                    input.for_each(|_time, data| {
+                        sink.lock().unwrap()[index] += data.len();
+                    });
+                });
+        });
+
+        // Only the first worker supplies keys, so each key is routed once.
+        if index == 0 {
+            for key in 0..6_000u64 {
+                input.send(3 * key);
+            }
+        }
+        // Dropping the input closes it, and the worker then runs the dataflow to completion.
+    })
+    .unwrap();
+
+    let counts = counts.lock().unwrap();
+    counts.clone()
+}
+
+#[test]
+fn modulo_skews_strided_keys() {
+    // Multiples of three only ever land on workers zero and three.
+    assert_eq!(received_per_worker(None), vec![3_000, 0, 0, 3_000, 0, 0]);
+}
+
+#[test]
+fn fibonacci_mix_spreads_strided_keys() {
+    let counts = received_per_worker(Some(fibonacci_mix));
+    assert_eq!(counts.iter().sum::<usize>(), 6_000);
+    for (index, count) in counts.iter().enumerate() {
+        assert!(*count > 900 && *count < 1_100, "worker {} received {} of 6000 keys", index, count);
+    }
+}
@@ -0,0 +1,28 @@
+mod common;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use timely::dataflow::operators::{Exchange, ToStream};
+
+#[test]
+fn pinned_keys_go_to_their_worker() {
+    let pins: Arc<HashMap<u64, usize>> = Arc::new(vec![(1, 2), (4, 2), (8, 0)].into_iter().collect());
+    let received = common::route_rounds(1, 3, 1, |_, _| (0..120).collect(), move |stream| {
+        stream.exchange_pinned(|x: &u64| *x % 12, Arc::clone(&pins), |k| *k)
+    });
+    common::assert_routed(&received, 360, |x| match x % 12 {
+        1 | 4 => 2,
+        8 => 0,
+        key => (key % 3) as usize,
+    });
+}
+
+#[test]
+#[should_panic(expected = "key pinned to worker 1, but there are only 1 workers")]
+fn pinned_worker_out_of_range() {
+    let pins: Arc<HashMap<u64, usize>> = Arc::new(vec![(0, 1)].into_iter().collect());
+    timely::example(move |scope| {
+        (0..10u64).to_stream(scope).exchange_pinned(|x| *x, pins, |k| *k);
+    });
+}
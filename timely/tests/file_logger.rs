@@ -0,0 +1,36 @@
+use std::time::Duration;
+
+use timely::logging::FileLogger;
+
+/// Writes seven flushes of 100 bytes through a logger rotating at 250 bytes and keeping two
+/// rotated files, and checks which flushes survive in which files.
+#[test]
+fn rotation_keeps_newest_files() {
+    let directory = std::env::temp_dir().join(format!("timely-file-logger-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    let mut logger = FileLogger::new(&directory, "rotation", 0, |buffer: &mut Vec<u8>, event: &(Duration, u8, ())| {
+        buffer.extend(std::iter::repeat(b'a' + event.1).take(100));
+    })
+    .unwrap()
+    .with_rotation(250, 2);
+
+    for tag in 0 .. 7u8 {
+        logger.publish_batch(&Duration::default(), &mut vec![(Duration::default(), tag, ())]).unwrap();
+        logger.flush().unwrap();
+    }
+    let path = logger.path().to_owned();
+    drop(logger);
+
+    // The contents of a file holding the flushes with the supplied tags.
+    let expected = |tags: &[u8]| tags.iter().flat_map(|tag| std::iter::repeat(b'a' + tag).take(100)).collect::<Vec<_>>();
+    let rotated = |index: usize| directory.join(format!("rotation.0.log.{}", index));
+
+    assert_eq!(path, directory.join("rotation.0.log"));
+    assert_eq!(std::fs::read(&path).unwrap(), expected(&[6]));
+    assert_eq!(std::fs::read(rotated(1)).unwrap(), expected(&[4, 5]));
+    assert_eq!(std::fs::read(rotated(2)).unwrap(), expected(&[2, 3]));
+    assert!(!rotated(3).exists());
+
+    std::fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,44 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use timely::dataflow::operators::{Input, Inspect, Map, Probe};
+use timely::dataflow::InputHandle;
+use timely::Config;
+
+#[test]
+fn flat_map_bounded_exploding_iterator() {
+    timely::execute(Config::thread(), |worker| {
+        let mut input = InputHandle::new();
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&seen);
+        let probe = worker.dataflow::<u64, _, _>(|scope| {
+            scope
+                .input_from(&mut input)
+                .flat_map_bounded(1000, |x: u64| (0..1_000_000u64).map(move |y| (x, y)))
+                .inspect_time(move |time, (x, y)| {
+                    let mut seen = sink.borrow_mut();
+                    if *y == 0 { seen.push((*time, *x, 0)); }
+                    seen.last_mut().unwrap().2 += 1;
+                })
+                .probe()
+        });
+
+        input.send(0);
+        input.send(1);
+        input.advance_to(1);
+        input.send(2);
+        input.close();
+
+        // Each step produces at most one bounded batch of output.
+        worker.step();
+        assert!(seen.borrow().iter().map(|(_, _, count)| count).sum::<u64>() <= 1000);
+
+        while !probe.done() {
+            worker.step();
+        }
+
+        let expected = vec![(0, 0, 1_000_000), (0, 1, 1_000_000), (1, 2, 1_000_000)];
+        assert_eq!(*seen.borrow(), expected);
+    })
+    .unwrap();
+}
@@ -0,0 +1,64 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use timely::container::CapacityContainerBuilder;
+use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::operators::Input;
+use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use timely::logging::{HeldCapabilitiesEvent, TimelyEvent};
+
+/// Runs an operator that keeps its capability after its input completes, with held capabilities
+/// logged after `schedules` idle schedulings if set, and returns the logged events.
+fn held_events(schedules: Option<usize>) -> Vec<HeldCapabilitiesEvent> {
+    let mut config = timely::Config::thread();
+    if let Some(schedules) = schedules {
+        config.worker = config.worker.log_held_capabilities(schedules);
+    }
+    let guards = timely::execute(config, |worker| {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let events2 = events.clone();
+        worker.log_register().insert::<TimelyEvent,_>("timely", move |_time, data| {
+            for (_, _, event) in data.drain(..) {
+                if let TimelyEvent::HeldCapabilities(event) = event {
+                    events2.borrow_mut().push(event);
+                }
+            }
+        });
+
+        let (input, token) = worker.dataflow_with_token::<usize,_,_>("Held", |scope| {
+            let (input, stream) = scope.new_input::<u64>();
+            let mut builder = OperatorBuilder::new("Hold".to_owned(), scope.clone());
+            let mut input_handle = builder.new_input(&stream, Pipeline);
+            let (_output, _stream) = builder.new_output::<CapacityContainerBuilder<Vec<u64>>>();
+            builder.build(move |capabilities| {
+                move |_frontiers| {
+                    // Leaks the capability, never dropping or downgrading it.
+                    let _held = &capabilities;
+                    input_handle.for_each(|_time, _data| { });
+                }
+            });
+            input
+        });
+
+        drop(input);
+        for _ in 0 .. 10 {
+            worker.step();
+        }
+        token.shutdown();
+        worker.step();
+        events.take()
+    }).unwrap();
+    guards.join().into_iter().next().unwrap().unwrap()
+}
+
+#[test]
+fn held_capabilities_are_logged() {
+    let events = held_events(Some(1));
+    assert_eq!(events.len(), 1, "{:?}", events);
+    assert_eq!(events[0].held, vec!["[0]".to_string()]);
+}
+
+#[test]
+fn held_capabilities_are_not_logged_by_default() {
+    assert!(held_events(None).is_empty());
+}
@@ -0,0 +1,58 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use timely::dataflow::operators::core::Coalesce;
+use timely::dataflow::operators::{Input, Inspect};
+use timely::dataflow::InputHandle;
+use timely::worker::MemoryPolicy;
+use timely::Config;
+
+/// Sends 100 records at the first time, without advancing it, and returns the number of records
+/// coalesced as well as the bytes of each report after a few steps.
+fn coalesce_with_limit(limit: Option<(usize, MemoryPolicy)>) -> (usize, Vec<usize>) {
+    let mut config = Config::thread();
+    if let Some((bytes, policy)) = limit {
+        config.worker = config.worker.operator_memory_limit(bytes, policy);
+    }
+    let guards = timely::execute(config, |worker| {
+        let sent = Rc::new(RefCell::new(0));
+        let sink = Rc::clone(&sent);
+        let mut input = InputHandle::<u64, u64>::new();
+        worker.dataflow(|scope| {
+            scope.input_from(&mut input)
+                 .coalesce_accounted(1_000_000)
+                 .inspect_batch(move |_time, data| *sink.borrow_mut() += data.len());
+        });
+
+        input.send_batch(&mut (0..100).collect());
+        for _ in 0..10 {
+            worker.step();
+        }
+        let reports = worker.operator_memory();
+        assert!(reports.iter().all(|report| report.name == "Coalesce"));
+        let sent = *sent.borrow();
+        (sent, reports.iter().map(|report| report.bytes).collect())
+    }).unwrap();
+    guards.join().pop().unwrap().unwrap()
+}
+
+#[test]
+fn no_limit_opens_no_account() {
+    assert_eq!(coalesce_with_limit(None), (0, vec![]));
+}
+
+#[test]
+fn log_policy_retains_buffers() {
+    assert_eq!(coalesce_with_limit(Some((64, MemoryPolicy::Log))), (0, vec![800]));
+}
+
+#[test]
+fn flush_policy_releases_buffers() {
+    assert_eq!(coalesce_with_limit(Some((64, MemoryPolicy::Flush))), (100, vec![0]));
+}
+
+#[test]
+#[should_panic(expected = "buffers 800 bytes, exceeding the limit of 64 bytes")]
+fn panic_policy_panics() {
+    coalesce_with_limit(Some((64, MemoryPolicy::Panic)));
+}
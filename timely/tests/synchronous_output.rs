@@ -0,0 +1,63 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use timely::dataflow::InputHandle;
+use timely::dataflow::operators::Input;
+use timely::dataflow::operators::core::{Filter, Inspect, Map, Probe};
+use timely::logging::{StartStop, TimelyEvent};
+
+/// Advances an input without data, and checks that `map`, `filter` and `inspect` are not
+/// scheduled for the resulting frontier changes.
+#[test]
+fn synchronous_operators_ignore_frontier_changes() {
+    timely::execute_directly(|worker| {
+        // Operator names, and the number of times each operator has been scheduled.
+        let names = Rc::new(RefCell::new(HashMap::new()));
+        let schedules = Rc::new(RefCell::new(HashMap::new()));
+        let (names2, schedules2) = (names.clone(), schedules.clone());
+        worker.log_register().insert::<TimelyEvent,_>("timely", move |_time, data| {
+            for (_, _, event) in data.drain(..) {
+                match event {
+                    TimelyEvent::Operates(event) => { names2.borrow_mut().insert(event.id, event.name); },
+                    TimelyEvent::Schedule(event) if event.start_stop == StartStop::Start => {
+                        *schedules2.borrow_mut().entry(event.id).or_insert(0) += 1;
+                    },
+                    _ => { },
+                }
+            }
+        });
+
+        let mut input = InputHandle::new();
+        let probe = worker.dataflow::<u64,_,_>(|scope| {
+            scope.input_from(&mut input)
+                 .map(|x: u64| x + 1)
+                 .container::<Vec<_>>()
+                 .filter(|x| *x % 2 == 0)
+                 .inspect(|_| { })
+                 .probe()
+        });
+
+        // The number of times each synchronous operator has been scheduled.
+        let counts = || {
+            let names = names.borrow();
+            let schedules = schedules.borrow();
+            ["FlatMap", "Filter", "InspectBatch"].iter().map(|name| {
+                let id = names.iter().find(|(_, n)| n == name).map(|(id, _)| *id).expect("operator not logged");
+                schedules.get(&id).cloned().unwrap_or(0)
+            }).collect::<Vec<usize>>()
+        };
+
+        input.send(0);
+        input.advance_to(1);
+        worker.step_while(|| probe.less_than(&1));
+        let before = counts();
+        assert!(before.iter().all(|count| *count > 0), "{:?}", before);
+
+        for round in 2 .. 20 {
+            input.advance_to(round);
+            worker.step_while(|| probe.less_than(&round));
+        }
+        assert_eq!(counts(), before);
+    });
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "async")]
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use futures_util::stream::StreamExt;
+
+use timely::dataflow::operators::core::ToStreamAsync;
+use timely::dataflow::operators::{Inspect, Probe};
+
+#[test]
+fn ready_stream_yields_to_the_worker() {
+    timely::execute_directly(|worker| {
+        let polled = Rc::new(Cell::new(0));
+        let source = Rc::clone(&polled);
+        let count = Rc::new(Cell::new(0));
+        let sink = Rc::clone(&count);
+        let probe = worker.dataflow::<u64, _, _>(|scope| {
+            futures_util::stream::iter(0..1_000_000u64)
+                .inspect(move |_| source.set(source.get() + 1))
+                .to_stream_async(scope, |_| 0)
+                .container::<Vec<_>>()
+                .inspect(move |_| sink.set(sink.get() + 1))
+                .probe()
+        });
+
+        worker.step();
+        assert!(polled.get() > 0);
+        assert!(polled.get() < 1_000_000, "the operator polled the whole stream in one step");
+
+        worker.step_while(|| !probe.done());
+        assert_eq!(count.get(), 1_000_000);
+    });
+}
+
+#[test]
+fn items_arrive_at_their_times() {
+    use timely::dataflow::operators::core::Capture;
+    use timely::dataflow::operators::core::capture::Extract;
+
+    let data = timely::example(|scope| {
+        futures_util::stream::iter(0..10u64)
+            .to_stream_async(scope, |x| *x / 5)
+            .container::<Vec<_>>()
+            .capture()
+    });
+
+    assert_eq!(data.extract(), vec![(0, vec![0, 1, 2, 3, 4]), (1, vec![5, 6, 7, 8, 9])]);
+}
@@ -0,0 +1,29 @@
+use timely::dataflow::operators::{Exchange, Input, Probe};
+use timely::dataflow::InputHandle;
+
+#[test]
+fn metrics_count_exchanged_bytes() {
+    let results = timely::testing::run_in_memory(2, 1, |worker| {
+        let mut input = InputHandle::new();
+        let probe = worker.dataflow::<u64, _, _>(|scope| {
+            scope.input_from(&mut input)
+                 .exchange(|x: &u64| *x)
+                 .probe()
+        });
+
+        for value in 0..1000u64 {
+            input.send(value);
+        }
+        input.advance_to(1);
+        worker.step_while(|| probe.less_than(input.time()));
+        worker.metrics_snapshot()
+    });
+
+    for result in results {
+        let metrics = result.unwrap();
+        assert_eq!(metrics.dataflows, 1);
+        assert!(metrics.buffers.allocated >= 1, "{:?}", metrics.buffers);
+        // Half of the records go to the other process, in the data channel.
+        assert!(metrics.channels.iter().any(|channel| channel.sent_bytes >= 500 * 8 && channel.received_bytes >= 500 * 8), "{:?}", metrics.channels);
+    }
+}
@@ -0,0 +1,47 @@
+use timely::Config;
+
+#[test]
+fn join_reports_panic_message() {
+    let guards = timely::execute(Config::process(2), |worker| {
+        if worker.index() == 1 {
+            panic!("worker {} gave up", worker.index());
+        }
+    })
+    .unwrap();
+
+    let results = guards.join();
+    assert!(results[0].is_ok());
+    let error = results[1].as_ref().unwrap_err();
+    assert!(error.contains("worker 1 gave up"), "unexpected error: {}", error);
+}
+
+#[test]
+fn panic_handler_reports_operator() {
+    use std::sync::{Arc, Mutex};
+    use timely::dataflow::operators::{Inspect, ToStream};
+
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&reports);
+
+    let mut config = Config::thread();
+    config.worker = config.worker.panic_handler(move |report| sink.lock().unwrap().push(report.clone()));
+
+    let guards = timely::execute(config, |worker| {
+        worker.dataflow::<u64, _, _>(|scope| {
+            (0..10u64)
+                .to_stream(scope)
+                .inspect(|x| if *x == 5 { panic!("found five") });
+        });
+    })
+    .unwrap();
+
+    let results = guards.join();
+    assert!(results[0].as_ref().unwrap_err().contains("found five"));
+
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].worker, 0);
+    assert_eq!(reports[0].message.as_deref(), Some("found five"));
+    let operator = reports[0].operator.as_ref().expect("panic outside of an operator");
+    assert_eq!(operator.name, "InspectBatch");
+}